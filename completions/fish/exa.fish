@@ -1,15 +1,34 @@
 # Meta-stuff
 complete -c exa -s 'v' -l 'version' -d "Show version of exa"
 complete -c exa -s '?' -l 'help'    -d "Show list of command-line options"
+complete -c exa        -l 'strict'  -d "Complain about repeated or useless options"
 
 # Display options
 complete -c exa -s '1' -l 'oneline'      -d "Display one entry per line"
 complete -c exa -s 'l' -l 'long'         -d "Display extended file metadata as a table"
 complete -c exa -s 'G' -l 'grid'         -d "Display entries in a grid"
 complete -c exa -s 'x' -l 'across'       -d "Sort the grid across, rather than downwards"
+complete -c exa        -l 'down'         -d "Sort the grid downwards, overriding --across"
+complete -c exa        -l 'strict-grid'  -d "Keep to a one-column grid when names are too wide"
+complete -c exa        -l 'wrap-names'   -d "Break names too wide for the terminal over several lines"
 complete -c exa -s 'R' -l 'recurse'      -d "Recurse into directories"
 complete -c exa -s 'T' -l 'tree'         -d "Recurse into directories as a tree"
+complete -c exa        -l 'separate-trees' -d "List each directory argument as its own tree"
+complete -c exa        -l 'stat'         -d "List every detail of a single file"
+complete -c exa        -l 'complete-dirs' -d "List directory names separated by NULs"
+complete -c exa        -l 'oneliner-summary' -d "Print one line of file counts and sizes"
 complete -c exa -s 'F' -l 'classify'     -d "Display type indicator by file names"
+complete -c exa        -l 'classify-chars' -d "Only show these type indicators" -x -a "dir exec link pipe socket"
+complete -c exa        -l 'mark-empty'   -d "Mark empty files and directories"
+complete -c exa        -l 'mark-unreadable' -d "Mark files you don't have permission to read"
+complete -c exa        -l 'summary'      -d "Show each directory's entry count and total size"
+complete -c exa        -l 'filesystem-info' -d "Show the size and free space of each directory's filesystem"
+complete -c exa        -l 'symlink-target-color' -d "Colour symlinks like the files they point to"
+complete -c exa        -l 'short-symlink-targets' -d "Show just the names of symlink targets"
+complete -c exa        -l 'relative-to'  -d "Show each file's path relative to a directory" -x -a "(__fish_complete_directories)"
+complete -c exa        -l 'quote'        -d "Quote file names so they can be pasted into a shell"
+complete -c exa        -l 'bidi-isolate' -d "Keep right-to-left names from reordering the columns"
+complete -c exa        -l 'output'       -d "Write the listing to a file" -r
 complete -c exa        -l 'color' \
                        -l 'colour'       -d "When to use terminal colours" -x -a "
     always\t'Always use colour'
@@ -18,22 +37,54 @@ complete -c exa        -l 'color' \
 "
 complete -c exa        -l 'color-scale' \
                        -l 'colour-scale' -d "Highlight levels of file sizes distinctly"
+complete -c exa        -l 'color-symlink-path' \
+                       -l 'colour-symlink-path' -d "Colour the directories in symlink targets' paths"
+complete -c exa        -l 'color-by' \
+                       -l 'colour-by'    -d "What to colour files by" -x -a "
+    extension\t'Colour files by their names'
+    content\t'Colour files by sniffing their contents'
+"
+complete -c exa        -l 'color-scheme' \
+                       -l 'colour-scheme' -d "Colours for the terminal's background" -x -a "
+    dark\t'Colours for a dark background'
+    light\t'Colours for a light background'
+    auto\t'Guess the background from COLORFGBG'
+"
+complete -c exa        -l 'paging'       -d "When to send output through a pager" -x -a "
+    always\t'Always page output to a terminal'
+    auto\t'Page output if it would not fit on the screen'
+    never\t'Never use a pager'
+"
+complete -c exa        -l 'watch'        -d "List the files again whenever they change"
+complete -c exa        -l 'group-by'     -d "List files in labelled sections" -x -a "
+    extension\t'One section per file extension'
+    none\t'List every file together'
+"
 complete -c exa        -l 'icons'        -d "Display icons"
 complete -c exa        -l 'no-icons'     -d "Don't display icons"
 
 # Filtering and sorting options
 complete -c exa -l 'group-directories-first' -d "Sort directories before other files"
+complete -c exa -l 'dotfiles-last' -d "Sort dotfiles after other files"
 complete -c exa -l 'git-ignore'           -d "Ignore files mentioned in '.gitignore'"
 complete -c exa -s 'a' -l 'all'       -d "Show hidden and 'dot' files"
 complete -c exa -s 'd' -l 'list-dirs' -d "List directories like regular files"
 complete -c exa -s 'L' -l 'level'     -d "Limit the depth of recursion" -x -a "1 2 3 4 5 6 7 8 9"
+complete -c exa -l 'max-total' -d "Stop recursing after this many entries" -x
+complete -c exa -l 'max-entries' -d "List at most this many entries from each directory" -x
+complete -c exa -l 'descend-only' -d "Only recurse into directories matching this glob" -x
+complete -c exa -l 'max-width' -d "Only recurse into this many directories in each one" -x
 complete -c exa -s 'r' -l 'reverse'   -d "Reverse the sort order"
+complete -c exa -l 'type-stable' -d "Keep types in order when reversing a sort by type"
+complete -c exa -l 'warn-atime' -d "Warn when sorting by access times that look unrecorded"
 complete -c exa -s 's' -l 'sort'      -d "Which field to sort by" -x -a "
     accessed\t'Sort by file accessed time'
     age\t'Sort by file modified time (newest first)'
     changed\t'Sort by changed time'
+    child-count\t'Sort directories by their number of entries'
     created\t'Sort by file modified time'
     date\t'Sort by file modified time'
+    dictionary\t'Sort by filename (lowercase before uppercase)'
     ext\t'Sort by file extension'
     Ext\t'Sort by file extension (uppercase first)'
     extension\t'Sort by file extension'
@@ -46,22 +97,51 @@ complete -c exa -s 's' -l 'sort'      -d "Which field to sort by" -x -a "
     Name\t'Sort by filename (uppercase first)'
     newest\t'Sort by file modified time (newest first)'
     none\t'Do not sort files at all'
+    numeric\t'Sort by the first number in the filename'
     oldest\t'Sort by file modified time'
     size\t'Sort by file size'
     time\t'Sort by file modified time'
     type\t'Sort by file type'
 "
 
+complete -c exa -l 'sort2' -d "Which field to sort by when --sort is a tie" -x -a "
+    accessed changed child-count created dictionary extension Extension filename Filename inode modified name Name none numeric size type
+"
+complete -c exa -l 'sort-order' -d "How to compare file names" -x -a "
+    natural\t'Compare numbers in names by their values'
+    byte\t'Compare names byte by byte'
+"
+complete -c exa -l 'compound-extensions' -d "Sort and colour by compound extensions such as tar.gz"
 complete -c exa -s 'I' -l 'ignore-glob' -d "Ignore files that match these glob patterns" -r
+complete -c exa -l 'ignore-match-path' -d "Match ignore globs against paths, not just names"
+complete -c exa -l 'respect-ignore-files' -d "Ignore files mentioned in '.ignore' and '.fdignore'"
+complete -c exa -l 'no-global-ignore' -d "Don't ignore the globs in the user's exa/ignore file"
 complete -c exa -s 'D' -l 'only-dirs'   -d "List only directories"
+complete -c exa -l 'newer-than' -d "Only list files modified within this long" -x
+complete -c exa -l 'older-than' -d "Only list files modified longer ago than this" -x
 
 # Long view options
 complete -c exa -s 'b' -l 'binary'   -d "List file sizes with binary prefixes"
 complete -c exa -s 'B' -l 'bytes'    -d "List file sizes in bytes, without any prefixes"
+complete -c exa        -l 'size-both' -d "List file sizes with binary and decimal prefixes"
+complete -c exa        -l 'human-above' -d "List sizes smaller than this in bytes" -x
+complete -c exa        -l 'size-color' \
+                       -l 'size-colour'  -d "What to colour sizes by" -x -a "
+    magnitude\t'Colour sizes by how big they are'
+    by-type\t'Colour sizes like their file types'
+"
 complete -c exa -s 'g' -l 'group'    -d "List each file's group"
+complete -c exa        -l 'merge-owner' -d "Show user and group in one column"
 complete -c exa -s 'h' -l 'header'   -d "Add a header row to each column"
+complete -c exa        -l 'summary-row' -d "Add a row with the total size and links under the table"
+complete -c exa        -l 'link-target-column' -d "List symlink targets in their own column"
+complete -c exa        -l 'borders'     -d "Draw lines between the columns"
+complete -c exa        -l 'no-lead-pad' -d "Don't start lines with the first column's padding"
 complete -c exa -s 'H' -l 'links'    -d "List each file's number of hard links"
+complete -c exa        -l 'reverse-links' -d "List how many of the listed symlinks point at each file"
 complete -c exa -s 'g' -l 'group'    -d "List each file's inode number"
+complete -c exa        -l 'device-inode' -d "List each file's device and inode number"
+complete -c exa        -l 'file-version' -d "List each file's generation number"
 complete -c exa -s 'S' -l 'blocks'   -d "List each file's number of filesystem blocks"
 complete -c exa -s 't' -l 'time'     -d "Which timestamp field to list" -x -a "
     modified\t'Display modified time'
@@ -71,6 +151,7 @@ complete -c exa -s 't' -l 'time'     -d "Which timestamp field to list" -x -a "
 "
 complete -c exa -s 'm' -l 'modified'      -d "Use the modified timestamp field"
 complete -c exa -s 'n' -l 'numeric'       -d "List numeric user and group IDs."
+complete -c exa        -l 'truncate-names' -d "Cut user and group names down to this many characters" -x
 complete -c exa        -l 'changed'       -d "Use the changed timestamp field"
 complete -c exa -s 'u' -l 'accessed'      -d "Use the accessed timestamp field"
 complete -c exa -s 'U' -l 'created'       -d "Use the created timestamp field"
@@ -78,14 +159,35 @@ complete -c exa        -l 'time-style'    -d "How to format timestamps" -x -a "
     default\t'Use the default time style'
     iso\t'Display brief ISO timestamps'
     long-iso\t'Display longer ISO timestaps, up to the minute'
+    iso-seconds\t'Display ISO timestamps, up to the second'
     full-iso\t'Display full ISO timestamps, up to the nanosecond'
 "
+complete -c exa        -l 'month-names'   -d "Which locale's month names to use in timestamps" -x
+complete -c exa        -l 'nanoseconds'   -d "Show the seconds and nanoseconds in timestamps"
+complete -c exa        -l 'age'           -d "Also show how long ago the timestamp was"
+complete -c exa        -l 'color-age' \
+                       -l 'colour-age'    -d "Colour timestamps by how long ago they were"
+complete -c exa        -l 'size-percent'  -d "List each file's share of the total size"
+complete -c exa        -l 'size-bar'      -d "Draw a bar of each file's size next to the largest"
+complete -c exa        -l 'mark-sparse'   -d "Mark the sizes of sparse files"
 complete -c exa        -l 'no-permissions' -d "Suppress the permissions field"
 complete -c exa        -l 'octal-permissions' -d "List each file's permission in octal format"
+complete -c exa        -l 'effective-permissions' -d "List what you can do with each file"
 complete -c exa        -l 'no-filesize'    -d "Suppress the filesize field"
 complete -c exa        -l 'no-user'        -d "Suppress the user field"
 complete -c exa        -l 'no-time'        -d "Suppress the time field"
+complete -c exa        -l 'checksum'       -d "List a hash of each file's contents" -x -a "
+    crc32\t'Use the CRC-32 checksum'
+    md5\t'Use the MD5 hash'
+"
+complete -c exa        -l 'encoding'       -d "List a guess at each file's text encoding"
+complete -c exa        -l 'volume'         -d "List the name of the mount each file is on"
+complete -c exa        -l 'type-column'    -d "List each file's type as a word"
+complete -c exa        -l 'mount-info'     -d "Mark bind mounts and their propagation"
 
 # Optional extras
 complete -c exa -l 'git' -d "List each file's Git status, if tracked"
+complete -c exa -l 'git-col-width' -d "Fix the width of the Git status column" -x
+complete -c exa -l 'git-max-files' -d "Skip Git statuses in repositories with more files than this" -x
 complete -c exa -s '@' -l 'extended' -d "List each file's extended attributes and sizes"
+complete -c exa -l 'security-summary' -d "List each file's attribute count and ACL"
@@ -0,0 +1,242 @@
+//! Hashing the contents of files, for the checksum column.
+//!
+//! Only two algorithms are supported, and both are implemented here rather
+//! than pulled in from a crate: CRC32 is tiny, and MD5 isn’t much bigger.
+//! Neither is any good for security, but they’re fine for checking at a
+//! glance whether two files are the same.
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+
+/// Which algorithm to hash file contents with.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Algorithm {
+
+    /// The CRC-32 used by zip and gzip, printed as eight hex digits.
+    Crc32,
+
+    /// MD5, printed as thirty-two hex digits.
+    Md5,
+}
+
+impl Algorithm {
+
+    /// Reads everything from the given reader, and returns its hash as a
+    /// lowercase hex string.
+    pub fn hash<R: Read>(self, mut reader: R) -> io::Result<String> {
+        let mut buffer = [0; 64 * 1024];
+
+        match self {
+            Self::Crc32 => {
+                let mut crc = Crc32::default();
+                loop {
+                    let count = reader.read(&mut buffer)?;
+                    if count == 0 { break; }
+                    crc.update(&buffer[.. count]);
+                }
+                Ok(format!("{:08x}", crc.finish()))
+            }
+            Self::Md5 => {
+                let mut md5 = Md5::default();
+                loop {
+                    let count = reader.read(&mut buffer)?;
+                    if count == 0 { break; }
+                    md5.update(&buffer[.. count]);
+                }
+                Ok(md5.finish().iter().map(|b| format!("{:02x}", b)).collect())
+            }
+        }
+    }
+
+    /// Hashes the contents of the file at the given path.
+    pub fn hash_file(self, path: &Path) -> io::Result<String> {
+        self.hash(fs::File::open(path)?)
+    }
+}
+
+
+/// A running CRC-32, using the reflected 0xEDB88320 polynomial.
+struct Crc32 {
+    value: u32,
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self { value: 0xFFFF_FFFF }
+    }
+}
+
+impl Crc32 {
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.value ^= u32::from(byte);
+            for _ in 0 .. 8 {
+                let mask = (self.value & 1).wrapping_neg();
+                self.value = (self.value >> 1) ^ (0xEDB8_8320 & mask);
+            }
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        ! self.value
+    }
+}
+
+
+/// A running MD5, as described in RFC 1321.
+struct Md5 {
+    state: [u32; 4],
+    block: Vec<u8>,
+    length: u64,
+}
+
+impl Default for Md5 {
+    fn default() -> Self {
+        Self {
+            state: [ 0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476 ],
+            block: Vec::with_capacity(64),
+            length: 0,
+        }
+    }
+}
+
+/// The per-round shift amounts.
+const MD5_SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22,
+    5,  9, 14, 20, 5,  9, 14, 20, 5,  9, 14, 20, 5,  9, 14, 20,
+    4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23,
+    6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+/// The per-round constants, the integer parts of the sines of 1 to 64
+/// scaled up by 2³².
+const MD5_CONSTANTS: [u32; 64] = [
+    0xd76a_a478, 0xe8c7_b756, 0x2420_70db, 0xc1bd_ceee, 0xf57c_0faf, 0x4787_c62a, 0xa830_4613, 0xfd46_9501,
+    0x6980_98d8, 0x8b44_f7af, 0xffff_5bb1, 0x895c_d7be, 0x6b90_1122, 0xfd98_7193, 0xa679_438e, 0x49b4_0821,
+    0xf61e_2562, 0xc040_b340, 0x265e_5a51, 0xe9b6_c7aa, 0xd62f_105d, 0x0244_1453, 0xd8a1_e681, 0xe7d3_fbc8,
+    0x21e1_cde6, 0xc337_07d6, 0xf4d5_0d87, 0x455a_14ed, 0xa9e3_e905, 0xfcef_a3f8, 0x676f_02d9, 0x8d2a_4c8a,
+    0xfffa_3942, 0x8771_f681, 0x6d9d_6122, 0xfde5_380c, 0xa4be_ea44, 0x4bde_cfa9, 0xf6bb_4b60, 0xbebf_bc70,
+    0x289b_7ec6, 0xeaa1_27fa, 0xd4ef_3085, 0x0488_1d05, 0xd9d4_d039, 0xe6db_99e5, 0x1fa2_7cf8, 0xc4ac_5665,
+    0xf429_2244, 0x432a_ff97, 0xab94_23a7, 0xfc93_a039, 0x655b_59c3, 0x8f0c_cc92, 0xffef_f47d, 0x8584_5dd1,
+    0x6fa8_7e4f, 0xfe2c_e6e0, 0xa301_4314, 0x4e08_11a1, 0xf753_7e82, 0xbd3a_f235, 0x2ad7_d2bb, 0xeb86_d391,
+];
+
+impl Md5 {
+    fn update(&mut self, bytes: &[u8]) {
+        self.length = self.length.wrapping_add(bytes.len() as u64);
+
+        for &byte in bytes {
+            self.block.push(byte);
+            if self.block.len() == 64 {
+                self.compress();
+                self.block.clear();
+            }
+        }
+    }
+
+    fn finish(mut self) -> [u8; 16] {
+        let bits = self.length.wrapping_mul(8);
+
+        self.block.push(0x80);
+        if self.block.len() > 56 {
+            self.block.resize(64, 0);
+            self.compress();
+            self.block.clear();
+        }
+
+        self.block.resize(56, 0);
+        self.block.extend_from_slice(&bits.to_le_bytes());
+        self.compress();
+
+        let mut digest = [0; 16];
+        for (chunk, word) in digest.chunks_mut(4).zip(&self.state) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        digest
+    }
+
+    // The single-letter names are the ones RFC 1321 uses.
+    #[allow(clippy::many_single_char_names)]
+    fn compress(&mut self) {
+        let mut words = [0_u32; 16];
+        for (word, chunk) in words.iter_mut().zip(self.block.chunks(4)) {
+            *word = u32::from_le_bytes([ chunk[0], chunk[1], chunk[2], chunk[3] ]);
+        }
+
+        let [mut a, mut b, mut c, mut d] = self.state;
+
+        for i in 0 .. 64 {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (! b & d),  i),
+                1 => ((d & b) | (! d & c),  (5 * i + 1) % 16),
+                2 => (b ^ c ^ d,            (3 * i + 5) % 16),
+                _ => (c ^ (b | ! d),        (7 * i) % 16),
+            };
+
+            let rotated = a.wrapping_add(f)
+                           .wrapping_add(MD5_CONSTANTS[i])
+                           .wrapping_add(words[g])
+                           .rotate_left(MD5_SHIFTS[i]);
+
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(rotated);
+        }
+
+        self.state[0] = self.state[0].wrapping_add(a);
+        self.state[1] = self.state[1].wrapping_add(b);
+        self.state[2] = self.state[2].wrapping_add(c);
+        self.state[3] = self.state[3].wrapping_add(d);
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fs::test::TempDir;
+
+    #[test]
+    fn crc32_check_value() {
+        let hash = Algorithm::Crc32.hash(&b"123456789"[..]).unwrap();
+        assert_eq!(hash, "cbf43926");
+    }
+
+    #[test]
+    fn crc32_nothing() {
+        let hash = Algorithm::Crc32.hash(&b""[..]).unwrap();
+        assert_eq!(hash, "00000000");
+    }
+
+    #[test]
+    fn md5_nothing() {
+        let hash = Algorithm::Md5.hash(&b""[..]).unwrap();
+        assert_eq!(hash, "d41d8cd98f00b204e9800998ecf8427e");
+    }
+
+    #[test]
+    fn md5_abc() {
+        let hash = Algorithm::Md5.hash(&b"abc"[..]).unwrap();
+        assert_eq!(hash, "900150983cd24fb0d6963f7d28e17f72");
+    }
+
+    #[test]
+    fn md5_more_than_a_block() {
+        let input = b"12345678901234567890123456789012345678901234567890123456789012345678901234567890";
+        let hash = Algorithm::Md5.hash(&input[..]).unwrap();
+        assert_eq!(hash, "57edf4a22be3c955ac49da2e2107b67a");
+    }
+
+    #[test]
+    fn crc32_of_a_file() {
+        let dir = TempDir::new("checksum");
+        let path = dir.join("file");
+        std::fs::write(&path, b"The quick brown fox jumps over the lazy dog").unwrap();
+
+        let hash = Algorithm::Crc32.hash_file(&path).unwrap();
+        assert_eq!(hash, "414fa339");
+    }
+}
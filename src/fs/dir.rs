@@ -106,6 +106,9 @@ impl<'dir, 'ig> Files<'dir, 'ig> {
     fn next_visible_file(&mut self) -> Option<Result<File<'dir>, (PathBuf, io::Error)>> {
         loop {
             if let Some(path) = self.inner.next() {
+                // The real `.` and `..` entries never come out of reading a
+                // directory — they get added separately, above — so anything
+                // starting with a dot here, even `..foo`, is a dotfile.
                 let filename = File::filename(path);
                 if ! self.dotfiles && filename.starts_with('.') {
                     continue;
@@ -215,3 +218,31 @@ impl DotFilter {
         }
     }
 }
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fs::test::TempDir;
+
+    fn names(path: &Path, dots: DotFilter) -> Vec<String> {
+        let dir = Dir::read_dir(path.to_path_buf()).unwrap();
+        let mut names = dir.files(dots, None, false)
+                           .map(|f| f.unwrap().name)
+                           .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn double_dot_files() {
+        let path = TempDir::new("dots");
+        for name in &[ ".a", "..b", "c" ] {
+            std::fs::write(path.join(name), b"").unwrap();
+        }
+
+        assert_eq!(names(&path, DotFilter::JustFiles),        vec![ "c" ]);
+        assert_eq!(names(&path, DotFilter::Dotfiles),         vec![ "..b", ".a", "c" ]);
+        assert_eq!(names(&path, DotFilter::DotfilesAndDots),  vec![ ".", "..", "..b", ".a", "c" ]);
+    }
+}
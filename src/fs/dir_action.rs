@@ -1,5 +1,8 @@
 //! What to do when encountering a directory?
 
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+
 /// The action to take when trying to list a file that turns out to be a
 /// directory.
 ///
@@ -19,7 +22,7 @@
 /// into them and print out their contents. The recurse mode does this by
 /// having extra output blocks at the end, while the tree mode will show
 /// directories inline, with their contents immediately underneath.
-#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[derive(PartialEq, Eq, Debug, Clone)]
 pub enum DirAction {
 
     /// This directory should be listed along with the regular files, instead
@@ -39,18 +42,19 @@ pub enum DirAction {
 impl DirAction {
 
     /// Gets the recurse options, if this dir action has any.
-    pub fn recurse_options(self) -> Option<RecurseOptions> {
+    pub fn recurse_options(&self) -> Option<&RecurseOptions> {
         match self {
             Self::Recurse(o)  => Some(o),
             _                 => None,
         }
     }
 
-    /// Whether to treat directories as regular files or not.
-    pub fn treat_dirs_as_files(self) -> bool {
+    /// Whether to treat directories as regular files or not. Trees list
+    /// their roots as files, unless each root is getting a tree of its own.
+    pub fn treat_dirs_as_files(&self) -> bool {
         match self {
             Self::AsFile      => true,
-            Self::Recurse(o)  => o.tree,
+            Self::Recurse(o)  => o.tree && ! o.separate_trees,
             Self::List        => false,
         }
     }
@@ -58,7 +62,7 @@ impl DirAction {
 
 
 /// The options that determine how to recurse into a directory.
-#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[derive(PartialEq, Eq, Debug, Clone)]
 pub struct RecurseOptions {
 
     /// Whether recursion should be done as a tree or as multiple individual
@@ -68,15 +72,161 @@ pub struct RecurseOptions {
     /// The maximum number of times that recursion should descend to, if one
     /// is specified.
     pub max_depth: Option<usize>,
+
+    /// The maximum number of entries to list across every directory that
+    /// gets recursed into, if one is specified.
+    pub max_total: Option<usize>,
+
+    /// Whether, in tree mode, each directory argument should be listed as
+    /// a separate tree under its own heading, instead of all of them being
+    /// roots of the same tree.
+    pub separate_trees: bool,
+
+    /// A glob pattern that directories’ names have to match to be recursed
+    /// into, if one is specified. Directories that don’t match still get
+    /// listed, just without their contents.
+    pub descend_only: Option<glob::Pattern>,
+
+    /// The maximum number of subdirectories of any one directory that
+    /// should be recursed into, if one is specified. The rest still get
+    /// listed, just without their contents.
+    pub max_width: Option<usize>,
 }
 
 impl RecurseOptions {
 
     /// Returns whether a directory of the given depth would be too deep.
-    pub fn is_too_deep(self, depth: usize) -> bool {
+    pub fn is_too_deep(&self, depth: usize) -> bool {
         match self.max_depth {
             None     => false,
             Some(d)  => d <= depth
         }
     }
+
+    /// Returns whether a directory with the given name should have its
+    /// contents listed, going by the `--descend-only` pattern.
+    pub fn descends_into(&self, name: &str) -> bool {
+        self.descend_only.as_ref().map_or(true, |p| p.matches(name))
+    }
+
+    /// Returns whether a directory’s subdirectory at the given index, out
+    /// of the ones that would be recursed into, is past the width limit.
+    pub fn is_too_wide(&self, index: usize) -> bool {
+        match self.max_width {
+            None     => false,
+            Some(w)  => w <= index
+        }
+    }
+
+    /// The note to print under a directory when some of its subdirectories
+    /// weren’t recursed into because of the width limit.
+    pub fn width_note(&self, skipped: usize) -> String {
+        format!("[{} more directories not descended into]", skipped)
+    }
+}
+
+
+/// The number of entries a recursive listing is still allowed to print
+/// before it should stop, shared between every directory it recurses into.
+/// This stops an accidental `exa -R /` from running for hours.
+///
+/// The counts are atomic, so the details view can spend the budget while it
+/// builds a tree, even though its renderer gets shared between threads.
+#[derive(Debug)]
+pub struct EntryBudget {
+
+    /// The number of entries that can still be listed. This only means
+    /// anything if there’s a limit.
+    remaining: AtomicUsize,
+
+    /// The limit this budget started with, or `None` if there’s no limit.
+    limit: Option<usize>,
+
+    /// Whether any entries have had to be left out.
+    exhausted: AtomicBool,
+}
+
+impl EntryBudget {
+
+    /// Creates a new budget with the given limit.
+    pub fn new(limit: Option<usize>) -> Self {
+        Self { remaining: AtomicUsize::new(limit.unwrap_or(0)), limit, exhausted: AtomicBool::new(false) }
+    }
+
+    /// Spends the budget on the given list of entries, removing any that
+    /// it can’t afford. Returns whether anything was removed.
+    pub fn spend<T>(&self, entries: &mut Vec<T>) -> bool {
+        if self.limit.is_none() {
+            return false;
+        }
+
+        let remaining = self.remaining.load(Ordering::Relaxed);
+        if entries.len() > remaining {
+            entries.truncate(remaining);
+            self.remaining.store(0, Ordering::Relaxed);
+            self.exhausted.store(true, Ordering::Relaxed);
+        }
+        else {
+            self.remaining.store(remaining - entries.len(), Ordering::Relaxed);
+        }
+
+        self.is_exhausted()
+    }
+
+    /// Whether entries have had to be left out, meaning the listing should
+    /// stop.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted.load(Ordering::Relaxed)
+    }
+
+    /// The note to print once the listing has been cut short.
+    pub fn note(&self) -> String {
+        format!("[listing stopped after {} entries]", self.limit.unwrap_or(0))
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unlimited() {
+        let budget = EntryBudget::new(None);
+        let mut entries = vec![ "file"; 100 ];
+
+        assert!(! budget.spend(&mut entries));
+        assert_eq!(entries.len(), 100);
+        assert!(! budget.is_exhausted());
+    }
+
+    #[test]
+    fn stops_across_directories() {
+        let budget = EntryBudget::new(Some(5));
+        let mut listed = Vec::new();
+
+        for count in &[ 3, 4, 10 ] {
+            if budget.is_exhausted() {
+                break;
+            }
+
+            let mut entries = vec![ "file"; *count ];
+            budget.spend(&mut entries);
+            listed.extend(entries);
+        }
+
+        assert_eq!(listed.len(), 5);
+        assert!(budget.is_exhausted());
+        assert_eq!(budget.note(), "[listing stopped after 5 entries]");
+    }
+
+    #[test]
+    fn exactly_enough() {
+        let budget = EntryBudget::new(Some(5));
+        let mut entries = vec![ "file"; 5 ];
+
+        assert!(! budget.spend(&mut entries));
+        assert_eq!(entries.len(), 5);
+        assert!(! budget.is_exhausted());
+    }
 }
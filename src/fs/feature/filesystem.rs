@@ -0,0 +1,73 @@
+//! Looking up how big a filesystem is, and how much of it is free, for the
+//! line that `--filesystem-info` prints above a directory’s listing.
+
+use std::io;
+use std::path::Path;
+
+
+/// The sizes of a filesystem, in bytes.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Space {
+
+    /// The size of the whole filesystem.
+    pub total: u64,
+
+    /// How much of the filesystem is in use.
+    pub used: u64,
+
+    /// How much is left for unprivileged users, which doesn’t include any
+    /// blocks kept back for root.
+    pub free: u64,
+}
+
+/// Somewhere to look up filesystem sizes from. This is a trait so the sizes
+/// can be mocked up in tests.
+pub trait SpaceSource {
+    fn space(&self, path: &Path) -> io::Result<Space>;
+}
+
+/// Looks up the sizes of real filesystems, using `statvfs(3)`.
+pub struct Statvfs;
+
+#[cfg(unix)]
+impl SpaceSource for Statvfs {
+
+    // The statvfs fields are only 64 bits wide on some platforms, so the
+    // conversions that do nothing here are needed elsewhere.
+    #[allow(clippy::useless_conversion)]
+    fn space(&self, path: &Path) -> io::Result<Space> {
+        use std::ffi::CString;
+        use std::mem::MaybeUninit;
+        use std::os::unix::ffi::OsStrExt;
+
+        let c_path = CString::new(path.as_os_str().as_bytes())
+                             .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let mut stats = MaybeUninit::<libc::statvfs>::uninit();
+        if unsafe { libc::statvfs(c_path.as_ptr(), stats.as_mut_ptr()) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        // statvfs has returned successfully, so the struct is filled in.
+        let stats = unsafe { stats.assume_init() };
+        let fragment = u64::from(stats.f_frsize);
+        let blocks = u64::from(stats.f_blocks);
+
+        Ok(Space {
+            total: blocks * fragment,
+            used:  blocks.saturating_sub(u64::from(stats.f_bfree)) * fragment,
+            free:  u64::from(stats.f_bavail) * fragment,
+        })
+    }
+}
+
+#[cfg(not(unix))]
+impl SpaceSource for Statvfs {
+
+    // The statvfs fields are only 64 bits wide on some platforms, so the
+    // conversions that do nothing here are needed elsewhere.
+    #[allow(clippy::useless_conversion)]
+    fn space(&self, _path: &Path) -> io::Result<Space> {
+        Err(io::Error::new(io::ErrorKind::Other, "filesystem sizes are not supported on this platform"))
+    }
+}
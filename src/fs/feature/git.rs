@@ -22,9 +22,21 @@ pub struct GitCache {
 
     /// Paths that we’ve confirmed do not have Git repositories underneath them.
     misses: Vec<PathBuf>,
+
+    /// The most files a repository can have before exa stops looking up
+    /// statuses in it, if there’s a limit.
+    max_files: Option<usize>,
 }
 
 impl GitCache {
+
+    /// Sets the most files a repository can have for its statuses to be
+    /// looked up. Any repository with more than that gets every file shown
+    /// as unmodified, rather than taking ages to list.
+    pub fn with_max_files(self, max_files: Option<usize>) -> Self {
+        Self { max_files, .. self }
+    }
+
     pub fn has_anything_for(&self, index: &Path) -> bool {
         self.repos.iter().any(|e| e.has_path(index))
     }
@@ -32,9 +44,18 @@ impl GitCache {
     pub fn get(&self, index: &Path, prefix_lookup: bool) -> f::Git {
         self.repos.iter()
             .find(|e| e.has_path(index))
-            .map(|repo| repo.search(index, prefix_lookup))
+            .map(|repo| repo.search(index, prefix_lookup, self.max_files))
             .unwrap_or_default()
     }
+
+    /// Whether the given path is in a repository with too many files for
+    /// its statuses to have been looked up. This is only known once a
+    /// status has been got from the repository.
+    pub fn is_over_limit(&self, index: &Path) -> bool {
+        self.repos.iter()
+            .find(|e| e.has_path(index))
+            .map_or(false, GitRepo::is_over_limit)
+    }
 }
 
 use std::iter::FromIterator;
@@ -46,6 +67,7 @@ impl FromIterator<PathBuf> for GitCache {
         let mut git = Self {
             repos: Vec::with_capacity(iter.size_hint().0),
             misses: Vec::new(),
+            max_files: None,
         };
 
         for path in iter {
@@ -131,7 +153,7 @@ impl GitRepo {
     /// The temporary `Processing` enum variant is used after the `git2`
     /// repository is moved out, but before the results have been moved in!
     /// See <https://stackoverflow.com/q/45985827/3484614>
-    fn search(&self, index: &Path, prefix_lookup: bool) -> f::Git {
+    fn search(&self, index: &Path, prefix_lookup: bool, max_files: Option<usize>) -> f::Git {
         use std::mem::replace;
 
         let mut contents = self.contents.lock().unwrap();
@@ -142,12 +164,21 @@ impl GitRepo {
 
         debug!("Querying Git repo {:?} for the first time", &self.workdir);
         let repo = replace(&mut *contents, GitContents::Processing).inner_repo();
-        let statuses = repo_to_statuses(&repo, &self.workdir);
+        let statuses = repo_to_statuses(&repo, &self.workdir, max_files);
         let result = statuses.status(index, prefix_lookup);
         let _processing = replace(&mut *contents, GitContents::After { statuses });
         result
     }
 
+    /// Whether this repository turned out to have too many files for its
+    /// statuses to be looked up.
+    fn is_over_limit(&self) -> bool {
+        match *self.contents.lock().unwrap() {
+            GitContents::After { ref statuses }  => statuses.over_limit,
+            _                                    => false,
+        }
+    }
+
     /// Whether this repository has the given working directory.
     fn has_workdir(&self, path: &Path) -> bool {
         self.workdir == path
@@ -160,9 +191,25 @@ impl GitRepo {
 
     /// Searches for a Git repository at any point above the given path.
     /// Returns the original buffer if none is found.
+    ///
+    /// The path can be a file that was passed as an argument, rather than a
+    /// directory. A file can’t contain a repository, so the search starts
+    /// from the directory it’s in, but the file’s own path is what gets
+    /// remembered, so only it counts as being part of the repository.
     fn discover(path: PathBuf) -> Result<Self, PathBuf> {
         info!("Searching for Git repository above {:?}", path);
-        let repo = match git2::Repository::discover(&path) {
+
+        let start = if path.is_dir() {
+                path.as_path()
+            }
+            else {
+                match path.parent() {
+                    Some(parent) if ! parent.as_os_str().is_empty()  => parent,
+                    _                                                => Path::new("."),
+                }
+            };
+
+        let repo = match git2::Repository::discover(start) {
             Ok(r) => r,
             Err(e) => {
                 error!("Error discovering Git repositories: {:?}", e);
@@ -201,9 +248,21 @@ impl GitContents {
 /// mapping of files to their Git status.
 /// We will have already used the working directory at this point, so it gets
 /// passed in rather than deriving it from the `Repository` again.
-fn repo_to_statuses(repo: &git2::Repository, workdir: &Path) -> Git {
+///
+/// If there’s a limit on the number of files, a repository that tracks more
+/// than that doesn’t get its statuses looked up at all, as that’s the slow
+/// part. The statuses get counted afterwards too, as untracked and ignored
+/// files aren’t in the index.
+fn repo_to_statuses(repo: &git2::Repository, workdir: &Path, max_files: Option<usize>) -> Git {
     let mut statuses = Vec::new();
 
+    if let (Some(max), Ok(index)) = (max_files, repo.index()) {
+        if index.len() > max {
+            warn!("Not getting Git statuses for repo with workdir {:?}, as it tracks {} files (more than {})", workdir, index.len(), max);
+            return Git { statuses, over_limit: true };
+        }
+    }
+
     info!("Getting Git statuses for repo with workdir {:?}", workdir);
     match repo.statuses(None) {
         Ok(es) => {
@@ -223,7 +282,7 @@ fn repo_to_statuses(repo: &git2::Repository, workdir: &Path) -> Git {
         }
     }
 
-    Git { statuses }
+    Git::limited(statuses, max_files)
 }
 
 // The `repo.statuses` call above takes a long time. exa debug output:
@@ -238,10 +297,26 @@ fn repo_to_statuses(repo: &git2::Repository, workdir: &Path) -> Git {
 /// Container of Git statuses for all the files in this folder’s Git repository.
 struct Git {
     statuses: Vec<(PathBuf, git2::Status)>,
+
+    /// Whether the repository had too many files, so no statuses were kept.
+    over_limit: bool,
 }
 
 impl Git {
 
+    /// Keeps the given statuses, unless there are more of them than the
+    /// limit, in which case they’re all dropped, and every file comes out
+    /// unmodified.
+    fn limited(statuses: Vec<(PathBuf, git2::Status)>, max_files: Option<usize>) -> Self {
+        match max_files {
+            Some(max) if statuses.len() > max => {
+                warn!("Ignoring Git statuses, as there are {} of them (more than {})", statuses.len(), max);
+                Self { statuses: Vec::new(), over_limit: true }
+            }
+            _ => Self { statuses, over_limit: false },
+        }
+    }
+
     /// Get either the file or directory status for the given path.
     /// “Prefix lookup” means that it should report an aggregate status of all
     /// paths starting with the given prefix (in other words, a directory).
@@ -318,15 +393,19 @@ fn reorient(path: &Path) -> PathBuf {
 }
 
 /// The character to display if the file has been modified, but not staged.
+///
+/// A file with a merge conflict is conflicted in both the index and the
+/// working tree, so that gets checked first in both places, ahead of any
+/// other changes to the file.
 fn working_tree_status(status: git2::Status) -> f::GitStatus {
     match status {
+        s if s.contains(git2::Status::CONFLICTED)     => f::GitStatus::Conflicted,
         s if s.contains(git2::Status::WT_NEW)         => f::GitStatus::New,
         s if s.contains(git2::Status::WT_MODIFIED)    => f::GitStatus::Modified,
         s if s.contains(git2::Status::WT_DELETED)     => f::GitStatus::Deleted,
         s if s.contains(git2::Status::WT_RENAMED)     => f::GitStatus::Renamed,
         s if s.contains(git2::Status::WT_TYPECHANGE)  => f::GitStatus::TypeChange,
         s if s.contains(git2::Status::IGNORED)        => f::GitStatus::Ignored,
-        s if s.contains(git2::Status::CONFLICTED)     => f::GitStatus::Conflicted,
         _                                             => f::GitStatus::NotModified,
     }
 }
@@ -335,6 +414,7 @@ fn working_tree_status(status: git2::Status) -> f::GitStatus {
 /// has been staged.
 fn index_status(status: git2::Status) -> f::GitStatus {
     match status {
+        s if s.contains(git2::Status::CONFLICTED)        => f::GitStatus::Conflicted,
         s if s.contains(git2::Status::INDEX_NEW)         => f::GitStatus::New,
         s if s.contains(git2::Status::INDEX_MODIFIED)    => f::GitStatus::Modified,
         s if s.contains(git2::Status::INDEX_DELETED)     => f::GitStatus::Deleted,
@@ -343,3 +423,134 @@ fn index_status(status: git2::Status) -> f::GitStatus {
         _                                                => f::GitStatus::NotModified,
     }
 }
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+    use crate::fs::test::TempDir;
+
+    /// Creates a repository in a new temporary directory, commits files
+    /// with the given names to it, then changes the first one. The
+    /// directory comes back along with its canonical path, which is the
+    /// one Git reports statuses under.
+    fn repository_with_a_change(label: &str, files: &[&str]) -> (TempDir, PathBuf) {
+        let dir = TempDir::new(label);
+        let path = dir.canonicalize().unwrap();
+
+        for name in files {
+            fs::write(path.join(name), b"original").unwrap();
+        }
+
+        let repo = git2::Repository::init(&path).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_all([ "*" ].iter(), git2::IndexAddOption::DEFAULT, None).unwrap();
+        index.write().unwrap();
+
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("exa", "exa@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "Initial commit", &tree, &[]).unwrap();
+
+        fs::write(path.join(files[0]), b"changed").unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn tally_of_a_repository() {
+        let (_dir, path) = repository_with_a_change("git-tally", &[ "modified", "clean", "also-clean" ]);
+
+        let cache = vec![ path.clone() ].into_iter().collect::<GitCache>();
+        let mut tally = f::GitTally::default();
+        for name in &[ "modified", "clean", "also-clean" ] {
+            tally.add(cache.get(&path.join(name), false));
+        }
+
+        assert_eq!(tally, f::GitTally { modified: 1, staged: 0, untracked: 0, conflicted: 0, clean: 2, skipped: 0 });
+        assert_eq!(tally.summary(), "1 modified, 0 staged, 0 untracked, 2 clean");
+        assert_eq!(tally.skipped_note(), None);
+    }
+
+    #[test]
+    fn repository_over_the_limit() {
+        let (_dir, path) = repository_with_a_change("git-over-limit", &[ "modified", "clean" ]);
+        let cache = vec![ path.clone() ].into_iter().collect::<GitCache>().with_max_files(Some(1));
+
+        // Nothing is known about the repository until it’s been asked for
+        // a status, and then the change doesn’t show up.
+        assert!(! cache.is_over_limit(&path.join("modified")));
+        assert!(cache.get(&path.join("modified"), false).unstaged == f::GitStatus::NotModified);
+        assert!(cache.is_over_limit(&path.join("modified")));
+    }
+
+    #[test]
+    fn single_file_argument() {
+        let (_dir, path) = repository_with_a_change("git-file-argument", &[ "modified", "clean" ]);
+        let file = path.join("modified");
+
+        let cache = vec![ file.clone() ].into_iter().collect::<GitCache>();
+        assert!(cache.has_anything_for(&file));
+        assert!(! cache.has_anything_for(&path.join("clean")));
+
+        let status = cache.get(&file, false);
+        assert!(status.unstaged == f::GitStatus::Modified);
+        assert!(status.staged == f::GitStatus::NotModified);
+    }
+
+    #[test]
+    fn merge_conflict() {
+        let (_dir, path) = repository_with_a_change("git-conflict", &[ "conflicted" ]);
+        let repo = git2::Repository::open(&path).unwrap();
+        let signature = git2::Signature::now("exa", "exa@example.com").unwrap();
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+
+        // Commit our change on the current branch...
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("conflicted")).unwrap();
+        index.write().unwrap();
+        let ours = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "Ours", &ours, &[ &base ]).unwrap();
+
+        // ...and a different change to the same file on another, then
+        // merge that one in.
+        let mut builder = repo.treebuilder(Some(&base.tree().unwrap())).unwrap();
+        builder.insert("conflicted", repo.blob(b"theirs").unwrap(), 0o100_644).unwrap();
+        let theirs = repo.find_tree(builder.write().unwrap()).unwrap();
+        let theirs = repo.commit(None, &signature, &signature, "Theirs", &theirs, &[ &base ]).unwrap();
+        let theirs = repo.find_annotated_commit(theirs).unwrap();
+        repo.merge(&[ &theirs ], None, None).unwrap();
+
+        let cache = vec![ path.clone() ].into_iter().collect::<GitCache>();
+        let status = cache.get(&path.join("conflicted"), false);
+        assert!(status.staged == f::GitStatus::Conflicted);
+        assert!(status.unstaged == f::GitStatus::Conflicted);
+
+        let mut tally = f::GitTally::default();
+        tally.add(status);
+        assert_eq!(tally, f::GitTally { conflicted: 1, .. f::GitTally::default() });
+        assert_eq!(tally.summary(), "0 modified, 0 staged, 0 untracked, 1 conflicted, 0 clean");
+    }
+
+    #[test]
+    fn too_many_statuses() {
+        let path = TempDir::new("git-max-files");
+        let path = path.canonicalize().unwrap();
+
+        let statuses = vec![
+            (path.join("one"),    git2::Status::WT_MODIFIED),
+            (path.join("two"),    git2::Status::WT_NEW),
+            (path.join("three"),  git2::Status::INDEX_MODIFIED),
+        ];
+
+        let kept = Git::limited(statuses.clone(), Some(3));
+        assert!(kept.status(&path.join("one"), false).unstaged == f::GitStatus::Modified);
+
+        assert!(! kept.over_limit);
+
+        let skipped = Git::limited(statuses, Some(2));
+        assert!(skipped.over_limit);
+        let status = skipped.status(&path.join("one"), false);
+        assert!(status.unstaged == f::GitStatus::NotModified);
+        assert!(status.staged == f::GitStatus::NotModified);
+    }
+}
@@ -1,3 +1,8 @@
+pub mod filesystem;
+pub mod mount_info;
+pub mod version;
+#[cfg(unix)]
+pub mod volume;
 pub mod xattr;
 
 #[cfg(feature = "git")]
@@ -22,6 +27,10 @@ pub mod git {
     }
 
     impl GitCache {
+        pub fn with_max_files(self, _max_files: Option<usize>) -> Self {
+            self
+        }
+
         pub fn has_anything_for(&self, _index: &Path) -> bool {
             false
         }
@@ -29,5 +38,9 @@ pub mod git {
         pub fn get(&self, _index: &Path, _prefix_lookup: bool) -> f::Git {
             unreachable!();
         }
+
+        pub fn is_over_limit(&self, _index: &Path) -> bool {
+            false
+        }
     }
 }
@@ -0,0 +1,115 @@
+//! Looking up which directories are mount points, and what sort of mounts
+//! they are, for the `--mount-info` column.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::fs::fields as f;
+
+
+/// Every mount point on the system, with whether it’s a bind mount and how
+/// mount events propagate through it.
+#[derive(PartialEq, Eq, Debug, Default)]
+pub struct Mounts {
+    points: HashMap<PathBuf, (bool, f::Propagation)>,
+}
+
+impl Mounts {
+
+    /// Reads the list of mounts from `/proc/self/mountinfo`. Only Linux has
+    /// bind mounts and propagation to report, so on any other platform, or
+    /// if the list can’t be read, it’s empty, and no directory gets flagged.
+    #[cfg(target_os = "linux")]
+    pub fn load() -> Self {
+        match std::fs::read_to_string("/proc/self/mountinfo") {
+            Ok(text) => {
+                Self::from_mountinfo(&text)
+            }
+            Err(e) => {
+                log::error!("Error reading the list of mounts: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn load() -> Self {
+        Self::default()
+    }
+
+    /// Parses the contents of `/proc/self/mountinfo`. Its fourth field is
+    /// the directory within the filesystem that’s mounted, which is only
+    /// something other than ‘`/`’ for a bind mount of a subdirectory; its
+    /// fifth is the mount point; and the optional fields after the sixth,
+    /// up to a lone ‘`-`’, give the propagation. Lines that don’t parse are
+    /// skipped.
+    #[cfg(target_os = "linux")]
+    pub fn from_mountinfo(text: &str) -> Self {
+        use std::ffi::OsString;
+        use std::os::unix::ffi::OsStringExt;
+
+        use crate::fs::feature::volume::unescape_octal;
+
+        let mut points = HashMap::new();
+
+        for line in text.lines() {
+            let mut fields = line.split(' ');
+            let root = fields.nth(3);
+            let mount_point = fields.next();
+
+            let (root, mount_point) = match (root, mount_point) {
+                (Some(r), Some(m))  => (r, m),
+                _                   => continue,
+            };
+
+            let tags = fields.skip(1).take_while(|field| *field != "-").collect::<Vec<_>>();
+            let has_tag = |prefix: &str| tags.iter().any(|tag| tag.starts_with(prefix));
+
+            let propagation = if has_tag("unbindable")   { f::Propagation::Unbindable }
+                         else if has_tag("shared:")      { f::Propagation::Shared }
+                         else if has_tag("master:")      { f::Propagation::Slave }
+                         else                            { f::Propagation::Private };
+
+            let path = OsString::from_vec(unescape_octal(mount_point));
+            points.insert(PathBuf::from(path), (root != "/", propagation));
+        }
+
+        Self { points }
+    }
+
+    /// What sort of mount, if any, the directory at the given path is. The
+    /// path has to be absolute, and without any symlinks, to be found.
+    pub fn mount_info(&self, path: &Path) -> f::MountInfo {
+        match self.points.get(path) {
+            Some(&(bind, propagation))  => f::MountInfo::Mount { bind, propagation },
+            None                        => f::MountInfo::NotMount,
+        }
+    }
+}
+
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mountinfo() {
+        let text = "\
+22 1 8:1 / / rw,relatime shared:1 - ext4 /dev/sda1 rw
+45 22 8:17 / /mnt/usb rw,relatime - ext4 /dev/sdb1 rw
+46 22 8:1 /srv/data /mnt/my\\040data rw,relatime shared:1 - ext4 /dev/sda1 rw
+47 22 0:40 / /tmp/jail rw master:3 - tmpfs tmpfs rw
+48 22 0:41 / /tmp/locked rw unbindable - tmpfs tmpfs rw
+nonsense
+";
+
+        let mounts = Mounts::from_mountinfo(text);
+        assert_eq!(mounts.mount_info(Path::new("/")),            f::MountInfo::Mount { bind: false, propagation: f::Propagation::Shared });
+        assert_eq!(mounts.mount_info(Path::new("/mnt/usb")),     f::MountInfo::Mount { bind: false, propagation: f::Propagation::Private });
+        assert_eq!(mounts.mount_info(Path::new("/mnt/my data")), f::MountInfo::Mount { bind: true,  propagation: f::Propagation::Shared });
+        assert_eq!(mounts.mount_info(Path::new("/tmp/jail")),    f::MountInfo::Mount { bind: false, propagation: f::Propagation::Slave });
+        assert_eq!(mounts.mount_info(Path::new("/tmp/locked")),  f::MountInfo::Mount { bind: false, propagation: f::Propagation::Unbindable });
+        assert_eq!(mounts.mount_info(Path::new("/srv/data")),    f::MountInfo::NotMount);
+    }
+}
@@ -0,0 +1,119 @@
+//! Reading a file’s generation number, on filesystems that keep one.
+//!
+//! Linux exposes this through the `FS_IOC_GETVERSION` ioctl, which ext4 and
+//! a handful of other filesystems support. Everywhere else, the version is
+//! reported as unsupported, and gets rendered as a dash.
+
+use std::io;
+use std::path::Path;
+
+use log::*;
+
+use crate::fs::fields as f;
+
+
+/// Something that can look up the version number of a file.
+pub trait VersionSource {
+    fn file_version(&self, path: &Path) -> io::Result<u64>;
+}
+
+/// Looks up version numbers by asking the filesystem directly.
+pub struct Ioctl;
+
+/// The direction bits of an ioctl that reads from the kernel, `_IOC_READ`,
+/// shifted into place. Most architectures keep them in the top two bits,
+/// but these ones have three direction bits, starting one bit lower.
+#[cfg(target_os = "linux")]
+#[cfg(any(target_arch = "powerpc", target_arch = "powerpc64", target_arch = "mips", target_arch = "mips64", target_arch = "sparc", target_arch = "sparc64"))]
+const IOC_READ: libc::c_ulong = 2 << 29;
+
+#[cfg(target_os = "linux")]
+#[cfg(not(any(target_arch = "powerpc", target_arch = "powerpc64", target_arch = "mips", target_arch = "mips64", target_arch = "sparc", target_arch = "sparc64")))]
+const IOC_READ: libc::c_ulong = 2 << 30;
+
+/// This is `_IOR('v', 1, long)`. The kernel only ever writes an `int` back.
+#[cfg(target_os = "linux")]
+#[allow(trivial_numeric_casts)]
+const FS_IOC_GETVERSION: libc::c_ulong =
+    IOC_READ | ((std::mem::size_of::<libc::c_long>() as libc::c_ulong) << 16) | (0x76 << 8) | 1;
+
+#[cfg(target_os = "linux")]
+impl VersionSource for Ioctl {
+
+    // The type of the request argument differs between libcs.
+    #[allow(trivial_numeric_casts)]
+    fn file_version(&self, path: &Path) -> io::Result<u64> {
+        use std::os::unix::io::AsRawFd;
+
+        let file = std::fs::File::open(path)?;
+        let mut version: libc::c_int = 0;
+
+        let result = unsafe { libc::ioctl(file.as_raw_fd(), FS_IOC_GETVERSION as _, &mut version) };
+        if result == 0 {
+            Ok(u64::from(version as u32))
+        }
+        else {
+            Err(io::Error::last_os_error())
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+impl VersionSource for Ioctl {
+    fn file_version(&self, _path: &Path) -> io::Result<u64> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+}
+
+/// Looks up the version of the file at the given path, treating any error
+/// as the version being unavailable.
+pub fn version_of<S: VersionSource>(source: &S, path: &Path) -> f::Version {
+    match source.file_version(path) {
+        Ok(version) => {
+            f::Version::Some(version)
+        }
+        Err(e) => {
+            debug!("Unable to get version of {:?}: {}", path, e);
+            f::Version::None
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct MockSource(Option<u64>);
+
+    impl VersionSource for MockSource {
+        fn file_version(&self, _path: &Path) -> io::Result<u64> {
+            self.0.ok_or_else(|| io::ErrorKind::Unsupported.into())
+        }
+    }
+
+    #[test]
+    fn supported() {
+        let version = version_of(&MockSource(Some(1_234)), Path::new("file"));
+        assert_eq!(version, f::Version::Some(1_234));
+    }
+
+    #[test]
+    fn unsupported() {
+        let version = version_of(&MockSource(None), Path::new("file"));
+        assert_eq!(version, f::Version::None);
+    }
+
+    #[test]
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    fn request_number() {
+        assert_eq!(FS_IOC_GETVERSION, 0x8008_7601);
+    }
+
+    #[test]
+    #[cfg(not(target_os = "linux"))]
+    fn ioctl_unsupported() {
+        let version = version_of(&Ioctl, Path::new("."));
+        assert_eq!(version, f::Version::None);
+    }
+}
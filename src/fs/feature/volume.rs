@@ -0,0 +1,219 @@
+//! Looking up which mount each file is on, for the `--volume` column.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use log::*;
+
+use crate::fs::fields as f;
+
+
+/// The short names of every mount on the system, keyed by the device ID
+/// that files on each of them have.
+#[derive(PartialEq, Eq, Debug, Default)]
+pub struct Volumes {
+    labels: HashMap<u64, String>,
+}
+
+impl Volumes {
+
+    /// Builds the map from a list of device IDs and the paths they’re
+    /// mounted at. If a device is mounted in more than one place, the last
+    /// mount wins, as it’s the one that hides the others.
+    pub fn from_mounts<'a, I>(mounts: I) -> Self
+    where I: IntoIterator<Item = (u64, &'a Path)>
+    {
+        let labels = mounts.into_iter()
+                           .map(|(dev, path)| (dev, label(path)))
+                           .collect();
+
+        Self { labels }
+    }
+
+    /// Reads the list of mounts from the system. This uses
+    /// `/proc/self/mountinfo` on Linux and `getmntinfo(3)` on the BSDs; on
+    /// any other platform, or if the list can’t be read, it’s empty, and
+    /// every file gets a dash.
+    pub fn load() -> Self {
+        match mounts() {
+            Ok(mounts) => {
+                Self::from_mounts(mounts.iter().map(|(dev, path)| (*dev, path.as_path())))
+            }
+            Err(e) => {
+                error!("Error reading the list of mounts: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// The volume that files with the given device ID are on.
+    pub fn volume(&self, dev: u64) -> f::Volume<'_> {
+        f::Volume(self.labels.get(&dev).map(String::as_str))
+    }
+}
+
+
+/// The last component of a mount point, or the whole thing for the root.
+fn label(path: &Path) -> String {
+    match path.file_name() {
+        Some(name)  => name.to_string_lossy().into_owned(),
+        None        => path.to_string_lossy().into_owned(),
+    }
+}
+
+
+#[cfg(target_os = "linux")]
+fn mounts() -> std::io::Result<Vec<(u64, std::path::PathBuf)>> {
+    let text = std::fs::read_to_string("/proc/self/mountinfo")?;
+    Ok(parse_mountinfo(&text))
+}
+
+/// Parses the contents of `/proc/self/mountinfo`, which has one mount per
+/// line, with its device as `major:minor` in the third field and its mount
+/// point in the fifth. Spaces and other awkward characters in the mount
+/// point are escaped as octal, like ‘`\040`’. Lines that don’t parse are
+/// skipped.
+#[cfg(target_os = "linux")]
+fn parse_mountinfo(text: &str) -> Vec<(u64, std::path::PathBuf)> {
+    use std::ffi::OsString;
+    use std::os::unix::ffi::OsStringExt;
+
+    let mut mounts = Vec::new();
+
+    for line in text.lines() {
+        let mut fields = line.split(' ');
+        let device = fields.nth(2);
+        let mount_point = fields.nth(1);
+
+        let (device, mount_point) = match (device, mount_point) {
+            (Some(d), Some(m))  => (d, m),
+            _                   => continue,
+        };
+
+        let (major, minor) = match device.split_once(':') {
+            Some((major, minor)) => match (major.parse(), minor.parse()) {
+                (Ok(major), Ok(minor))  => (major, minor),
+                _                       => continue,
+            },
+            None => continue,
+        };
+
+        let path = OsString::from_vec(unescape_octal(mount_point));
+        mounts.push((makedev(major, minor), path.into()));
+    }
+
+    mounts
+}
+
+/// Turns the ‘`\NNN`’ octal escapes in a mount point back into bytes.
+#[cfg(target_os = "linux")]
+pub(crate) fn unescape_octal(input: &str) -> Vec<u8> {
+    let bytes = input.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let escape = bytes.get(index + 1 .. index + 4)
+                          .filter(|_| bytes[index] == b'\\')
+                          .and_then(|digits| std::str::from_utf8(digits).ok())
+                          .and_then(|digits| u8::from_str_radix(digits, 8).ok());
+
+        if let Some(byte) = escape {
+            output.push(byte);
+            index += 4;
+        }
+        else {
+            output.push(bytes[index]);
+            index += 1;
+        }
+    }
+
+    output
+}
+
+/// Combines a major and minor device number into a device ID the same way
+/// glibc’s `makedev` does, so they can be compared with `st_dev`.
+#[cfg(target_os = "linux")]
+fn makedev(major: u64, minor: u64) -> u64 {
+    ((major & 0xffff_f000) << 32) | ((major & 0x0000_0fff) << 8) |
+    ((minor & 0xffff_ff00) << 12) | (minor & 0x0000_00ff)
+}
+
+
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+fn mounts() -> std::io::Result<Vec<(u64, std::path::PathBuf)>> {
+    use std::ffi::{CStr, OsStr};
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::fs::MetadataExt;
+
+    let mut buffer: *mut libc::statfs = std::ptr::null_mut();
+    let count = unsafe { libc::getmntinfo(&mut buffer, libc::MNT_NOWAIT) };
+    if count <= 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    // getmntinfo has returned successfully, so the buffer holds this many
+    // entries, and it stays around until the next call.
+    let entries = unsafe { std::slice::from_raw_parts(buffer, count as usize) };
+    let mut mounts = Vec::new();
+
+    // statfs doesn’t give the device ID that files on the mount have, so
+    // each mount point has to be looked up to find it.
+    for entry in entries {
+        let name = unsafe { CStr::from_ptr(entry.f_mntonname.as_ptr()) };
+        let path = Path::new(OsStr::from_bytes(name.to_bytes()));
+
+        match std::fs::metadata(path) {
+            Ok(metadata)  => mounts.push((metadata.dev(), path.to_path_buf())),
+            Err(e)        => error!("Error looking up mount point {:?}: {}", path, e),
+        }
+    }
+
+    Ok(mounts)
+}
+
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "freebsd")))]
+fn mounts() -> std::io::Result<Vec<(u64, std::path::PathBuf)>> {
+    Ok(Vec::new())
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn labels() {
+        let volumes = Volumes::from_mounts(vec![
+            (1, Path::new("/")),
+            (2, Path::new("/mnt/usb")),
+        ]);
+
+        assert_eq!(volumes.volume(1), f::Volume(Some("/")));
+        assert_eq!(volumes.volume(2), f::Volume(Some("usb")));
+        assert_eq!(volumes.volume(3), f::Volume(None));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn mountinfo() {
+        use std::path::PathBuf;
+
+        let text = "\
+22 1 8:1 / / rw,relatime shared:1 - ext4 /dev/sda1 rw
+45 22 8:17 / /mnt/my\\040drive rw,relatime shared:2 - ext4 /dev/sdb1 rw
+46 22 259:65536 / /big rw shared:3 - ext4 /dev/nvme0n1p1 rw
+nonsense
+";
+
+        let mounts = parse_mountinfo(text);
+        assert_eq!(mounts, vec![
+            (makedev(8, 1),        PathBuf::from("/")),
+            (makedev(8, 17),       PathBuf::from("/mnt/my drive")),
+            (makedev(259, 65536),  PathBuf::from("/big")),
+        ]);
+
+        assert_eq!(makedev(8, 17), 0x811);
+    }
+}
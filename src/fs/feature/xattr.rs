@@ -6,6 +6,8 @@ use std::cmp::Ordering;
 use std::io;
 use std::path::Path;
 
+use crate::fs::fields as f;
+
 
 pub const ENABLED: bool = cfg!(any(target_os = "macos", target_os = "linux"));
 
@@ -38,6 +40,27 @@ impl FileAttributes for Path {
 }
 
 
+/// The attributes that Linux keeps a file’s POSIX access control lists in.
+/// These count towards the ACL, rather than as attributes of their own.
+const ACL_ATTRIBUTES: &[&str] = &[ "system.posix_acl_access", "system.posix_acl_default" ];
+
+/// Counts a file’s extended attributes and checks whether it has an ACL.
+/// Files whose attributes can’t be listed are summarised as having none.
+pub fn security_summary<A: FileAttributes + ?Sized>(file: &A) -> f::SecuritySummary {
+    let attrs = match file.attributes() {
+        Ok(attrs)  => attrs,
+        Err(_)     => return f::SecuritySummary::default(),
+    };
+
+    let is_acl = |attr: &&Attribute| ACL_ATTRIBUTES.contains(&attr.name.as_str());
+
+    f::SecuritySummary {
+        xattrs: attrs.iter().filter(|a| ! is_acl(a)).count(),
+        acl:    attrs.iter().any(|a| is_acl(&a)),
+    }
+}
+
+
 /// Attributes which can be passed to `Attribute::list_with_flags`
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 #[derive(Copy, Clone)]
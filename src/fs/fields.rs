@@ -19,6 +19,9 @@
 /// The type of a file’s block count.
 pub type blkcnt_t = u64;
 
+/// The type of the device a file lives on.
+pub type dev_t = u64;
+
 /// The type of a file’s group ID.
 pub type gid_t = u32;
 
@@ -113,6 +116,15 @@ pub struct OctalPermissions {
     pub permissions: Permissions,
 }
 
+/// What the current user can actually do with a file, which depends on
+/// which of the sets of permission bits apply to them.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct EffectivePermissions {
+    pub read:     bool,
+    pub write:    bool,
+    pub execute:  bool,
+}
+
 /// A file’s number of hard links on the filesystem.
 ///
 /// Under Unix, a file can exist on the filesystem only once but appear in
@@ -137,6 +149,33 @@ pub struct Links {
 pub struct Inode(pub ino_t);
 
 
+/// A file’s inode along with the device it’s on. Inode numbers are only
+/// unique within one filesystem, so listings that cross mount points need
+/// both to tell files apart.
+#[derive(Copy, Clone)]
+pub struct DeviceInode {
+
+    /// The ID of the device holding the filesystem.
+    pub device: dev_t,
+
+    /// The file’s inode on that filesystem.
+    pub inode: ino_t,
+}
+
+
+/// How many extended attributes a file has, and whether it has an access
+/// control list, for the compact security column.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub struct SecuritySummary {
+
+    /// The number of extended attributes, not counting the ACL’s own.
+    pub xattrs: usize,
+
+    /// Whether the file has an access control list.
+    pub acl: bool,
+}
+
+
 /// The number of blocks that a file takes up on the filesystem, if any.
 #[derive(Copy, Clone)]
 pub enum Blocks {
@@ -149,6 +188,123 @@ pub enum Blocks {
 }
 
 
+/// A file’s generation number, which some filesystems keep so they can tell
+/// apart different files that have used the same inode.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Version {
+
+    /// The filesystem gave this file the given version.
+    Some(u64),
+
+    /// The filesystem doesn’t keep versions, or couldn’t be asked.
+    None,
+}
+
+
+/// A hash of a file’s contents, as a hex string, for the checksum column.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Checksum {
+
+    /// The file’s contents hashed to this.
+    Some(String),
+
+    /// The file isn’t a regular file, or couldn’t be read.
+    None,
+}
+
+
+/// A guess at how a file’s text is encoded, from a byte-order mark or the
+/// bytes at its start, for the encoding column.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Encoding {
+
+    /// Nothing but seven-bit bytes.
+    Ascii,
+
+    /// Valid UTF-8, either with a byte-order mark or without one.
+    Utf8,
+
+    /// A little-endian UTF-16 byte-order mark.
+    Utf16Le,
+
+    /// A big-endian UTF-16 byte-order mark.
+    Utf16Be,
+
+    /// NUL bytes, or bytes that aren’t valid UTF-8.
+    Binary,
+
+    /// The file isn’t a regular file, is empty, or couldn’t be read.
+    None,
+}
+
+impl Encoding {
+
+    /// Guesses the encoding of the given bytes, which should be the start
+    /// of a file. A multi-byte character cut off at the end of the prefix
+    /// doesn’t stop it from counting as UTF-8.
+    pub fn of_bytes(bytes: &[u8]) -> Self {
+        if bytes.is_empty() {
+            Self::None
+        }
+        else if bytes.starts_with(b"\xef\xbb\xbf") {
+            Self::Utf8
+        }
+        else if bytes.starts_with(b"\xff\xfe") {
+            Self::Utf16Le
+        }
+        else if bytes.starts_with(b"\xfe\xff") {
+            Self::Utf16Be
+        }
+        else if bytes.contains(&0) {
+            Self::Binary
+        }
+        else if bytes.is_ascii() {
+            Self::Ascii
+        }
+        else {
+            match std::str::from_utf8(bytes) {
+                Ok(_)                             => Self::Utf8,
+                Err(e) if e.error_len().is_none() => Self::Utf8,
+                Err(_)                            => Self::Binary,
+            }
+        }
+    }
+}
+
+
+/// The short name of the mount that a file is on, for the volume column,
+/// if it’s on one that could be found.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Volume<'a>(pub Option<&'a str>);
+
+
+/// Whether a directory is a mount point, for the mount-info column, and if
+/// it is, whether it’s a bind mount and how mounts beneath it propagate.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum MountInfo {
+    NotMount,
+    Mount { bind: bool, propagation: Propagation },
+}
+
+/// How mounts and unmounts beneath a mount point spread to and from its
+/// peers, as set by `mount --make-shared` and friends.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Propagation {
+
+    /// Events don’t spread either way.
+    Private,
+
+    /// Events spread to and from the mount’s peer group.
+    Shared,
+
+    /// Events spread in from a master mount, but not back out.
+    Slave,
+
+    /// Like private, but the mount can’t be bind-mounted anywhere else.
+    Unbindable,
+}
+
+
 /// The ID of the user that owns a file. This will only ever be a number;
 /// looking up the username is done in the `display` module.
 #[derive(Copy, Clone)]
@@ -158,6 +314,14 @@ pub struct User(pub uid_t);
 #[derive(Copy, Clone)]
 pub struct Group(pub gid_t);
 
+/// A file’s user and group together, for when they get shown in the same
+/// column.
+#[derive(Copy, Clone)]
+pub struct Owner {
+    pub user: User,
+    pub group: Group,
+}
+
 
 /// A file’s size, in bytes. This is usually formatted by the `number_prefix`
 /// crate into something human-readable.
@@ -259,3 +423,70 @@ impl Default for Git {
         }
     }
 }
+
+
+/// How many files with each kind of Git status have been listed, for the
+/// line that gets printed underneath a table with a Git column.
+///
+/// A file that has been staged and then changed again counts as both staged
+/// and modified, but a file with a merge conflict only counts as conflicted.
+/// Ignored files don’t get counted at all, and neither do files in a
+/// repository too big for their statuses to be looked up, though those get
+/// mentioned in a note of their own.
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct GitTally {
+    pub modified:   usize,
+    pub staged:     usize,
+    pub untracked:  usize,
+    pub conflicted: usize,
+    pub clean:      usize,
+    pub skipped:    usize,
+}
+
+impl GitTally {
+
+    /// Counts the given file’s status.
+    pub fn add(&mut self, git: Git) {
+        use self::GitStatus::*;
+
+        if git.staged == Conflicted || git.unstaged == Conflicted {
+            self.conflicted += 1;
+            return;
+        }
+
+        if git.staged != NotModified {
+            self.staged += 1;
+        }
+
+        match git.unstaged {
+            New                 => self.untracked += 1,
+            Ignored             => {},
+            NotModified         => if git.staged == NotModified { self.clean += 1 },
+            _                   => self.modified += 1,
+        }
+    }
+
+    /// The summary line, such as `1 modified, 0 staged, 0 untracked, 2 clean`.
+    /// Conflicts only get mentioned when there are any.
+    pub fn summary(self) -> String {
+        if self.conflicted > 0 {
+            format!("{} modified, {} staged, {} untracked, {} conflicted, {} clean",
+                    self.modified, self.staged, self.untracked, self.conflicted, self.clean)
+        }
+        else {
+            format!("{} modified, {} staged, {} untracked, {} clean",
+                    self.modified, self.staged, self.untracked, self.clean)
+        }
+    }
+
+    /// The note saying how many files had no status to count, because
+    /// their repository has more files than `--git-max-files`, if any.
+    pub fn skipped_note(self) -> Option<String> {
+        if self.skipped > 0 {
+            Some(format!("[Git statuses skipped for {} files, as their repository is over --git-max-files]", self.skipped))
+        }
+        else {
+            None
+        }
+    }
+}
@@ -6,11 +6,17 @@ use std::os::unix::fs::{FileTypeExt, MetadataExt, PermissionsExt};
 #[cfg(windows)]
 use std::os::windows::fs::MetadataExt;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use ansi_term::Style;
+#[cfg(unix)]
+use lazy_static::lazy_static;
 use log::*;
 
+use crate::fs::checksum;
 use crate::fs::dir::Dir;
+use crate::fs::feature::version;
 use crate::fs::fields as f;
 
 
@@ -35,6 +41,11 @@ pub struct File<'dir> {
     /// This is queried many times over, so it’s worth caching it.
     pub ext: Option<String>,
 
+    /// The file’s name’s compound extension, if it has one, such as
+    /// `tar.gz`. This gets compared over and over when sorting by it, so
+    /// it gets worked out once up front too.
+    pub compound_ext: Option<String>,
+
     /// The path that begat this file.
     ///
     /// Even though the file’s name is extracted, the path needs to be kept
@@ -66,6 +77,14 @@ pub struct File<'dir> {
     /// directory’s children, and are in fact added specifically by exa; this
     /// means that they should be skipped when recursing.
     pub is_all_all: bool,
+
+    /// The style for this file’s name that comes from its name alone, once
+    /// it’s been worked out.
+    ///
+    /// Finding it means checking the name against every list of extensions,
+    /// and a name can get painted more than once — in the grid-details view,
+    /// or with icons turned on — so it’s worth holding on to.
+    name_style: Mutex<Option<Style>>,
 }
 
 impl<'dir> File<'dir> {
@@ -75,36 +94,36 @@ impl<'dir> File<'dir> {
     {
         let parent_dir = parent_dir.into();
         let name       = filename.into().unwrap_or_else(|| File::filename(&path));
-        let ext        = File::ext(&path);
 
         debug!("Statting file {:?}", &path);
         let metadata   = std::fs::symlink_metadata(&path)?;
-        let is_all_all = false;
 
-        Ok(File { name, ext, path, metadata, parent_dir, is_all_all })
+        Ok(File::with_metadata(path, name, metadata, parent_dir, false))
     }
 
     pub fn new_aa_current(parent_dir: &'dir Dir) -> io::Result<File<'dir>> {
         let path       = parent_dir.path.clone();
-        let ext        = File::ext(&path);
 
         debug!("Statting file {:?}", &path);
         let metadata   = std::fs::symlink_metadata(&path)?;
-        let is_all_all = true;
-        let parent_dir = Some(parent_dir);
 
-        Ok(File { path, parent_dir, metadata, ext, name: ".".into(), is_all_all })
+        Ok(File::with_metadata(path, ".".into(), metadata, Some(parent_dir), true))
     }
 
     pub fn new_aa_parent(path: PathBuf, parent_dir: &'dir Dir) -> io::Result<File<'dir>> {
-        let ext        = File::ext(&path);
-
         debug!("Statting file {:?}", &path);
         let metadata   = std::fs::symlink_metadata(&path)?;
-        let is_all_all = true;
-        let parent_dir = Some(parent_dir);
 
-        Ok(File { path, parent_dir, metadata, ext, name: "..".into(), is_all_all })
+        Ok(File::with_metadata(path, "..".into(), metadata, Some(parent_dir), true))
+    }
+
+    /// Puts together a file from its already-statted metadata. Every other
+    /// constructor goes through here, so the cached fields all start off
+    /// empty in the same way.
+    fn with_metadata(path: PathBuf, name: String, metadata: std::fs::Metadata, parent_dir: Option<&'dir Dir>, is_all_all: bool) -> File<'dir> {
+        let ext = File::ext(&path);
+        let compound_ext = File::compound_ext(&name);
+        File { name, ext, compound_ext, path, metadata, parent_dir, is_all_all, name_style: Mutex::new(None) }
     }
 
     /// A file’s name is derived from its string. This needs to handle directories
@@ -137,6 +156,18 @@ impl<'dir> File<'dir> {
             .to_ascii_lowercase())
     }
 
+    /// Extract the *compound* extension from a file name, which is
+    /// everything after the first dot that isn’t at the very start, such as
+    /// `tar.gz` for `backup.tar.gz`. Like the regular extension, this is
+    /// lowercased. Dotfiles with no other dots in their names don’t have one.
+    pub fn compound_ext(name: &str) -> Option<String> {
+        let without_leading = name.strip_prefix('.').unwrap_or(name);
+
+        without_leading.find('.')
+            .map(|p| without_leading[p + 1 ..]
+            .to_ascii_lowercase())
+    }
+
     /// Whether this file is a directory on the filesystem.
     pub fn is_directory(&self) -> bool {
         self.metadata.is_dir()
@@ -212,11 +243,47 @@ impl<'dir> File<'dir> {
         self.metadata.file_type().is_socket()
     }
 
+    /// The style for this file’s name that comes from its name alone. The
+    /// given function works it out the first time this is called, and the
+    /// same style gets returned every time after that.
+    pub fn name_style<F: FnOnce() -> Style>(&self, work_out: F) -> Style {
+        let mut name_style = self.name_style.lock().unwrap();
+        *name_style.get_or_insert_with(work_out)
+    }
+
+    /// Whether this file is a regular file with no contents, or a directory
+    /// with no entries. Anything else, including a directory that can’t be
+    /// read, doesn’t count as empty.
+    pub fn is_empty(&self) -> bool {
+        if self.is_directory() {
+            std::fs::read_dir(&self.path)
+                .map(|mut entries| entries.next().is_none())
+                .unwrap_or(false)
+        }
+        else {
+            self.is_file() && self.metadata.len() == 0
+        }
+    }
+
+    /// The number of entries in this directory, without going any further
+    /// down. Anything that isn’t a directory, or a directory that can’t be
+    /// read, has none.
+    pub fn child_count(&self) -> usize {
+        if self.is_directory() {
+            std::fs::read_dir(&self.path)
+                .map(Iterator::count)
+                .unwrap_or(0)
+        }
+        else {
+            0
+        }
+    }
+
 
     /// Re-prefixes the path pointed to by this file, if it’s a symlink, to
     /// make it an absolute path that can be accessed from whichever
     /// directory exa is being run from.
-    fn reorient_target_path(&self, path: &Path) -> PathBuf {
+    pub fn reorient_target_path(&self, path: &Path) -> PathBuf {
         if path.is_absolute() {
             path.to_path_buf()
         }
@@ -255,13 +322,19 @@ impl<'dir> File<'dir> {
 
         let absolute_path = self.reorient_target_path(&path);
 
+        // Walk the rest of the chain ourselves, so a pathologically long
+        // one gets reported as broken rather than left up to the OS.
+        if ! Self::chain_ends_within(&absolute_path, MAX_LINK_HOPS - 1) {
+            error!("Too many levels of symbolic links following {:?}", &path);
+            return FileTarget::Broken(path);
+        }
+
         // Use plain `metadata` instead of `symlink_metadata` - we *want* to
         // follow links.
         match std::fs::metadata(&absolute_path) {
             Ok(metadata) => {
-                let ext  = File::ext(&path);
                 let name = File::filename(&path);
-                let file = File { parent_dir: None, path, ext, metadata, name, is_all_all: false };
+                let file = File::with_metadata(path, name, metadata, None, false);
                 FileTarget::Ok(Box::new(file))
             }
             Err(e) => {
@@ -294,6 +367,15 @@ impl<'dir> File<'dir> {
         f::Inode(self.metadata.ino())
     }
 
+    /// This file’s inode, paired with the device it lives on.
+    #[cfg(unix)]
+    pub fn device_inode(&self) -> f::DeviceInode {
+        f::DeviceInode {
+            device: self.metadata.dev(),
+            inode: self.metadata.ino(),
+        }
+    }
+
     /// This file’s number of filesystem blocks.
     ///
     /// (Not the size of each block, which we don’t actually report on)
@@ -307,6 +389,62 @@ impl<'dir> File<'dir> {
         }
     }
 
+    /// This file’s generation number, if its filesystem keeps one.
+    ///
+    /// Only regular files and directories get asked, as opening anything
+    /// else (such as a named pipe) could block.
+    pub fn version(&self) -> f::Version {
+        if self.is_file() || self.is_directory() {
+            version::version_of(&version::Ioctl, &self.path)
+        }
+        else {
+            f::Version::None
+        }
+    }
+
+    /// A hash of this file’s contents, using the given algorithm.
+    ///
+    /// This reads the whole file, so it’s only done when asked for. Only
+    /// regular files get hashed; anything else, or a file that can’t be
+    /// read, gets no checksum.
+    pub fn checksum(&self, algorithm: checksum::Algorithm) -> f::Checksum {
+        if ! self.is_file() {
+            return f::Checksum::None;
+        }
+
+        match algorithm.hash_file(&self.path) {
+            Ok(hash) => f::Checksum::Some(hash),
+            Err(e) => {
+                error!("Error hashing {:?}: {:#?}", &self.path, e);
+                f::Checksum::None
+            }
+        }
+    }
+
+    /// A guess at how this file’s contents are encoded.
+    ///
+    /// Only the first kilobyte of a regular file is read, so a file that
+    /// starts out as text but has binary further in still counts as text.
+    pub fn encoding(&self) -> f::Encoding {
+        use std::io::Read;
+
+        if ! self.is_file() {
+            return f::Encoding::None;
+        }
+
+        let mut bytes = Vec::with_capacity(ENCODING_PREFIX_LENGTH as usize);
+        let result = std::fs::File::open(&self.path)
+            .and_then(|file| file.take(ENCODING_PREFIX_LENGTH).read_to_end(&mut bytes));
+
+        match result {
+            Ok(_) => f::Encoding::of_bytes(&bytes),
+            Err(e) => {
+                error!("Error reading {:?}: {:#?}", &self.path, e);
+                f::Encoding::None
+            }
+        }
+    }
+
     /// The ID of the user that own this file.
     #[cfg(unix)]
     pub fn user(&self) -> f::User {
@@ -470,6 +608,23 @@ impl<'dir> File<'dir> {
         }
     }
 
+    /// Whether the current user is shut out from reading this file, going by
+    /// its permission bits and who owns it. Access control lists and the
+    /// like aren’t taken into account.
+    #[cfg(unix)]
+    pub fn is_unreadable(&self) -> bool {
+        let owner = (self.metadata.uid(), self.metadata.gid());
+        ! readable_by(self.permissions(), owner, users::get_effective_uid(), &CURRENT_GROUPS)
+    }
+
+    /// What the current user can do with this file, going by its permission
+    /// bits and who owns it, in the same way as `is_unreadable`.
+    #[cfg(unix)]
+    pub fn effective_permissions(&self) -> f::EffectivePermissions {
+        let owner = (self.metadata.uid(), self.metadata.gid());
+        access_for(self.permissions(), owner, users::get_effective_uid(), &CURRENT_GROUPS)
+    }
+
     #[cfg(windows)]
     pub fn attributes(&self) -> f::Attributes {
         let bits = self.metadata.file_attributes();
@@ -501,9 +656,45 @@ impl<'dir> File<'dir> {
     pub fn name_is_one_of(&self, choices: &[&str]) -> bool {
         choices.contains(&&self.name[..])
     }
+
+    /// Whether following the symlinks starting at the given path reaches
+    /// something that isn’t a symlink within the given number of hops.
+    /// Paths that can’t be read count as the end of the chain, as
+    /// following them is going to fail anyway.
+    fn chain_ends_within(start: &Path, hops: usize) -> bool {
+        let mut current = start.to_path_buf();
+
+        for _ in 0 .. hops {
+            match std::fs::symlink_metadata(&current) {
+                Ok(m) if m.file_type().is_symlink() => {}
+                _ => return true,
+            }
+
+            let next = match std::fs::read_link(&current) {
+                Ok(p)   => p,
+                Err(_)  => return true,
+            };
+
+            current = match current.parent() {
+                Some(parent) if next.is_relative() => parent.join(next),
+                _                                  => next,
+            };
+        }
+
+        ! matches!(std::fs::symlink_metadata(&current), Ok(m) if m.file_type().is_symlink())
+    }
 }
 
 
+/// The most symlinks to follow when finding a link’s target before giving
+/// up and calling it broken. This is the same limit Linux uses before it
+/// returns `ELOOP`.
+const MAX_LINK_HOPS: usize = 40;
+
+/// How many bytes from the start of a file get read to guess its encoding.
+const ENCODING_PREFIX_LENGTH: u64 = 1024;
+
+
 impl<'a> AsRef<File<'a>> for File<'a> {
     fn as_ref(&self) -> &File<'a> {
         self
@@ -541,6 +732,51 @@ impl<'dir> FileTarget<'dir> {
 }
 
 
+#[cfg(unix)]
+lazy_static! {
+
+    /// The IDs of every group the current user is in, which gets looked up
+    /// only once, as it doesn’t change while exa is running.
+    static ref CURRENT_GROUPS: Vec<u32> = {
+        let mut gids = vec![ users::get_effective_gid() ];
+        if let Ok(groups) = users::group_access_list() {
+            gids.extend(groups.iter().map(users::Group::gid));
+        }
+        gids
+    };
+}
+
+/// Whether a user with the given user ID and list of group IDs can read a
+/// file with the given permissions and owner.
+#[cfg(unix)]
+fn readable_by(permissions: f::Permissions, owner: (u32, u32), uid: u32, gids: &[u32]) -> bool {
+    access_for(permissions, owner, uid, gids).read
+}
+
+/// What a user with the given user ID and list of group IDs can do with a
+/// file with the given permissions and owner. Only one set of bits applies:
+/// the owner is judged by the user bits even if the group bits say more.
+/// The superuser can read and write anything, and execute anything that
+/// anyone at all can execute.
+#[cfg(unix)]
+fn access_for(p: f::Permissions, (owner, group): (u32, u32), uid: u32, gids: &[u32]) -> f::EffectivePermissions {
+    let (read, write, execute) = if uid == 0 {
+        (true, true, p.user_execute || p.group_execute || p.other_execute)
+    }
+    else if uid == owner {
+        (p.user_read, p.user_write, p.user_execute)
+    }
+    else if gids.contains(&group) {
+        (p.group_read, p.group_write, p.group_execute)
+    }
+    else {
+        (p.other_read, p.other_write, p.other_execute)
+    };
+
+    f::EffectivePermissions { read, write, execute }
+}
+
+
 /// More readable aliases for the permission bits exposed by libc.
 #[allow(trivial_numeric_casts)]
 #[cfg(unix)]
@@ -587,6 +823,22 @@ mod ext_test {
     fn no_extension() {
         assert_eq!(None, File::ext(Path::new("jarlsberg")))
     }
+
+    #[test]
+    fn compound_extension() {
+        assert_eq!(Some("tar.gz".to_string()), File::compound_ext("backup.TAR.gz"))
+    }
+
+    #[test]
+    fn compound_single_extension() {
+        assert_eq!(Some("gz".to_string()), File::compound_ext("backup.gz"))
+    }
+
+    #[test]
+    fn compound_dotfile() {
+        assert_eq!(None, File::compound_ext(".vimrc"));
+        assert_eq!(Some("tar.gz".to_string()), File::compound_ext(".config.tar.gz"))
+    }
 }
 
 
@@ -626,3 +878,172 @@ mod filename_test {
         assert_eq!("/", File::filename(Path::new("/")))
     }
 }
+
+
+#[cfg(test)]
+mod emptiness_test {
+    use super::File;
+    use crate::fs::test::TempDir;
+
+    #[test]
+    fn emptiness() {
+        let path = TempDir::new("empty");
+        std::fs::create_dir_all(path.join("empty-dir")).unwrap();
+        std::fs::create_dir_all(path.join("full-dir")).unwrap();
+        std::fs::write(path.join("full-dir").join("file"), b"").unwrap();
+        std::fs::write(path.join("empty-file"), b"").unwrap();
+        std::fs::write(path.join("full-file"), b"contents").unwrap();
+
+        let is_empty = |name| File::from_args(path.join(name), None, None).unwrap().is_empty();
+        assert!(is_empty("empty-file"));
+        assert!(! is_empty("full-file"));
+        assert!(is_empty("empty-dir"));
+        assert!(! is_empty("full-dir"));
+    }
+}
+
+
+#[cfg(test)]
+mod encoding_test {
+    use super::File;
+    use crate::fs::fields as f;
+    use crate::fs::test::TempDir;
+
+    #[test]
+    fn bytes() {
+        assert_eq!(f::Encoding::of_bytes(b"plain text\n"), f::Encoding::Ascii);
+        assert_eq!(f::Encoding::of_bytes("caf\u{e9}\n".as_bytes()), f::Encoding::Utf8);
+        assert_eq!(f::Encoding::of_bytes(b"caf\xc3"), f::Encoding::Utf8);
+        assert_eq!(f::Encoding::of_bytes(b"\xfe\xff\0a"), f::Encoding::Utf16Be);
+        assert_eq!(f::Encoding::of_bytes(b"\x7fELF\x02\x01\x01\0"), f::Encoding::Binary);
+        assert_eq!(f::Encoding::of_bytes(b"caf\xe9 au lait"), f::Encoding::Binary);
+        assert_eq!(f::Encoding::of_bytes(b""), f::Encoding::None);
+    }
+
+    #[test]
+    fn files() {
+        let path = TempDir::new("encoding");
+        std::fs::write(path.join("wide.txt"), b"\xff\xfeh\0i\0\n\0").unwrap();
+        std::fs::write(path.join("plain.txt"), b"hi\n").unwrap();
+
+        let encoding = |name| File::from_args(path.join(name), None, None).unwrap().encoding();
+        assert_eq!(encoding("wide.txt"), f::Encoding::Utf16Le);
+        assert_eq!(encoding("plain.txt"), f::Encoding::Ascii);
+        assert_eq!(encoding("."), f::Encoding::None);
+    }
+}
+
+
+#[cfg(test)]
+#[cfg(unix)]
+mod link_chain_test {
+    use super::File;
+    use std::os::unix::fs::symlink;
+    use crate::fs::test::TempDir;
+
+    /// Makes a chain of the given number of symlinks, each pointing to the
+    /// next, with the last pointing to a regular file, and returns the
+    /// target of the first one.
+    fn chain_of(length: usize) -> super::FileTarget<'static> {
+        let path = TempDir::new(&format!("chain-{}", length));
+        std::fs::write(path.join("target"), b"").unwrap();
+
+        for i in 0 .. length {
+            let next = if i + 1 == length { "target".to_string() } else { format!("link{}", i + 1) };
+            symlink(next, path.join(format!("link{}", i))).unwrap();
+        }
+
+        File::from_args(path.join("link0"), None, None).unwrap().link_target()
+    }
+
+    #[test]
+    fn short_chain() {
+        assert!(matches!(chain_of(10), super::FileTarget::Ok(_)));
+    }
+
+    #[test]
+    fn chain_at_the_limit() {
+        assert!(matches!(chain_of(super::MAX_LINK_HOPS), super::FileTarget::Ok(_)));
+    }
+
+    #[test]
+    fn chain_past_the_limit() {
+        assert!(matches!(chain_of(super::MAX_LINK_HOPS + 5), super::FileTarget::Broken(_)));
+    }
+
+    #[test]
+    fn cycle() {
+        let path = TempDir::new("cycle");
+        symlink("ouroboros", path.join("ouroboros")).unwrap();
+
+        let target = File::from_args(path.join("ouroboros"), None, None).unwrap().link_target();
+        assert!(target.is_broken());
+    }
+}
+
+
+#[cfg(test)]
+#[cfg(unix)]
+mod readable_test {
+    use super::*;
+    use crate::fs::test::TempDir;
+
+    fn permissions(user_read: bool, group_read: bool, other_read: bool) -> f::Permissions {
+        f::Permissions {
+            user_read, user_write: false, user_execute: false,
+            group_read, group_write: false, group_execute: false,
+            other_read, other_write: false, other_execute: false,
+            sticky: false, setgid: false, setuid: false,
+        }
+    }
+
+    #[test]
+    fn nothing_for_someone_else() {
+        assert!(! readable_by(permissions(false, false, false), (1001, 1001), 1000, &[ 1000 ]));
+    }
+
+    #[test]
+    fn owner() {
+        assert!(readable_by(permissions(true, false, false), (1000, 1001), 1000, &[ 1000 ]));
+        assert!(! readable_by(permissions(false, true, true), (1000, 1000), 1000, &[ 1000 ]));
+    }
+
+    #[test]
+    fn group_member() {
+        assert!(readable_by(permissions(false, true, false), (1001, 50), 1000, &[ 1000, 50 ]));
+        assert!(! readable_by(permissions(true, false, true), (1001, 50), 1000, &[ 1000, 50 ]));
+    }
+
+    #[test]
+    fn everyone_else() {
+        assert!(readable_by(permissions(false, false, true), (1001, 1001), 1000, &[ 1000 ]));
+    }
+
+    #[test]
+    fn superuser() {
+        assert!(readable_by(permissions(false, false, false), (1001, 1001), 0, &[ 0 ]));
+    }
+
+    #[test]
+    fn own_private_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new("effective");
+        let path = dir.join("file");
+        std::fs::write(&path, b"").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+
+        let file = File::from_args(path, None, None).unwrap();
+        let access = file.effective_permissions();
+        assert_eq!(access, f::EffectivePermissions { read: true, write: true, execute: false });
+    }
+
+    #[test]
+    fn superuser_executes_if_anyone_can() {
+        let mut bits = permissions(false, false, false);
+        assert!(! access_for(bits, (1001, 1001), 0, &[ 0 ]).execute);
+
+        bits.other_execute = true;
+        assert!(access_for(bits, (1001, 1001), 0, &[ 0 ]).execute);
+    }
+}
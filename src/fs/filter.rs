@@ -1,12 +1,20 @@
 //! Filtering and sorting the list of files before displaying them.
 
 use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
 use std::iter::FromIterator;
 #[cfg(unix)]
 use std::os::unix::fs::MetadataExt;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use log::*;
 
 use crate::fs::DotFilter;
 use crate::fs::File;
+use crate::fs::fields as f;
 
 
 /// The **file filter** processes a list of files before displaying them to
@@ -30,17 +38,38 @@ pub struct FileFilter {
     /// second. Some users prefer it like this.
     pub list_dirs_first: bool,
 
+    /// Where dotfiles should be listed among the other files.
+    pub dotfile_order: DotfileOrder,
+
     /// The metadata field to sort by.
     pub sort_field: SortField,
 
+    /// The metadata field to sort files by when they compare equal using
+    /// the main sort field, if one was given.
+    pub secondary_sort: Option<SortField>,
+
+    /// How to compare file names when either sort field uses them.
+    pub sort_order: SortOrder,
+
     /// Whether to reverse the sorting order. This would sort the largest
     /// files first, or files starting with Z, or the most-recently-changed
-    /// ones, depending on the sort field.
+    /// ones, depending on the sort field. Each level of a tree gets reversed
+    /// on its own, the same as each directory does when listing flat.
     pub reverse: bool,
 
+    /// What reversing a sort by type does to the order of the types.
+    pub type_order: TypeOrder,
+
+    /// Whether to warn when sorting by access time, but the access times
+    /// look like they can’t be trusted.
+    pub atime_warning: AtimeWarning,
+
     /// Whether to only show directories.
     pub only_dirs: bool,
 
+    /// How long ago files must have been modified to be shown.
+    pub modified_window: ModifiedWindow,
+
     /// Which invisible “dot” files to include when listing a directory.
     ///
     /// Files starting with a single “.” are used to determine “system” or
@@ -60,17 +89,41 @@ pub struct FileFilter {
 
     /// Whether to ignore Git-ignored patterns.
     pub git_ignore: GitIgnore,
+
+    /// Whether to hide files matched by the `.ignore` and `.fdignore` files
+    /// in the directory being listed, or in any directory above it.
+    pub ignore_files: IgnoreFiles,
+
+    /// The most entries to list from each directory, if there’s a limit.
+    /// The entries kept are the first ones after sorting and reversing.
+    pub max_entries: Option<usize>,
 }
 
 impl FileFilter {
     /// Remove every file in the given vector that does *not* pass the
-    /// filter predicate for files found inside a directory.
-    pub fn filter_child_files(&self, files: &mut Vec<File<'_>>) {
-        files.retain(|f| ! self.ignore_patterns.is_ignored(&f.name));
+    /// filter predicate for files found inside a directory, somewhere under
+    /// the given directory from the command-line.
+    pub fn filter_child_files(&self, files: &mut Vec<File<'_>>, root: &Path) {
+        files.retain(|f| ! self.ignore_patterns.is_ignored_file(&f.path, &f.name, root));
+
+        if let IgnoreFiles::Respect(cache) = &self.ignore_files {
+            let mut layers = HashMap::new();
+            files.retain(|f| {
+                let parent = f.path.parent().unwrap_or_else(|| Path::new(""));
+                let (dir, layer) = layers.entry(parent.to_path_buf())
+                                         .or_insert_with(|| cache.layers_for_path(parent));
+                ! layer.is_ignored(&dir.join(&f.name), &f.name, f.is_directory())
+            });
+        }
 
         if self.only_dirs {
             files.retain(File::is_directory);
         }
+
+        if self.modified_window.is_bounded() {
+            let now = SystemTime::now();
+            files.retain(|f| self.modified_window.contains(f.modified_time(), now));
+        }
     }
 
     /// Remove every file in the given vector that does *not* pass the
@@ -84,22 +137,70 @@ impl FileFilter {
     /// from the glob, even though the globbing is done by the shell!
     pub fn filter_argument_files(&self, files: &mut Vec<File<'_>>) {
         files.retain(|f| {
-            ! self.ignore_patterns.is_ignored(&f.name)
+            ! self.ignore_patterns.is_ignored_file(&f.path, &f.name, Path::new(""))
         });
     }
 
+    /// Sort the files in the given vector, then cut it down to the maximum
+    /// number of entries. The cut has to come last, so that `--reverse`
+    /// keeps the entries that end up at the top of the list.
+    pub fn sort_and_limit<'a, F>(&self, files: &mut Vec<F>)
+    where F: AsRef<File<'a>>
+    {
+        self.sort_files(files);
+
+        if let Some(max) = self.max_entries {
+            files.truncate(max);
+        }
+    }
+
     /// Sort the files in the given vector based on the sort field option.
+    ///
+    /// The sort is *stable*: files that compare equal on every sort field
+    /// keep the order they were given in, which is usually the order they
+    /// were read from the directory. Listing directories first is done as
+    /// a second pass that relies on this too.
     pub fn sort_files<'a, F>(&self, files: &mut [F])
     where F: AsRef<File<'a>>
     {
-        files.sort_by(|a, b| {
-            self.sort_field.compare_files(a.as_ref(), b.as_ref())
-        });
+        let by_type = self.sorts_by(SortField::FileType);
+        let by_child_count = self.sorts_by(SortField::ChildCount);
+
+        if by_type || by_child_count {
+            let get_keys = |f: &F| SortKeys {
+                file_type:    if by_type        { Some(f.as_ref().type_char()) }   else { None },
+                child_count:  if by_child_count { Some(f.as_ref().child_count()) } else { None },
+            };
+
+            sort_with_cached_keys(files, get_keys, |(a, a_keys), (b, b_keys)| {
+                self.compare_keyed_files((a.as_ref(), *a_keys), (b.as_ref(), *b_keys))
+            });
+        }
+        else {
+            files.sort_by(|a, b| {
+                self.compare_files(a.as_ref(), b.as_ref())
+            });
+        }
 
         if self.reverse {
             files.reverse();
         }
 
+        // Reversing a sort by type also reverses the order of the types, so
+        // that directories come last. Sorting by just the type afterwards
+        // puts them back, keeping each type’s files in reverse order.
+        if self.type_order == TypeOrder::Stable && self.sort_field == SortField::FileType {
+            files.sort_by_cached_key(|f| f.as_ref().type_char());
+        }
+
+        // The `.` and `..` entries stay at the top, ahead of everything.
+        if self.dotfile_order == DotfileOrder::Last {
+            files.sort_by_key(|f| {
+                let f = f.as_ref();
+                f.name.starts_with('.') && ! f.is_all_all
+            });
+        }
+
         if self.list_dirs_first {
             // This relies on the fact that `sort_by` is *stable*: it will keep
             // adjacent elements next to each other.
@@ -109,6 +210,97 @@ impl FileFilter {
             });
         }
     }
+
+    /// Compares two files using the sort field, falling back to the
+    /// secondary sort field for files that compare equal.
+    pub fn compare_files(&self, a: &File<'_>, b: &File<'_>) -> Ordering {
+        self.compare_keyed_files((a, SortKeys::default()), (b, SortKeys::default()))
+    }
+
+    /// Whether the given field is used to sort, either first or to break
+    /// ties.
+    fn sorts_by(&self, field: SortField) -> bool {
+        self.sort_field == field || self.secondary_sort == Some(field)
+    }
+
+    /// Whether to warn that the given files’ access times look unreliable,
+    /// when they’re being sorted by them. On filesystems mounted with
+    /// `noatime`, access times never get updated, so they stay the same as
+    /// the modified times, and an access time sort just sorts by those.
+    pub fn should_warn_about_atimes(&self, files: &[File<'_>]) -> bool {
+        self.atime_warning == AtimeWarning::CheckAndWarn
+            && self.sorts_by(SortField::AccessedDate)
+            && access_times_match(files.iter().map(|f| (f.accessed_time(), f.modified_time())))
+    }
+
+    /// Compares two files like `compare_files`, using whichever of their
+    /// sort keys have already been worked out.
+    fn compare_keyed_files(&self, a: (&File<'_>, SortKeys), b: (&File<'_>, SortKeys)) -> Ordering {
+        let order = self.sort_field.compare_keyed_files(a, b, self.sort_order);
+
+        match self.secondary_sort {
+            Some(secondary) if order == Ordering::Equal  => secondary.compare_keyed_files(a, b, self.sort_order),
+            _                                            => order,
+        }
+    }
+}
+
+
+/// Whether every one of the given access times is the same as its modified
+/// time. There has to be at least one pair to go on, and a missing time
+/// means there’s no telling, so that doesn’t count as a match.
+fn access_times_match<I>(times: I) -> bool
+where I: IntoIterator<Item = (Option<SystemTime>, Option<SystemTime>)>
+{
+    let mut any = false;
+
+    for (accessed, modified) in times {
+        match (accessed, modified) {
+            (Some(a), Some(m)) if a == m  => any = true,
+            _                             => return false,
+        }
+    }
+
+    any
+}
+
+
+/// The sort keys that are slow to work out, which get worked out once for
+/// each file before sorting when the sort needs them. Any that are missing
+/// get worked out during the comparison instead.
+#[derive(Copy, Clone, Default)]
+struct SortKeys {
+    file_type: Option<f::Type>,
+    child_count: Option<usize>,
+}
+
+
+/// Sorts the items by a comparison that needs a key for each item that’s
+/// expensive to work out. The keys get worked out once per item up front,
+/// instead of twice per comparison. Unlike `sort_by_cached_key`, the
+/// comparison gets to look at the items as well as their keys.
+///
+/// Like `sort_by`, this sort is stable.
+fn sort_with_cached_keys<T, K, G, C>(items: &mut [T], get_key: G, compare: C)
+where G: Fn(&T) -> K,
+      C: Fn((&T, &K), (&T, &K)) -> Ordering,
+{
+    let keys = items.iter().map(get_key).collect::<Vec<_>>();
+    let mut order = (0 .. items.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| compare((&items[a], &keys[a]), (&items[b], &keys[b])));
+
+    // Move each item to its sorted position by following the cycles in the
+    // permutation, so the items never need to be cloned.
+    for start in 0 .. items.len() {
+        let mut current = start;
+        while order[current] != start {
+            let next = order[current];
+            items.swap(current, next);
+            order[current] = current;
+            current = next;
+        }
+        order[current] = current;
+    }
 }
 
 
@@ -126,6 +318,11 @@ pub enum SortField {
     /// The file’s extension, with extensionless files being listed first.
     Extension(SortCase),
 
+    /// The file’s compound extension — everything after the first dot that
+    /// isn’t at the start of its name — so `a.tar.gz` gets sorted under
+    /// `tar.gz` rather than under `gz`.
+    CompoundExtension(SortCase),
+
     /// The file’s size, in bytes.
     Size,
 
@@ -166,7 +363,9 @@ pub enum SortField {
     /// The type of the file: directories, links, pipes, regular, files, etc.
     ///
     /// Files are ordered according to the `PartialOrd` implementation of
-    /// `fs::fields::Type`, so changing that will change this.
+    /// `fs::fields::Type`, so changing that will change this. Files of the
+    /// same type are ordered by name, and `--reverse` flips both orders,
+    /// unless `--type-stable` is given to keep the types as they are.
     FileType,
 
     /// The “age” of the file, which is the time it was modified sorted
@@ -183,6 +382,22 @@ pub enum SortField {
     /// The file's name, however if the name of the file begins with `.`
     /// ignore the leading `.` and then sort as Name
     NameMixHidden(SortCase),
+
+    /// The first run of digits in the file’s name, compared as a number,
+    /// with the rest of the name ignored. Files without any digits in their
+    /// names come after those with, and ties are broken by name.
+    ///
+    /// This is for files such as `01-intro`, `2-setup`, and `10-end`, where
+    /// the number is the only part that matters.
+    Numeric,
+
+    /// The number of entries in each directory, not counting any further
+    /// down. Anything that isn’t a directory counts as having none, and
+    /// ties are broken by name.
+    ///
+    /// This has to read every directory being listed, so it’s slower than
+    /// the other fields.
+    ChildCount,
 }
 
 /// Whether a field should be sorted case-sensitively or case-insensitively.
@@ -192,8 +407,8 @@ pub enum SortField {
 /// insensitive. Would a case-sensitive sort put capital letters first because
 /// it takes the case of the letters into account, or intermingle them with
 /// lowercase letters because it takes the difference between the two cases
-/// into account? I gave up and just named these two variants after the
-/// effects they have.
+/// into account? I gave up and just named these variants after the effects
+/// they have.
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
 pub enum SortCase {
 
@@ -203,26 +418,97 @@ pub enum SortCase {
 
     /// Sort files case-insensitively, with ‘A’ being equal to ‘a’.
     AaBbCc,
+
+    /// Sort files case-insensitively, like a dictionary, but with case
+    /// breaking any ties, so ‘a’ comes just before ‘A’.
+    Dictionary,
+}
+
+/// How to compare two file names with each other.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum SortOrder {
+
+    /// Compare runs of digits by their values, so `file2` comes before
+    /// `file10`, using the `natord` crate.
+    Natural,
+
+    /// Compare names byte by byte, like `LC_ALL=C sort`, so `file10` comes
+    /// before `file2`.
+    Byte,
+}
+
+impl SortOrder {
+
+    /// Compares two names in this order, taking their case into account
+    /// or not.
+    fn compare(self, a: &str, b: &str, case: SortCase) -> Ordering {
+        match (self, case) {
+            (Self::Natural, SortCase::ABCabc)      => natord::compare(a, b),
+            (Self::Natural, SortCase::AaBbCc)      => natord::compare_ignore_case(a, b),
+            (Self::Byte,    SortCase::ABCabc)      => a.cmp(b),
+            (Self::Byte,    SortCase::AaBbCc)      => a.bytes().map(|c| c.to_ascii_lowercase())
+                                                      .cmp(b.bytes().map(|c| c.to_ascii_lowercase())),
+
+            // Swapping the case of each letter before comparing them puts
+            // lowercase letters before uppercase ones.
+            (_,             SortCase::Dictionary)  => self.compare(a, b, SortCase::AaBbCc)
+                                                      .then_with(|| a.chars().map(swap_case).cmp(b.chars().map(swap_case))),
+        }
+    }
+}
+
+/// What reversing a sort by type does to the types themselves.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum TypeOrder {
+
+    /// Reverse the order of the types along with the files, so that
+    /// directories come last.
+    Reversible,
+
+    /// Leave the types in their usual order, and only reverse the files
+    /// within each type.
+    Stable,
+}
+
+/// Turns an uppercase letter into a lowercase one, and the other way
+/// around. Anything that isn’t a letter is left alone.
+fn swap_case(c: char) -> char {
+    if c.is_ascii_uppercase()       { c.to_ascii_lowercase() }
+    else if c.is_ascii_lowercase()  { c.to_ascii_uppercase() }
+    else                            { c }
 }
 
 impl SortField {
 
     /// Compares two files to determine the order they should be listed in,
-    /// depending on the search field.
+    /// depending on the search field, with names compared in the given order.
     ///
     /// The `natord` crate is used here to provide a more *natural* sorting
     /// order than just sorting character-by-character. This splits filenames
     /// into groups between letters and numbers, and then sorts those blocks
     /// together, so `file10` will sort after `file9`, instead of before it
     /// because of the `1`.
-    pub fn compare_files(self, a: &File<'_>, b: &File<'_>) -> Ordering {
-        use self::SortCase::{ABCabc, AaBbCc};
+    ///
+    /// A run of digits with a leading zero gets compared character by
+    /// character rather than by value, as though it were the part of a
+    /// decimal after the point. This means `01` still sorts before `2` and
+    /// `10`, but a list that mixes padded and unpadded names, such as
+    /// `chapter1` and `chapter01`, won’t be ordered purely by value. Use
+    /// `Numeric` for a sort that only looks at the value.
+    ///
+    /// With `SortOrder::Byte`, none of this happens, and names get compared
+    /// one byte at a time instead.
+    pub fn compare_files(self, a: &File<'_>, b: &File<'_>, order: SortOrder) -> Ordering {
+        self.compare_keyed_files((a, SortKeys::default()), (b, SortKeys::default()), order)
+    }
 
+    /// Compares two files like `compare_files`, using their sort keys if
+    /// they’ve already been worked out, rather than working them out again.
+    fn compare_keyed_files(self, (a, a_keys): (&File<'_>, SortKeys), (b, b_keys): (&File<'_>, SortKeys), order: SortOrder) -> Ordering {
         match self {
             Self::Unsorted  => Ordering::Equal,
 
-            Self::Name(ABCabc)  => natord::compare(&a.name, &b.name),
-            Self::Name(AaBbCc)  => natord::compare_ignore_case(&a.name, &b.name),
+            Self::Name(case)    => order.compare(&a.name, &b.name, case),
 
             Self::Size          => a.metadata.len().cmp(&b.metadata.len()),
             #[cfg(unix)]
@@ -233,29 +519,57 @@ impl SortField {
             Self::CreatedDate   => a.created_time().cmp(&b.created_time()),
             Self::ModifiedAge   => b.modified_time().cmp(&a.modified_time()),  // flip b and a
 
-            Self::FileType => match a.type_char().cmp(&b.type_char()) { // todo: this recomputes
-                Ordering::Equal  => natord::compare(&*a.name, &*b.name),
-                order            => order,
+            Self::FileType => match a_keys.file_type.unwrap_or_else(|| a.type_char()).cmp(&b_keys.file_type.unwrap_or_else(|| b.type_char())) {
+                Ordering::Equal  => order.compare(&a.name, &b.name, SortCase::ABCabc),
+                type_order       => type_order,
             },
 
-            Self::Extension(ABCabc) => match a.ext.cmp(&b.ext) {
-                Ordering::Equal  => natord::compare(&*a.name, &*b.name),
-                order            => order,
+            Self::ChildCount => match a_keys.child_count.unwrap_or_else(|| a.child_count()).cmp(&b_keys.child_count.unwrap_or_else(|| b.child_count())) {
+                Ordering::Equal  => order.compare(&a.name, &b.name, SortCase::AaBbCc),
+                count_order      => count_order,
             },
 
-            Self::Extension(AaBbCc) => match a.ext.cmp(&b.ext) {
-                Ordering::Equal  => natord::compare_ignore_case(&*a.name, &*b.name),
-                order            => order,
-            },
+            Self::Extension(case) => Self::compare_extensions(
+                (a.ext.as_deref(), &a.name),
+                (b.ext.as_deref(), &b.name),
+                case, order
+            ),
 
-            Self::NameMixHidden(ABCabc) => natord::compare(
-                Self::strip_dot(&a.name),
-                Self::strip_dot(&b.name)
+            Self::CompoundExtension(case) => Self::compare_extensions(
+                (a.compound_ext.as_deref(), &a.name),
+                (b.compound_ext.as_deref(), &b.name),
+                case, order
             ),
-            Self::NameMixHidden(AaBbCc) => natord::compare_ignore_case(
+
+            Self::NameMixHidden(case) => order.compare(
                 Self::strip_dot(&a.name),
-                Self::strip_dot(&b.name)
-            )
+                Self::strip_dot(&b.name),
+                case
+            ),
+
+            Self::Numeric => Self::compare_numeric(&a.name, &b.name, order),
+        }
+    }
+
+    /// Compares two files by their extensions, falling back to their names
+    /// for files that share an extension.
+    fn compare_extensions(a: (Option<&str>, &str), b: (Option<&str>, &str), case: SortCase, order: SortOrder) -> Ordering {
+        match a.0.cmp(&b.0) {
+            Ordering::Equal  => order.compare(a.1, b.1, case),
+            ext_order        => ext_order,
+        }
+    }
+
+    /// Compares two file names by the value of the first number in each.
+    fn compare_numeric(a: &str, b: &str, order: SortOrder) -> Ordering {
+        match (first_number(a), first_number(b)) {
+            (Some(an), Some(bn)) => match compare_digits(an, bn) {
+                Ordering::Equal  => order.compare(a, b, SortCase::AaBbCc),
+                number_order     => number_order,
+            },
+            (Some(_), None)  => Ordering::Less,
+            (None, Some(_))  => Ordering::Greater,
+            (None, None)     => order.compare(a, b, SortCase::AaBbCc),
         }
     }
 
@@ -267,6 +581,23 @@ impl SortField {
     }
 }
 
+/// Returns the first run of ASCII digits in the given name, if there is one.
+fn first_number(name: &str) -> Option<&str> {
+    let start = name.find(|c: char| c.is_ascii_digit())?;
+    let rest = &name[start ..];
+    let end = rest.find(|c: char| ! c.is_ascii_digit()).unwrap_or(rest.len());
+    Some(&rest[.. end])
+}
+
+/// Compares two runs of digits by their value. This works on the strings
+/// themselves, rather than parsing them, so numbers that are too long to fit
+/// in an integer still get compared correctly.
+fn compare_digits(a: &str, b: &str) -> Ordering {
+    let a = a.trim_start_matches('0');
+    let b = b.trim_start_matches('0');
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
 
 /// The **ignore patterns** are a list of globs that are tested against
 /// each filename, and if any of them match, that file isn’t displayed.
@@ -274,6 +605,10 @@ impl SortField {
 #[derive(PartialEq, Eq, Default, Debug, Clone)]
 pub struct IgnorePatterns {
     patterns: Vec<glob::Pattern>,
+
+    /// Whether to test the patterns against each file’s path, rather than
+    /// just its name, so they can name the directories it’s in.
+    pub match_path: bool,
 }
 
 impl FromIterator<glob::Pattern> for IgnorePatterns {
@@ -282,7 +617,7 @@ impl FromIterator<glob::Pattern> for IgnorePatterns {
     where I: IntoIterator<Item = glob::Pattern>
     {
         let patterns = iter.into_iter().collect();
-        Self { patterns }
+        Self { patterns, match_path: false }
     }
 }
 
@@ -311,18 +646,112 @@ impl IgnorePatterns {
             }
         }
 
-        (Self { patterns }, errors)
+        (Self { patterns, match_path: false }, errors)
     }
 
     /// Create a new empty set of patterns that matches nothing.
     pub fn empty() -> Self {
-        Self { patterns: Vec::new() }
+        Self { patterns: Vec::new(), match_path: false }
+    }
+
+    /// Add the patterns from another set to the end of this one, keeping
+    /// this set’s choice of whether to match paths.
+    pub fn extend(&mut self, other: Self) {
+        self.patterns.extend(other.patterns);
+    }
+
+    /// Test whether the file with the given path and name should be hidden
+    /// from the results, going by its path or name as configured.
+    fn is_ignored_file(&self, path: &Path, name: &str, root: &Path) -> bool {
+        if self.match_path { self.is_ignored_path(path, root) }
+                      else { self.is_ignored(name) }
     }
 
     /// Test whether the given file should be hidden from the results.
     fn is_ignored(&self, file: &str) -> bool {
         self.patterns.iter().any(|p| p.matches(file))
     }
+
+    /// Test whether the file at the given path should be hidden from the
+    /// results. The path is taken relative to the given root, the directory
+    /// it was reached from on the command-line, without any leading `./`,
+    /// and wildcards don’t match across a `/`, so `src/*.rs` matches
+    /// `src/main.rs` but not `src/fs/file.rs`.
+    fn is_ignored_path(&self, path: &Path, root: &Path) -> bool {
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            .. glob::MatchOptions::new()
+        };
+
+        let path = path.strip_prefix(root).unwrap_or(path);
+        let path = path.components()
+                       .filter(|c| *c != Component::CurDir)
+                       .collect::<PathBuf>();
+
+        self.patterns.iter().any(|p| p.matches_path_with(&path, options))
+    }
+}
+
+
+/// The **modified window** picks out files by how long ago they were last
+/// modified, from the `--newer-than` and `--older-than` arguments. Either
+/// end can be left open, and by default both are, showing every file.
+#[derive(PartialEq, Eq, Default, Debug, Copy, Clone)]
+pub struct ModifiedWindow {
+
+    /// Files modified longer ago than this get hidden.
+    pub newer_than: Option<Duration>,
+
+    /// Files modified more recently than this get hidden.
+    pub older_than: Option<Duration>,
+}
+
+impl ModifiedWindow {
+
+    /// Whether either end of the window has been set, so there’s any point
+    /// checking files against it.
+    pub fn is_bounded(self) -> bool {
+        self.newer_than.is_some() || self.older_than.is_some()
+    }
+
+    /// Whether a file last modified at the given time falls inside the
+    /// window, measured back from `now`. Files modified in the future are
+    /// as new as can be, and files without a modified time are never in it.
+    pub fn contains(self, modified: Option<SystemTime>, now: SystemTime) -> bool {
+        let modified = match modified {
+            Some(m)  => m,
+            None     => return false,
+        };
+
+        let age = now.duration_since(modified).unwrap_or_default();
+        self.newer_than.map_or(true, |limit| age < limit)
+            && self.older_than.map_or(true, |limit| age > limit)
+    }
+}
+
+
+/// Where dotfiles go when the files get sorted.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum DotfileOrder {
+
+    /// Sort dotfiles along with every other file.
+    Mixed,
+
+    /// List dotfiles after every other file, whatever they’re sorted by.
+    Last,
+}
+
+
+/// Whether to check that access times can be trusted before sorting by
+/// them, and warn if they can’t.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum AtimeWarning {
+
+    /// Warn when the access times look like they never get updated.
+    CheckAndWarn,
+
+    /// Sort by access times without checking them.
+    Off,
 }
 
 
@@ -338,6 +767,614 @@ pub enum GitIgnore {
 }
 
 
+/// Whether to hide files matched by ignore files, which hold patterns like
+/// `.gitignore` files do, but aren’t tied to Git.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum IgnoreFiles {
+
+    /// Hide the files they match, reading them with the given cache.
+    Respect(IgnoreFileCache),
+
+    /// Display files, whatever the ignore files say.
+    Off,
+}
+
+/// The ignore files that have been read so far, by the directory they’re
+/// in, along with the ones above it. Every directory that gets listed shares
+/// this, so recursing into a directory only means reading the ignore files
+/// in that one directory, rather than in every directory above it again.
+#[derive(Debug, Default)]
+pub struct IgnoreFileCache {
+    layers: Mutex<HashMap<PathBuf, Arc<IgnoreLayer>>>,
+}
+
+// The cache is only there to save work, so any two of them are the same as
+// far as the options go, and a copy of one just starts off empty.
+
+impl PartialEq for IgnoreFileCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for IgnoreFileCache {}
+
+impl Clone for IgnoreFileCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl IgnoreFileCache {
+
+    /// Finds the directory with the given path on the filesystem, resolving
+    /// any symlinks, so a directory gets the same ignore files whichever
+    /// path it was reached by, and returns it along with its ignore files.
+    fn layers_for_path(&self, path: &Path) -> (PathBuf, Arc<IgnoreLayer>) {
+        let path = if path.as_os_str().is_empty() { Path::new(".") } else { path };
+        let dir = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let layer = self.layers_for(&dir);
+        (dir, layer)
+    }
+
+    /// The ignore files in the given directory and every one above it,
+    /// reading the ones that haven’t been read yet.
+    fn layers_for(&self, dir: &Path) -> Arc<IgnoreLayer> {
+        if let Some(layer) = self.layers.lock().unwrap().get(dir) {
+            return Arc::clone(layer);
+        }
+
+        let above = dir.parent().map(|parent| self.layers_for(parent));
+        let layer = Arc::new(IgnoreLayer { files: IgnoreFile::read_all(dir), above });
+        self.layers.lock().unwrap().insert(dir.to_path_buf(), Arc::clone(&layer));
+        layer
+    }
+}
+
+/// The ignore files in one directory, followed by those in the directories
+/// above it, which apply to everything underneath them too.
+#[derive(Debug)]
+struct IgnoreLayer {
+    files: Vec<IgnoreFile>,
+    above: Option<Arc<IgnoreLayer>>,
+}
+
+impl IgnoreLayer {
+
+    /// Whether the file with the given path and name is matched by an
+    /// ignore file in this directory or any directory above it.
+    fn is_ignored(&self, path: &Path, name: &str, is_dir: bool) -> bool {
+        self.files.iter().any(|f| f.is_ignored(path, name, is_dir))
+            || self.above.as_ref().map_or(false, |above| above.is_ignored(path, name, is_dir))
+    }
+}
+
+
+/// The names of the files that `--respect-ignore-files` reads patterns
+/// from. These are the ones `ripgrep` and `fd` use, which follow the same
+/// syntax as `.gitignore` files without being tied to Git.
+const IGNORE_FILE_NAMES: &[&str] = &[ ".ignore", ".fdignore" ];
+
+/// The patterns read from one **ignore file**, which apply to everything
+/// under the directory it was found in, however far down.
+#[derive(PartialEq, Eq, Debug, Clone)]
+struct IgnoreFile {
+
+    /// The directory the ignore file was in, which anchored patterns are
+    /// matched relative to.
+    dir: PathBuf,
+
+    /// The patterns, in the order they were given.
+    lines: Vec<IgnoreLine>,
+}
+
+/// One pattern from an ignore file.
+#[derive(PartialEq, Eq, Debug, Clone)]
+struct IgnoreLine {
+    pattern: glob::Pattern,
+
+    /// Whether the pattern contained a `/`, so it gets tested against the
+    /// file’s path from the ignore file’s directory, rather than its name.
+    anchored: bool,
+
+    /// Whether the pattern ended with a `/`, so it only matches directories.
+    dirs_only: bool,
+}
+
+impl IgnoreFile {
+
+    /// Reads the ignore files in the given directory. Files that can’t be
+    /// read are treated as empty, as they usually just aren’t there.
+    fn read_all(dir: &Path) -> Vec<Self> {
+        IGNORE_FILE_NAMES.iter()
+            .filter_map(|name| fs::read_to_string(dir.join(name)).ok())
+            .map(|contents| Self::parse(dir, &contents))
+            .collect()
+    }
+
+    /// Parses the contents of an ignore file found in the given directory.
+    /// Blank lines and comments are skipped, as are negated patterns
+    /// starting with `!`, which exa doesn’t support.
+    fn parse(dir: &Path, contents: &str) -> Self {
+        let mut lines = Vec::new();
+
+        for line in contents.lines() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+                continue;
+            }
+
+            let dirs_only = line.ends_with('/');
+            let line = line.trim_end_matches('/');
+            let anchored = line.contains('/');
+            let line = line.trim_start_matches('/');
+
+            match glob::Pattern::new(line) {
+                Ok(pattern)  => lines.push(IgnoreLine { pattern, anchored, dirs_only }),
+                Err(e)       => debug!("Skipping ignore pattern {:?}: {}", line, e),
+            }
+        }
+
+        Self { dir: dir.to_path_buf(), lines }
+    }
+
+    /// Whether the file with the given path and name is matched by any of
+    /// the patterns in this ignore file.
+    fn is_ignored(&self, path: &Path, name: &str, is_dir: bool) -> bool {
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            .. glob::MatchOptions::new()
+        };
+
+        let relative = path.strip_prefix(&self.dir).unwrap_or(path);
+
+        self.lines.iter().any(|line| {
+            if line.dirs_only && ! is_dir {
+                false
+            }
+            else if line.anchored {
+                line.pattern.matches_path_with(relative, options)
+            }
+            else {
+                line.pattern.matches(name)
+            }
+        })
+    }
+}
+
+
+
+#[cfg(test)]
+mod test_sorts {
+    use super::*;
+    use crate::fs::test::TempDir;
+
+    fn sorted(field: fn(&str, &str) -> Ordering, names: &[&'static str]) -> Vec<&'static str> {
+        let mut names = names.to_vec();
+        names.sort_by(|a, b| field(a, b));
+        names
+    }
+
+    #[test]
+    fn natord_leading_zeros() {
+        let names = sorted(natord::compare, &[ "2-setup", "10-end", "01-intro" ]);
+        assert_eq!(names, vec![ "01-intro", "2-setup", "10-end" ]);
+    }
+
+    #[test]
+    fn natural_order() {
+        let names = sorted(|a, b| SortOrder::Natural.compare(a, b, SortCase::AaBbCc), &[ "file10", "file2", "File3" ]);
+        assert_eq!(names, vec![ "file2", "File3", "file10" ]);
+    }
+
+    #[test]
+    fn byte_order() {
+        let names = sorted(|a, b| SortOrder::Byte.compare(a, b, SortCase::ABCabc), &[ "file10", "file2", "File3" ]);
+        assert_eq!(names, vec![ "File3", "file10", "file2" ]);
+    }
+
+    #[test]
+    fn dictionary_order() {
+        let names = sorted(|a, b| SortOrder::Natural.compare(a, b, SortCase::Dictionary), &[ "Banana", "apple", "banana", "Apple" ]);
+        assert_eq!(names, vec![ "apple", "Apple", "banana", "Banana" ]);
+    }
+
+    #[test]
+    fn byte_order_ignoring_case() {
+        let names = sorted(|a, b| SortOrder::Byte.compare(a, b, SortCase::AaBbCc), &[ "file10", "file2", "File3" ]);
+        assert_eq!(names, vec![ "file10", "file2", "File3" ]);
+    }
+
+    #[test]
+    fn numeric_leading_zeros() {
+        let names = sorted(|a, b| SortField::compare_numeric(a, b, SortOrder::Natural), &[ "2-setup", "10-end", "01-intro" ]);
+        assert_eq!(names, vec![ "01-intro", "2-setup", "10-end" ]);
+    }
+
+    #[test]
+    fn numeric_ignores_padding() {
+        let names = sorted(|a, b| SortField::compare_numeric(a, b, SortOrder::Natural), &[ "chapter02", "chapter1", "chapter01", "chapter3" ]);
+        assert_eq!(names, vec![ "chapter01", "chapter1", "chapter02", "chapter3" ]);
+    }
+
+    #[test]
+    fn numeric_only_first_run() {
+        let names = sorted(|a, b| SortField::compare_numeric(a, b, SortOrder::Natural), &[ "v2-part9", "v10-part1", "readme", "v2-part10" ]);
+        assert_eq!(names, vec![ "v2-part9", "v2-part10", "v10-part1", "readme" ]);
+    }
+
+    #[test]
+    fn plain_extensions() {
+        fn ext(name: &str) -> Option<&str> {
+            name.rsplit_once('.').map(|(_, e)| e)
+        }
+
+        let names = sorted(|a, b| SortField::compare_extensions((ext(a), a), (ext(b), b), SortCase::AaBbCc, SortOrder::Natural),
+                           &[ "a.tar.gz", "b.gz", "c.tar.bz2" ]);
+        assert_eq!(names, vec![ "c.tar.bz2", "a.tar.gz", "b.gz" ]);
+    }
+
+    #[test]
+    fn compound_extensions() {
+        let names = sorted(|a, b| SortField::compare_extensions((File::compound_ext(a).as_deref(), a), (File::compound_ext(b).as_deref(), b), SortCase::AaBbCc, SortOrder::Natural),
+                           &[ "a.tar.gz", "b.gz", "c.tar.bz2" ]);
+        assert_eq!(names, vec![ "b.gz", "c.tar.bz2", "a.tar.gz" ]);
+    }
+
+    #[test]
+    fn secondary_sort() {
+        let path = TempDir::new("sort2");
+        std::fs::write(path.join("charlie"), b"12").unwrap();
+        std::fs::write(path.join("alpha"), b"12").unwrap();
+        std::fs::write(path.join("delta"), b"1").unwrap();
+        std::fs::write(path.join("bravo"), b"12").unwrap();
+
+        let mut files = [ "charlie", "alpha", "delta", "bravo" ].iter()
+                            .map(|name| File::from_args(path.join(name), None, None).unwrap())
+                            .collect::<Vec<_>>();
+
+        let filter = FileFilter {
+            list_dirs_first: false,
+            dotfile_order: DotfileOrder::Mixed,
+            sort_field: SortField::Size,
+            secondary_sort: Some(SortField::Name(SortCase::AaBbCc)),
+            sort_order: SortOrder::Natural,
+            reverse: false,
+            type_order: TypeOrder::Reversible,
+            atime_warning: AtimeWarning::Off,
+            modified_window: ModifiedWindow::default(),
+            only_dirs: false,
+            dot_filter: DotFilter::JustFiles,
+            ignore_patterns: IgnorePatterns::empty(),
+            git_ignore: GitIgnore::Off,
+            ignore_files: IgnoreFiles::Off,
+            max_entries: None,
+        };
+
+        filter.sort_files(&mut files);
+        let names = files.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec![ "delta", "alpha", "bravo", "charlie" ]);
+    }
+
+    #[test]
+    fn dotfiles_last() {
+        use crate::fs::Dir;
+
+        let path = TempDir::new("dotfiles-last");
+        for name in &[ ".env", "main.rs", ".gitignore", "lib.rs" ] {
+            std::fs::write(path.join(name), b"").unwrap();
+        }
+
+        let filter = FileFilter {
+            list_dirs_first: false,
+            dotfile_order: DotfileOrder::Last,
+            sort_field: SortField::Name(SortCase::AaBbCc),
+            secondary_sort: None,
+            sort_order: SortOrder::Natural,
+            reverse: false,
+            type_order: TypeOrder::Reversible,
+            atime_warning: AtimeWarning::Off,
+            modified_window: ModifiedWindow::default(),
+            only_dirs: false,
+            dot_filter: DotFilter::Dotfiles,
+            ignore_patterns: IgnorePatterns::empty(),
+            git_ignore: GitIgnore::Off,
+            ignore_files: IgnoreFiles::Off,
+            max_entries: None,
+        };
+
+        let dir = Dir::read_dir(path.clone()).unwrap();
+        let mut files = dir.files(filter.dot_filter, None, false)
+                           .map(Result::unwrap)
+                           .collect::<Vec<_>>();
+
+        filter.sort_files(&mut files);
+        let names = files.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec![ "lib.rs", "main.rs", ".env", ".gitignore" ]);
+    }
+
+    #[cfg(unix)]
+    fn sorted_by_type(path: &std::path::Path, reverse: bool, type_order: TypeOrder) -> Vec<String> {
+        let mut files = [ "b-dir", "b-file", "a-link", "a-dir", "b-link", "a-file" ].iter()
+                            .map(|name| File::from_args(path.join(name), None, None).unwrap())
+                            .collect::<Vec<_>>();
+
+        let filter = FileFilter {
+            list_dirs_first: false,
+            dotfile_order: DotfileOrder::Mixed,
+            sort_field: SortField::FileType,
+            secondary_sort: None,
+            sort_order: SortOrder::Natural,
+            reverse,
+            type_order,
+            atime_warning: AtimeWarning::Off,
+            modified_window: ModifiedWindow::default(),
+            only_dirs: false,
+            dot_filter: DotFilter::JustFiles,
+            ignore_patterns: IgnorePatterns::empty(),
+            git_ignore: GitIgnore::Off,
+            ignore_files: IgnoreFiles::Off,
+            max_entries: None,
+        };
+
+        filter.sort_files(&mut files);
+        files.iter().map(|f| f.name.clone()).collect()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn type_sorts_reversed() {
+        let path = TempDir::new("type-sort");
+        std::fs::create_dir_all(path.join("a-dir")).unwrap();
+        std::fs::create_dir_all(path.join("b-dir")).unwrap();
+        std::fs::write(path.join("a-file"), b"").unwrap();
+        std::fs::write(path.join("b-file"), b"").unwrap();
+        std::os::unix::fs::symlink("a-file", path.join("a-link")).unwrap();
+        std::os::unix::fs::symlink("b-file", path.join("b-link")).unwrap();
+
+        assert_eq!(sorted_by_type(&path, false, TypeOrder::Reversible), vec![ "a-dir", "b-dir", "a-file", "b-file", "a-link", "b-link" ]);
+        assert_eq!(sorted_by_type(&path, true,  TypeOrder::Reversible), vec![ "b-link", "a-link", "b-file", "a-file", "b-dir", "a-dir" ]);
+        assert_eq!(sorted_by_type(&path, true,  TypeOrder::Stable),     vec![ "b-dir", "a-dir", "b-file", "a-file", "b-link", "a-link" ]);
+        assert_eq!(sorted_by_type(&path, false, TypeOrder::Stable),     vec![ "a-dir", "b-dir", "a-file", "b-file", "a-link", "b-link" ]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn inode_breaks_ties() {
+        let path = TempDir::new("inode-tie");
+        std::fs::write(path.join("first"), b"same").unwrap();
+        std::fs::write(path.join("second"), b"size").unwrap();
+
+        let filter = FileFilter {
+            list_dirs_first: false,
+            dotfile_order: DotfileOrder::Mixed,
+            sort_field: SortField::Size,
+            secondary_sort: Some(SortField::FileInode),
+            sort_order: SortOrder::Natural,
+            reverse: false,
+            type_order: TypeOrder::Reversible,
+            atime_warning: AtimeWarning::Off,
+            modified_window: ModifiedWindow::default(),
+            only_dirs: false,
+            dot_filter: DotFilter::JustFiles,
+            ignore_patterns: IgnorePatterns::empty(),
+            git_ignore: GitIgnore::Off,
+            ignore_files: IgnoreFiles::Off,
+            max_entries: None,
+        };
+
+        // Whichever order the files start in, they should end up in the
+        // order of their inodes, as their sizes are the same.
+        for names in &[ [ "first", "second" ], [ "second", "first" ] ] {
+            let mut files = names.iter()
+                                 .map(|name| File::from_args(path.join(name), None, None).unwrap())
+                                 .collect::<Vec<_>>();
+
+            filter.sort_files(&mut files);
+            assert!(files[0].metadata.ino() < files[1].metadata.ino());
+        }
+    }
+
+    #[test]
+    fn child_count_sort() {
+        let path = TempDir::new("child-count");
+        for name in &[ "one", "two", "three" ] {
+            std::fs::create_dir_all(path.join("big").join(name)).unwrap();
+        }
+        std::fs::create_dir_all(path.join("small").join("only")).unwrap();
+        std::fs::write(path.join("file"), b"").unwrap();
+
+        let mut files = [ "big", "file", "small" ].iter()
+                            .map(|name| File::from_args(path.join(name), None, None).unwrap())
+                            .collect::<Vec<_>>();
+
+        let filter = FileFilter {
+            list_dirs_first: false,
+            dotfile_order: DotfileOrder::Mixed,
+            sort_field: SortField::ChildCount,
+            secondary_sort: None,
+            sort_order: SortOrder::Natural,
+            reverse: false,
+            type_order: TypeOrder::Reversible,
+            atime_warning: AtimeWarning::Off,
+            modified_window: ModifiedWindow::default(),
+            only_dirs: false,
+            dot_filter: DotFilter::JustFiles,
+            ignore_patterns: IgnorePatterns::empty(),
+            git_ignore: GitIgnore::Off,
+            ignore_files: IgnoreFiles::Off,
+            max_entries: None,
+        };
+
+        filter.sort_files(&mut files);
+        let names = files.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec![ "file", "small", "big" ]);
+    }
+
+    #[test]
+    fn limit_after_reversing() {
+        let path = TempDir::new("max-entries");
+        for name in &[ "alpha", "bravo", "charlie", "delta", "echo" ] {
+            std::fs::write(path.join(name), b"").unwrap();
+        }
+
+        let mut files = [ "charlie", "echo", "alpha", "delta", "bravo" ].iter()
+                            .map(|name| File::from_args(path.join(name), None, None).unwrap())
+                            .collect::<Vec<_>>();
+
+        let filter = FileFilter {
+            list_dirs_first: false,
+            dotfile_order: DotfileOrder::Mixed,
+            sort_field: SortField::Name(SortCase::AaBbCc),
+            secondary_sort: None,
+            sort_order: SortOrder::Natural,
+            reverse: true,
+            type_order: TypeOrder::Reversible,
+            atime_warning: AtimeWarning::Off,
+            modified_window: ModifiedWindow::default(),
+            only_dirs: false,
+            dot_filter: DotFilter::JustFiles,
+            ignore_patterns: IgnorePatterns::empty(),
+            git_ignore: GitIgnore::Off,
+            ignore_files: IgnoreFiles::Off,
+            max_entries: Some(2),
+        };
+
+        filter.sort_and_limit(&mut files);
+        let names = files.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec![ "echo", "delta" ]);
+    }
+
+    /// A tiny xorshift generator, so the stability test below can make up
+    /// file lists without pulling in a random number crate. It’s seeded
+    /// with a constant, so any failure can be reproduced.
+    struct Xorshift(u64);
+
+    impl Xorshift {
+        fn below(&mut self, n: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % n
+        }
+    }
+
+    #[test]
+    fn sorts_are_stable() {
+        let path = TempDir::new("stability");
+        let mut rng = Xorshift(0x2545_F491_4F6C_DD1D);
+
+        for round in 0 .. 20 {
+            let dir = path.join(round.to_string());
+            std::fs::create_dir_all(&dir).unwrap();
+
+            // Only a handful of sizes and extensions, so that plenty of
+            // files tie with each other.
+            let mut names = Vec::new();
+            for i in 0 .. 12 {
+                let name = match rng.below(4) {
+                    0 => format!("f{}", i),
+                    1 => format!("f{}.rs", i),
+                    2 => format!("f{}.md", i),
+                    _ => format!("d{}", i),
+                };
+
+                if name.starts_with('d') {
+                    std::fs::create_dir(dir.join(&name)).unwrap();
+                }
+                else {
+                    std::fs::write(dir.join(&name), vec![ b'x'; rng.below(3) as usize ]).unwrap();
+                }
+
+                names.push(name);
+            }
+
+            // Shuffle the names, standing in for whatever order readdir
+            // might return them in.
+            for i in (1 .. names.len()).rev() {
+                names.swap(i, rng.below(i as u64 + 1) as usize);
+            }
+
+            for &field in &[ SortField::Unsorted, SortField::Size, SortField::FileType, SortField::ModifiedDate ] {
+                for &list_dirs_first in &[ false, true ] {
+                    let filter = FileFilter {
+                        list_dirs_first,
+                        dotfile_order: DotfileOrder::Mixed,
+                        sort_field: field,
+                        secondary_sort: None,
+                        sort_order: SortOrder::Natural,
+                        reverse: false,
+                        type_order: TypeOrder::Reversible,
+                        atime_warning: AtimeWarning::Off,
+                        modified_window: ModifiedWindow::default(),
+                        only_dirs: false,
+                        dot_filter: DotFilter::JustFiles,
+                        ignore_patterns: IgnorePatterns::empty(),
+                        git_ignore: GitIgnore::Off,
+                        ignore_files: IgnoreFiles::Off,
+                        max_entries: None,
+                    };
+
+                    let mut files = names.iter()
+                                         .map(|name| File::from_args(dir.join(name), None, None).unwrap())
+                                         .collect::<Vec<_>>();
+                    filter.sort_files(&mut files);
+
+                    let position = |f: &File<'_>| names.iter().position(|n| n == &f.name).unwrap();
+                    for (i, a) in files.iter().enumerate() {
+                        for b in &files[i + 1 ..] {
+                            let tied = field.compare_files(a, b, SortOrder::Natural) == Ordering::Equal
+                                    && (! list_dirs_first || a.points_to_directory() == b.points_to_directory());
+
+                            if tied {
+                                assert!(position(a) < position(b),
+                                        "{:?} and {:?} were swapped sorting by {:?}", a.name, b.name, field);
+                            }
+                            else if ! list_dirs_first || a.points_to_directory() == b.points_to_directory() {
+                                assert_eq!(field.compare_files(a, b, SortOrder::Natural), Ordering::Less,
+                                           "{:?} came before {:?} sorting by {:?}", a.name, b.name, field);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cached_keys_worked_out_once_each() {
+        use std::cell::Cell;
+
+        let calls = Cell::new(0);
+        let mut names = vec![ "delta", "alpha", "echo", "bravo", "charlie", "alpha" ];
+        let mut expected = names.clone();
+        expected.sort_by_key(|n| n.len());
+
+        sort_with_cached_keys(&mut names, |n| { calls.set(calls.get() + 1); n.len() }, |(_, a), (_, b)| a.cmp(b));
+
+        assert_eq!(names, expected);
+        assert_eq!(calls.get(), 6);
+    }
+
+    #[test]
+    fn unchanged_access_times() {
+        use std::time::Duration;
+
+        let then = SystemTime::UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+        let later = then + Duration::from_secs(60);
+
+        assert!(access_times_match(vec![ (Some(then), Some(then)), (Some(later), Some(later)) ]));
+        assert!(! access_times_match(vec![ (Some(then), Some(then)), (Some(later), Some(then)) ]));
+        assert!(! access_times_match(vec![ (None, Some(then)) ]));
+        assert!(! access_times_match(Vec::new()));
+    }
+
+    #[test]
+    fn numeric_huge_numbers() {
+        assert_eq!(compare_digits("99999999999999999999999999999999999999999", "100000000000000000000000000000000000000000"), Ordering::Less);
+    }
+}
+
 
 #[cfg(test)]
 mod test_ignores {
@@ -373,4 +1410,166 @@ mod test_ignores {
         assert!(pats.is_ignored("nothing"));
         assert!(pats.is_ignored("test.mp3"));
     }
+
+    #[test]
+    fn names_by_default() {
+        let (pats, fails) = IgnorePatterns::parse_from_iter(vec![ "src/*.rs" ]);
+        assert!(fails.is_empty());
+        assert!(!pats.is_ignored_file(Path::new("src/foo.rs"), "foo.rs", Path::new("")));
+    }
+
+    #[test]
+    fn paths_when_asked() {
+        let (mut pats, fails) = IgnorePatterns::parse_from_iter(vec![ "src/*.rs" ]);
+        assert!(fails.is_empty());
+        pats.match_path = true;
+
+        assert!(pats.is_ignored_file(Path::new("src/foo.rs"), "foo.rs", Path::new("")));
+        assert!(pats.is_ignored_file(Path::new("./src/foo.rs"), "foo.rs", Path::new("")));
+        assert!(!pats.is_ignored_file(Path::new("foo.rs"), "foo.rs", Path::new("")));
+        assert!(!pats.is_ignored_file(Path::new("src/fs/foo.rs"), "foo.rs", Path::new("")));
+        assert!(!pats.is_ignored_file(Path::new("lib/foo.rs"), "foo.rs", Path::new("")));
+    }
+
+    #[test]
+    fn paths_from_the_root() {
+        let (mut pats, fails) = IgnorePatterns::parse_from_iter(vec![ "src/*.rs" ]);
+        assert!(fails.is_empty());
+        pats.match_path = true;
+
+        assert!(pats.is_ignored_file(Path::new("ip/src/foo.rs"), "foo.rs", Path::new("ip")));
+        assert!(pats.is_ignored_file(Path::new("/tmp/ip/src/foo.rs"), "foo.rs", Path::new("/tmp/ip")));
+        assert!(!pats.is_ignored_file(Path::new("ip/src/foo.rs"), "foo.rs", Path::new("")));
+        assert!(!pats.is_ignored_file(Path::new("ip/src/fs/foo.rs"), "foo.rs", Path::new("ip")));
+    }
+}
+
+
+#[cfg(test)]
+mod test_ignore_files {
+    use super::*;
+    use crate::fs::Dir;
+    use crate::fs::test::TempDir;
+
+    fn filter() -> FileFilter {
+        FileFilter {
+            list_dirs_first: false,
+            dotfile_order: DotfileOrder::Mixed,
+            sort_field: SortField::Name(SortCase::AaBbCc),
+            secondary_sort: None,
+            sort_order: SortOrder::Natural,
+            reverse: false,
+            type_order: TypeOrder::Reversible,
+            atime_warning: AtimeWarning::Off,
+            modified_window: ModifiedWindow::default(),
+            only_dirs: false,
+            dot_filter: DotFilter::JustFiles,
+            ignore_patterns: IgnorePatterns::empty(),
+            git_ignore: GitIgnore::Off,
+            ignore_files: IgnoreFiles::Respect(IgnoreFileCache::default()),
+            max_entries: None,
+        }
+    }
+
+    fn children(filter: &FileFilter, path: &Path) -> Vec<String> {
+        let dir = Dir::read_dir(path.to_path_buf()).unwrap();
+        let mut files = dir.files(filter.dot_filter, None, false)
+                           .map(Result::unwrap)
+                           .collect::<Vec<_>>();
+
+        filter.filter_child_files(&mut files, path);
+        filter.sort_files(&mut files);
+        files.iter().map(|f| f.name.clone()).collect()
+    }
+
+    #[test]
+    fn hidden_in_subtree() {
+        let path = TempDir::new("ignore-file");
+        std::fs::create_dir_all(path.join("sub").join("deeper")).unwrap();
+        std::fs::write(path.join(".ignore"), b"# logs\n*.log\n").unwrap();
+        std::fs::write(path.join("app.log"), b"").unwrap();
+        std::fs::write(path.join("keep.txt"), b"").unwrap();
+        std::fs::write(path.join("sub").join("sub.log"), b"").unwrap();
+        std::fs::write(path.join("sub").join("notes.txt"), b"").unwrap();
+        std::fs::write(path.join("sub").join("deeper").join("deep.log"), b"").unwrap();
+
+        assert_eq!(children(&filter(), &path), vec![ "keep.txt", "sub" ]);
+        assert_eq!(children(&filter(), &path.join("sub")), vec![ "deeper", "notes.txt" ]);
+        assert!(children(&filter(), &path.join("sub").join("deeper")).is_empty());
+    }
+
+    #[test]
+    fn read_once_for_the_whole_recursion() {
+        let path = TempDir::new("ignore-cache");
+        std::fs::create_dir_all(path.join("sub")).unwrap();
+        std::fs::write(path.join(".ignore"), b"*.log\n").unwrap();
+        std::fs::write(path.join("sub").join("sub.log"), b"").unwrap();
+
+        let filter = filter();
+        assert_eq!(children(&filter, &path), vec![ "sub" ]);
+
+        // The directory above gets found the same way through any path,
+        // and doesn’t get read again, so changing its file does nothing.
+        std::fs::write(path.join(".ignore"), b"").unwrap();
+        assert!(children(&filter, &path.join("sub").join("..").join("sub")).is_empty());
+
+        let cache = match &filter.ignore_files {
+            IgnoreFiles::Respect(cache)  => cache,
+            IgnoreFiles::Off             => unreachable!(),
+        };
+
+        let top = cache.layers_for(&fs::canonicalize(&path).unwrap());
+        let sub = cache.layers_for(&fs::canonicalize(path.join("sub")).unwrap());
+        assert!(Arc::ptr_eq(&top, sub.above.as_ref().unwrap()));
+    }
+
+    #[test]
+    fn anchored_and_directory_patterns() {
+        let file = IgnoreFile::parse(Path::new("top"), "/build/\n!keep.log\nsrc/*.tmp\n");
+
+        assert!(file.is_ignored(Path::new("top/build"), "build", true));
+        assert!(! file.is_ignored(Path::new("top/build"), "build", false));
+        assert!(file.is_ignored(Path::new("top/src/a.tmp"), "a.tmp", false));
+        assert!(! file.is_ignored(Path::new("top/src/deep/a.tmp"), "a.tmp", false));
+        assert!(! file.is_ignored(Path::new("top/keep.log"), "keep.log", false));
+    }
+}
+
+
+#[cfg(test)]
+mod test_windows {
+    use super::*;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    #[test]
+    fn unbounded_shows_everything() {
+        let now = SystemTime::UNIX_EPOCH + 1000 * DAY;
+        let window = ModifiedWindow::default();
+
+        assert!(! window.is_bounded());
+        assert!(window.contains(Some(now - 100 * DAY), now));
+    }
+
+    #[test]
+    fn newer_than_a_week() {
+        let now = SystemTime::UNIX_EPOCH + 1000 * DAY;
+        let window = ModifiedWindow { newer_than: Some(7 * DAY), older_than: None };
+
+        assert!(window.is_bounded());
+        assert!(window.contains(Some(now - 2 * DAY), now));
+        assert!(! window.contains(Some(now - 8 * DAY), now));
+        assert!(window.contains(Some(now + DAY), now));
+        assert!(! window.contains(None, now));
+    }
+
+    #[test]
+    fn older_than_a_week() {
+        let now = SystemTime::UNIX_EPOCH + 1000 * DAY;
+        let window = ModifiedWindow { newer_than: None, older_than: Some(7 * DAY) };
+
+        assert!(! window.contains(Some(now - 2 * DAY), now));
+        assert!(window.contains(Some(now - 8 * DAY), now));
+        assert!(! window.contains(Some(now + DAY), now));
+    }
 }
@@ -4,7 +4,53 @@ pub use self::dir::{Dir, DotFilter};
 mod file;
 pub use self::file::{File, FileTarget};
 
+pub mod checksum;
 pub mod dir_action;
 pub mod feature;
 pub mod fields;
 pub mod filter;
+
+
+#[cfg(test)]
+pub mod test {
+    use std::ops::Deref;
+    use std::path::{Path, PathBuf};
+
+    /// A directory for a test to make files in, under the system’s
+    /// temporary directory. It gets deleted, along with everything in it,
+    /// when it goes out of scope, even if the test fails.
+    pub struct TempDir(PathBuf);
+
+    impl TempDir {
+
+        /// Creates the directory, with the process ID in its name so that
+        /// two test runs can’t trip over each other.
+        pub fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("exa-{}-test-{}", name, std::process::id()));
+            std::fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Deref for TempDir {
+        type Target = PathBuf;
+
+        fn deref(&self) -> &PathBuf {
+            &self.0
+        }
+    }
+
+    impl AsRef<Path> for TempDir {
+        fn as_ref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            // There’s nothing to be done if it can’t be deleted, as the
+            // test has already passed or failed by now.
+            drop(std::fs::remove_dir_all(&self.0));
+        }
+    }
+}
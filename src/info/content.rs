@@ -0,0 +1,138 @@
+//! Sniffing the first few bytes of a file to work out what kind of data it
+//! holds, for when file names alone aren’t enough to go on.
+//!
+//! This is only ever done when the user asks for it with `--color-by=content`,
+//! as it means opening and reading every regular file in the listing.
+
+use std::fs;
+use std::io::Read;
+
+use ansi_term::Style;
+
+use crate::fs::File;
+use crate::theme::FileColours;
+
+
+/// How many bytes from the start of a file get looked at. Magic numbers are
+/// all well within this, and it’s enough to spot a binary file by its NULs.
+const SNIFF_LENGTH: u64 = 512;
+
+
+/// The broad kinds of content exa can tell apart.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum ContentKind {
+
+    /// An image file, recognised by its magic number.
+    Image,
+
+    /// Any other file with a NUL byte near its start.
+    Binary,
+
+    /// Everything else.
+    Text,
+}
+
+impl ContentKind {
+
+    /// Classifies the given bytes, which should be the start of a file.
+    pub fn of_bytes(bytes: &[u8]) -> Self {
+        const IMAGE_MAGICS: &[&[u8]] = &[
+            b"\x89PNG\r\n\x1a\n",  // PNG
+            b"\xff\xd8\xff",       // JPEG
+            b"GIF87a",             // GIF
+            b"GIF89a",
+        ];
+
+        if IMAGE_MAGICS.iter().any(|magic| bytes.starts_with(magic)) {
+            Self::Image
+        }
+        else if bytes.contains(&0) {
+            Self::Binary
+        }
+        else {
+            Self::Text
+        }
+    }
+
+    /// Reads the start of the given file and classifies it, returning `None`
+    /// if it isn’t a regular file or can’t be read.
+    pub fn of_file(file: &File<'_>) -> Option<Self> {
+        if ! file.is_file() {
+            return None;
+        }
+
+        let mut bytes = Vec::with_capacity(SNIFF_LENGTH as usize);
+        fs::File::open(&file.path).ok()?
+            .take(SNIFF_LENGTH)
+            .read_to_end(&mut bytes).ok()?;
+
+        Some(Self::of_bytes(&bytes))
+    }
+}
+
+
+/// Colours files by what their contents look like, instead of by their
+/// names. Files that can’t be read are left for the next set of colours in
+/// the chain.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ContentColours;
+
+impl FileColours for ContentColours {
+    fn colour_file(&self, file: &File<'_>) -> Option<Style> {
+        use ansi_term::Colour::*;
+
+        Some(match ContentKind::of_file(file)? {
+            ContentKind::Image   => Fixed(133).normal(),
+            ContentKind::Binary  => Fixed(137).normal(),
+            ContentKind::Text    => Style::default(),
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fs::test::TempDir;
+
+    #[test]
+    fn png() {
+        assert_eq!(ContentKind::of_bytes(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"), ContentKind::Image);
+    }
+
+    #[test]
+    fn gif() {
+        assert_eq!(ContentKind::of_bytes(b"GIF89a\x01\0\x01\0"), ContentKind::Image);
+    }
+
+    #[test]
+    fn binary() {
+        assert_eq!(ContentKind::of_bytes(b"\x7fELF\x02\x01\x01\0"), ContentKind::Binary);
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(ContentKind::of_bytes(b"#!/bin/sh\necho hello\n"), ContentKind::Text);
+    }
+
+    #[test]
+    fn nothing() {
+        assert_eq!(ContentKind::of_bytes(b""), ContentKind::Text);
+    }
+
+    #[test]
+    fn files_get_different_colours() {
+        let path = TempDir::new("content");
+        std::fs::write(path.join("picture.txt"), b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").unwrap();
+        std::fs::write(path.join("notes.png"), b"just some words\n").unwrap();
+
+        let picture = File::from_args(path.join("picture.txt"), None, None).unwrap();
+        let notes = File::from_args(path.join("notes.png"), None, None).unwrap();
+
+        let picture_style = ContentColours.colour_file(&picture);
+        let notes_style = ContentColours.colour_file(&notes);
+        assert_eq!(picture_style, Some(ansi_term::Colour::Fixed(133).normal()));
+        assert_eq!(notes_style, Some(Style::default()));
+        assert_ne!(picture_style, notes_style);
+    }
+}
@@ -3,5 +3,6 @@
 //! logic” routines that are performed on a file’s already-read metadata.
 //! (This counts the file name as metadata.)
 
+pub mod content;
 pub mod filetype;
 mod sources;
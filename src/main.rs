@@ -25,17 +25,21 @@
 use std::env;
 use std::ffi::{OsStr, OsString};
 use std::io::{self, Write, ErrorKind};
-use std::path::{Component, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
 use ansi_term::{ANSIStrings, Style};
 
 use log::*;
 
 use crate::fs::{Dir, File};
+use crate::fs::dir_action::EntryBudget;
+use crate::fs::feature::filesystem::{SpaceSource, Statvfs};
 use crate::fs::feature::git::GitCache;
 use crate::fs::filter::GitIgnore;
 use crate::options::{Options, Vars, vars, OptionsResult};
-use crate::output::{escape, lines, grid, grid_details, details, View, Mode};
+use crate::output::{escape, lines, grid, grid_details, details, groups, pager, stat, watch, View, Mode, RunMode};
+use crate::output::groups::GroupBy;
+use crate::output::summary::{self, Summary, Totals};
 use crate::theme::Theme;
 
 mod fs;
@@ -71,14 +75,29 @@ fn main() {
                 input_paths = vec![ OsStr::new(".") ];
             }
 
-            let git = git_options(&options, &input_paths);
-            let writer = io::stdout();
+            // Watching only makes sense when there’s a screen to clear, and
+            // the listings have to go straight to it, rather than a pager.
+            let watching = options.view.run == RunMode::Watch && options.view.output.is_none() && pager::terminal_height().is_some();
 
-            let console_width = options.view.width.actual_terminal_width();
-            let theme = options.theme.to_theme(console_width.is_some());
-            let exa = Exa { options, writer, input_paths, theme, console_width, git };
+            let (writer, console_width) = match output_for(&options, watching) {
+                Ok(output) => output,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    exit(exits::RUNTIME_ERROR);
+                }
+            };
+
+            let exa = Exa::new(options, input_paths, writer, console_width);
+
+            let result = if watching {
+                let trigger = watch::Poll::new(exa.input_paths.iter().map(PathBuf::from).collect());
+                exa.watch(trigger)
+            }
+            else {
+                exa.run()
+            };
 
-            match exa.run() {
+            match result {
                 Ok(exit_status) => {
                     exit(exit_status);
                 }
@@ -122,8 +141,9 @@ pub struct Exa<'args> {
     /// List of command-line options, having been successfully parsed.
     pub options: Options,
 
-    /// The output handle that we write to.
-    pub writer: io::Stdout,
+    /// The output handle that we write to. This is either stdout, or a
+    /// buffer that gets sent through a pager once everything is written.
+    pub writer: pager::Writer,
 
     /// List of the free command-line arguments that should correspond to file
     /// names (anything that isn’t an option).
@@ -142,6 +162,10 @@ pub struct Exa<'args> {
     /// This has to last the lifetime of the program, because the user might
     /// want to list several directories in the same repository.
     pub git: Option<GitCache>,
+
+    /// How many more entries can be listed when recursing, if the user
+    /// set a limit. This is shared by every directory that gets listed.
+    pub budget: EntryBudget,
 }
 
 /// The “real” environment variables type.
@@ -154,11 +178,33 @@ impl Vars for LiveVars {
     }
 }
 
+/// Works out where the output should go — to the file given with
+/// `--output`, straight to the screen when watching, or through a pager
+/// otherwise — and how wide the console is. Output written to a file never
+/// goes to a terminal, so it gets treated the same as output that’s been
+/// piped somewhere.
+fn output_for(options: &Options, watching: bool) -> io::Result<(pager::Writer, Option<usize>)> {
+    match options.view.output {
+        Some(ref path) => {
+            let writer = pager::Writer::to_file(path)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+            Ok((writer, options.view.width.file_width()))
+        }
+        None => {
+            let height = pager::terminal_height();
+            let writer = if watching { pager::Writer::Direct(io::stdout()) }
+                                else { pager::Writer::new(options.view.paging, height.is_some(), height, env::var_os(vars::PAGER)) };
+            Ok((writer, options.view.width.actual_terminal_width()))
+        }
+    }
+}
+
 /// Create a Git cache populated with the arguments that are going to be
 /// listed before they’re actually listed, if the options demand it.
 fn git_options(options: &Options, args: &[&OsStr]) -> Option<GitCache> {
     if options.should_scan_for_git() {
-        Some(args.iter().map(PathBuf::from).collect())
+        let git = args.iter().map(PathBuf::from).collect::<GitCache>();
+        Some(git.with_max_files(options.git_max_files))
     }
     else {
         None
@@ -166,12 +212,60 @@ fn git_options(options: &Options, args: &[&OsStr]) -> Option<GitCache> {
 }
 
 impl<'args> Exa<'args> {
+
+    /// Puts exa together to list the given paths to the writer. Colours
+    /// only get turned on automatically when there’s a console for them to
+    /// go to and the output isn’t going to a file instead.
+    #[must_use]
+    pub fn new(options: Options, input_paths: Vec<&'args OsStr>, writer: pager::Writer, console_width: Option<usize>) -> Self {
+        let theme = options.theme.to_theme(console_width.is_some() && options.view.output.is_none());
+        let git = git_options(&options, &input_paths);
+        let budget = EntryBudget::new(options.dir_action.recurse_options().and_then(|r| r.max_total));
+        Self { options, writer, input_paths, theme, console_width, git, budget }
+    }
+
     /// # Errors
     ///
     /// Will return `Err` if printing to stderr fails.
     pub fn run(mut self) -> io::Result<i32> {
+        let exit_status = self.list()?;
+        self.writer.finish()?;
+        Ok(exit_status)
+    }
+
+    /// Lists every input path, then lists them all again each time the
+    /// trigger goes off, clearing the screen before each listing.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if printing to stderr fails.
+    pub fn watch<T: watch::Trigger>(mut self, mut trigger: T) -> io::Result<i32> {
+        loop {
+            write!(&mut self.writer, "{}", watch::CLEAR_SCREEN)?;
+            let exit_status = self.list()?;
+            self.writer.flush()?;
+
+            if ! trigger.wait() {
+                self.writer.finish()?;
+                return Ok(exit_status);
+            }
+
+            // The entry budget has been spent by the last listing, and the
+            // Git statuses may be out of date, so start both afresh.
+            self.budget = EntryBudget::new(self.options.dir_action.recurse_options().and_then(|r| r.max_total));
+            self.git = git_options(&self.options, &self.input_paths);
+        }
+    }
+
+    /// Lists every input path to the writer, without flushing it.
+    fn list(&mut self) -> io::Result<i32> {
         debug!("Running with options: {:#?}", self.options);
 
+        if self.options.view.run == RunMode::DebugColours {
+            self.theme.ui.render_dump(&mut self.writer)?;
+            return Ok(exits::SUCCESS);
+        }
+
         let mut files = Vec::new();
         let mut dirs = Vec::new();
         let mut exit_status = 0;
@@ -205,13 +299,45 @@ impl<'args> Exa<'args> {
         let is_only_dir = dirs.len() == 1 && no_files;
 
         self.options.filter.filter_argument_files(&mut files);
-        self.print_files(None, files)?;
 
-        self.print_dirs(dirs, no_files, is_only_dir, exit_status)
+        if self.options.view.mode == Mode::OnelinerSummary {
+            self.print_totals(&files, &dirs)?;
+            return Ok(exit_status);
+        }
+
+        if self.options.filter.should_warn_about_atimes(&files) {
+            writeln!(io::stderr(), "exa: access times match modified times, so they may not be recorded")?;
+        }
+
+        // The roots of a tree are exempt from the limit, but any other files
+        // named on the command-line count towards it like everything else.
+        if ! self.is_tree() {
+            self.budget.spend(&mut files);
+        }
+
+        self.print_files(None, files, Vec::new())?;
+
+        let exit_status = self.print_dirs(dirs, no_files, is_only_dir, None, exit_status)?;
+
+        // A tree spends the budget while it’s being built, so whichever way
+        // the listing got cut short, the note goes at the very end.
+        if self.budget.is_exhausted() {
+            writeln!(&mut self.writer, "{}", self.budget.note())?;
+        }
+
+        Ok(exit_status)
     }
 
-    fn print_dirs(&mut self, dir_files: Vec<Dir>, mut first: bool, is_only_dir: bool, exit_status: i32) -> io::Result<i32> {
+    /// Lists the given directories, recursing into them if asked. The root
+    /// is the directory from the command-line they were found under, or
+    /// `None` if they were named there themselves.
+    fn print_dirs(&mut self, dir_files: Vec<Dir>, mut first: bool, is_only_dir: bool, root: Option<&Path>, exit_status: i32) -> io::Result<i32> {
         for dir in dir_files {
+            let root = root.unwrap_or(&dir.path);
+
+            if self.budget.is_exhausted() {
+                break;
+            }
 
             // Put a gap between directories, or between the list of files and
             // the first directory.
@@ -222,38 +348,80 @@ impl<'args> Exa<'args> {
                 writeln!(&mut self.writer)?;
             }
 
-            if ! is_only_dir {
+            // The summary and filesystem lines already include the
+            // directory’s path, so there’s no need to print it twice.
+            if ! is_only_dir && ! self.options.view.headings.any() {
                 let mut bits = Vec::new();
                 escape(dir.path.display().to_string(), &mut bits, Style::default(), Style::default());
                 writeln!(&mut self.writer, "{}:", ANSIStrings(&bits))?;
             }
 
             let mut children = Vec::new();
+            let mut unstatted = Vec::new();
             let git_ignore = self.options.filter.git_ignore == GitIgnore::CheckAndIgnore;
             for file in dir.files(self.options.filter.dot_filter, self.git.as_ref(), git_ignore) {
                 match file {
                     Ok(file)        => children.push(file),
-                    Err((path, e))  => writeln!(io::stderr(), "[{}: {}]", path.display(), e)?,
+                    Err((path, e))  => unstatted.push((path, e)),
                 }
             };
 
-            self.options.filter.filter_child_files(&mut children);
-            self.options.filter.sort_files(&mut children);
+            self.options.filter.filter_child_files(&mut children, root);
+            self.options.filter.sort_and_limit(&mut children);
+
+            if self.options.filter.should_warn_about_atimes(&children) {
+                writeln!(io::stderr(), "exa: {}: access times match modified times, so they may not be recorded", dir.path.display())?;
+            }
+
+            if self.options.view.headings.summary {
+                let summary = Summary::of_files(&children);
+                writeln!(&mut self.writer, "{}", ANSIStrings(&summary.render(&dir.path, &self.theme)))?;
+            }
+
+            if self.options.view.headings.filesystem_info {
+                match Statvfs.space(&dir.path) {
+                    Ok(space) => {
+                        let numerics = locale::Numeric::load_user_locale().unwrap_or_else(|_| locale::Numeric::english());
+                        let line = summary::render_space(space, &dir.path, &self.theme, &numerics);
+                        writeln!(&mut self.writer, "{}", ANSIStrings(&line))?;
+                    }
+                    Err(e) => {
+                        writeln!(io::stderr(), "{}: {}", dir.path.display(), e)?;
+                    }
+                }
+            }
 
             if let Some(recurse_opts) = self.options.dir_action.recurse_options() {
                 let depth = dir.path.components().filter(|&c| c != Component::CurDir).count() + 1;
                 if ! recurse_opts.tree && ! recurse_opts.is_too_deep(depth) {
 
                     let mut child_dirs = Vec::new();
-                    for child_dir in children.iter().filter(|f| f.is_directory() && ! f.is_all_all) {
+                    let mut skipped = 0;
+                    for (index, child_dir) in children.iter().filter(|f| f.is_directory() && ! f.is_all_all && recurse_opts.descends_into(&f.name)).enumerate() {
+                        if recurse_opts.is_too_wide(index) {
+                            skipped += 1;
+                            continue;
+                        }
+
                         match child_dir.to_dir() {
                             Ok(d)   => child_dirs.push(d),
                             Err(e)  => writeln!(io::stderr(), "{}: {}", child_dir.path.display(), e)?,
                         }
                     }
 
-                    self.print_files(Some(&dir), children)?;
-                    match self.print_dirs(child_dirs, false, false, exit_status) {
+                    if self.budget.spend(&mut children) {
+                        self.print_files(Some(&dir), children, unstatted)?;
+                        break;
+                    }
+
+                    let width_note = if skipped > 0 { Some(recurse_opts.width_note(skipped)) } else { None };
+
+                    self.print_files(Some(&dir), children, unstatted)?;
+                    if let Some(note) = width_note {
+                        writeln!(&mut self.writer, "{}", note)?;
+                    }
+
+                    match self.print_dirs(child_dirs, false, false, Some(root), exit_status) {
                         Ok(_)   => (),
                         Err(e)  => return Err(e),
                     }
@@ -261,33 +429,93 @@ impl<'args> Exa<'args> {
                 }
             }
 
-            self.print_files(Some(&dir), children)?;
+            // A tree spends the budget itself as it gets built, so it’s only
+            // a directory too deep to recurse into that needs spending here.
+            if ! self.is_tree() {
+                self.budget.spend(&mut children);
+            }
+
+            self.print_files(Some(&dir), children, unstatted)?;
         }
 
         Ok(exit_status)
     }
 
-    /// Prints the list of files using whichever view is selected.
-    fn print_files(&mut self, dir: Option<&Dir>, files: Vec<File<'_>>) -> io::Result<()> {
-        if files.is_empty() {
-            return Ok(());
+    /// Whether the listing is being shown as a tree, whose renderer spends
+    /// the entry budget itself.
+    fn is_tree(&self) -> bool {
+        self.options.dir_action.recurse_options().map_or(false, |r| r.tree)
+    }
+
+    /// Prints one line totalling up the given files and the contents of the
+    /// given directories, for `--oneliner-summary`.
+    fn print_totals(&mut self, files: &[File<'_>], dirs: &[Dir]) -> io::Result<()> {
+        let mut totals = Totals::of_files(files);
+
+        for dir in dirs {
+            let mut children = Vec::new();
+            let git_ignore = self.options.filter.git_ignore == GitIgnore::CheckAndIgnore;
+            for file in dir.files(self.options.filter.dot_filter, self.git.as_ref(), git_ignore) {
+                match file {
+                    Ok(file)        => children.push(file),
+                    Err((path, e))  => writeln!(io::stderr(), "[{}: {}]", path.display(), e)?,
+                }
+            };
+
+            self.options.filter.filter_child_files(&mut children, &dir.path);
+            totals.add(Totals::of_files(&children));
         }
 
+        let numerics = locale::Numeric::load_user_locale().unwrap_or_else(|_| locale::Numeric::english());
+        writeln!(&mut self.writer, "{}", totals.render(&self.theme, &numerics))
+    }
+
+    /// Prints the list of files using whichever view is selected, along
+    /// with any entries in the directory that couldn’t be statted.
+    fn print_files(&mut self, dir: Option<&Dir>, files: Vec<File<'_>>, unstatted: Vec<(PathBuf, io::Error)>) -> io::Result<()> {
         let theme = &self.theme;
-        let View { ref mode, ref file_style, .. } = self.options.view;
+        let View { ref mode, ref file_style, group_by, .. } = self.options.view;
+
+        // Only the details view has a row to show these in, so the other
+        // views report them as errors instead.
+        let unstatted = match (mode, self.console_width) {
+            (Mode::Details(_), _) | (Mode::GridDetails(_), None) => unstatted,
+            _ => {
+                for (path, e) in unstatted {
+                    writeln!(io::stderr(), "[{}: {}]", path.display(), e)?;
+                }
+                Vec::new()
+            }
+        };
+
+        if files.is_empty() && unstatted.is_empty() {
+            return Ok(());
+        }
 
         match (mode, self.console_width) {
             (Mode::Grid(ref opts), Some(console_width)) => {
                 let filter = &self.options.filter;
-                let r = grid::Render { files, theme, file_style, opts, console_width, filter };
-                r.render(&mut self.writer)
+                let render = |w: &mut pager::Writer, files| {
+                    grid::Render { files, theme, file_style, opts, console_width, filter }.render(w)
+                };
+
+                match group_by {
+                    GroupBy::Extension  => groups::render_by_extension(files, theme, &mut self.writer, render),
+                    GroupBy::Nothing    => render(&mut self.writer, files),
+                }
             }
 
             (Mode::Grid(_), None) |
             (Mode::Lines,   _)    => {
                 let filter = &self.options.filter;
-                let r = lines::Render { files, theme, file_style, filter };
-                r.render(&mut self.writer)
+                let render = |w: &mut pager::Writer, files| {
+                    lines::Render { files, theme, file_style, filter }.render(w)
+                };
+
+                match group_by {
+                    GroupBy::Extension  => groups::render_by_extension(files, theme, &mut self.writer, render),
+                    GroupBy::Nothing    => render(&mut self.writer, files),
+                }
             }
 
             (Mode::Details(ref opts), _) => {
@@ -296,7 +524,8 @@ impl<'args> Exa<'args> {
 
                 let git_ignoring = self.options.filter.git_ignore == GitIgnore::CheckAndIgnore;
                 let git = self.git.as_ref();
-                let r = details::Render { dir, files, theme, file_style, opts, recurse, filter, git_ignoring, git };
+                let budget = Some(&self.budget);
+                let r = details::Render { dir, files, theme, file_style, opts, recurse, budget, filter, git_ignoring, git, unstatted };
                 r.render(&mut self.writer)
             }
 
@@ -313,6 +542,18 @@ impl<'args> Exa<'args> {
                 r.render(&mut self.writer)
             }
 
+            (Mode::Stat, _) => {
+                stat::Render { files, theme }.render(&mut self.writer)
+            }
+
+            // The totals get printed by `print_totals` instead.
+            (Mode::OnelinerSummary, _) => Ok(()),
+
+            (Mode::NulSeparated, _) => {
+                let filter = &self.options.filter;
+                lines::Render { files, theme, file_style, filter }.render_nul_separated(&mut self.writer)
+            }
+
             (Mode::GridDetails(ref opts), None) => {
                 let opts = &opts.to_details_options();
                 let filter = &self.options.filter;
@@ -320,7 +561,8 @@ impl<'args> Exa<'args> {
                 let git_ignoring = self.options.filter.git_ignore == GitIgnore::CheckAndIgnore;
 
                 let git = self.git.as_ref();
-                let r = details::Render { dir, files, theme, file_style, opts, recurse, filter, git_ignoring, git };
+                let budget = Some(&self.budget);
+                let r = details::Render { dir, files, theme, file_style, opts, recurse, budget, filter, git_ignoring, git, unstatted };
                 r.render(&mut self.writer)
             }
         }
@@ -339,3 +581,225 @@ mod exits {
     /// Exit code for when the command-line options are invalid.
     pub const OPTIONS_ERROR: i32 = 3;
 }
+
+
+#[cfg(test)]
+#[cfg(unix)]
+mod test {
+    use super::*;
+    use crate::output::pager::Paging;
+    use crate::fs::test::TempDir;
+
+    /// Parses the arguments, which have to be valid.
+    fn parse<'a>(args: &[&'a OsStr]) -> (Options, Vec<&'a OsStr>) {
+        match Options::parse(args.iter().copied(), &None::<OsString>) {
+            OptionsResult::Ok(options, input_paths)  => (options, input_paths),
+            _                                        => panic!("Options failed to parse"),
+        }
+    }
+
+    /// Lists the paths given in the arguments into a buffer, as though
+    /// the output were being piped somewhere, and returns what got written.
+    fn listing_of(args: &[&OsStr]) -> String {
+        let (options, input_paths) = parse(args);
+        let writer = pager::Writer::Buffered { buffer: Vec::new(), paging: Paging::Never, height: None, pager: None };
+        let mut exa = Exa::new(options, input_paths, writer, None);
+        assert_eq!(exa.list().unwrap(), exits::SUCCESS);
+
+        match exa.writer {
+            pager::Writer::Buffered { buffer, .. }  => String::from_utf8(buffer).unwrap(),
+            _                                       => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn separate_trees() {
+        let path = TempDir::new("separate-trees");
+        let one = path.join("one");
+        let two = path.join("two");
+        std::fs::create_dir_all(one.join("inner")).unwrap();
+        std::fs::write(one.join("inner").join("deep"), b"").unwrap();
+        std::fs::write(one.join("shallow"), b"").unwrap();
+        std::fs::create_dir_all(&two).unwrap();
+        std::fs::write(two.join("file"), b"").unwrap();
+
+        let output = listing_of(&[ OsStr::new("--tree"), OsStr::new("--separate-trees"), one.as_os_str(), two.as_os_str() ]);
+        assert_eq!(output, format!("\
+{}:
+├── inner
+│  └── deep
+└── shallow
+
+{}:
+└── file
+", one.display(), two.display()));
+    }
+
+    #[test]
+    fn max_total() {
+        let path = TempDir::new("max-total");
+        std::fs::create_dir_all(path.join("sub")).unwrap();
+        for i in 0 .. 10 {
+            std::fs::write(path.join(format!("f{}", i)), b"").unwrap();
+            std::fs::write(path.join("sub").join(format!("g{}", i)), b"").unwrap();
+        }
+
+        let recursed = listing_of(&[ OsStr::new("--recurse"), OsStr::new("--max-total=5"), path.as_os_str() ]);
+        assert_eq!(recursed, "f0\nf1\nf2\nf3\nf4\n[listing stopped after 5 entries]\n");
+
+        let tree = listing_of(&[ OsStr::new("--tree"), OsStr::new("--max-total=5"), path.as_os_str() ]);
+        assert_eq!(tree, format!("\
+{}
+├── f0
+├── f1
+├── f2
+├── f3
+└── f4
+[listing stopped after 5 entries]
+", path.display()));
+
+        let too_deep = listing_of(&[ OsStr::new("--recurse"), OsStr::new("--level=1"), OsStr::new("--max-total=3"), path.as_os_str() ]);
+        assert_eq!(too_deep, "f0\nf1\nf2\n[listing stopped after 3 entries]\n");
+
+        let (f0, f1, f2, sub) = (path.join("f0"), path.join("f1"), path.join("f2"), path.join("sub"));
+        let arguments = listing_of(&[ OsStr::new("--recurse"), OsStr::new("--max-total=2"), f0.as_os_str(), f1.as_os_str(), f2.as_os_str(), sub.as_os_str() ]);
+        assert_eq!(arguments, format!("{}\n{}\n[listing stopped after 2 entries]\n", f0.display(), f1.display()));
+    }
+
+    #[test]
+    fn ignore_match_path() {
+        let path = TempDir::new("ignore-match-path");
+        std::fs::create_dir_all(path.join("src").join("fs")).unwrap();
+        std::fs::write(path.join("src").join("foo.rs"), b"").unwrap();
+        std::fs::write(path.join("src").join("fs").join("bar.rs"), b"").unwrap();
+
+        let recursed = listing_of(&[ OsStr::new("-R"), OsStr::new("--ignore-match-path"), OsStr::new("-I"), OsStr::new("src/*.rs"), path.as_os_str() ]);
+        assert_eq!(recursed, format!("\
+src
+
+{}:
+fs
+
+{}:
+bar.rs
+", path.join("src").display(), path.join("src").join("fs").display()));
+
+        let tree = listing_of(&[ OsStr::new("-T"), OsStr::new("--ignore-match-path"), OsStr::new("-I"), OsStr::new("src/*.rs"), path.as_os_str() ]);
+        assert_eq!(tree, format!("\
+{}
+└── src
+   └── fs
+      └── bar.rs
+", path.display()));
+    }
+
+    #[test]
+    #[cfg(feature = "git")]
+    fn git_max_files() {
+        let path = TempDir::new("git-max-files-listing");
+        std::fs::write(path.join("one"), b"original").unwrap();
+        std::fs::write(path.join("two"), b"original").unwrap();
+
+        let repo = git2::Repository::init(&*path).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_all([ "*" ].iter(), git2::IndexAddOption::DEFAULT, None).unwrap();
+        index.write().unwrap();
+        std::fs::write(path.join("one"), b"changed").unwrap();
+
+        let args = [ "--long", "--git", "--no-permissions", "--no-filesize", "--no-user", "--no-time", "--git-max-files=1" ];
+        let mut args = args.iter().map(OsStr::new).collect::<Vec<_>>();
+        args.push(path.as_os_str());
+
+        assert_eq!(listing_of(&args), "\
+-- one
+-- two
+0 modified, 0 staged, 0 untracked, 0 clean
+[Git statuses skipped for 2 files, as their repository is over --git-max-files]
+");
+    }
+
+    #[test]
+    fn complete_dirs() {
+        let path = TempDir::new("complete-dirs");
+        std::fs::create_dir_all(path.join("beta").join("nested")).unwrap();
+        std::fs::create_dir_all(path.join("alpha")).unwrap();
+        std::fs::write(path.join("file"), b"").unwrap();
+
+        let output = listing_of(&[ OsStr::new("--complete-dirs"), path.as_os_str() ]);
+        assert_eq!(output, "alpha\0beta\0");
+    }
+
+    #[test]
+    fn oneliner_summary() {
+        let path = TempDir::new("oneliner-summary");
+        std::fs::create_dir_all(path.join("sub")).unwrap();
+        std::fs::write(path.join("one"), vec![ 0; 1000 ]).unwrap();
+        std::fs::write(path.join("two"), vec![ 0; 500 ]).unwrap();
+        std::fs::write(path.join(".hidden"), vec![ 0; 2000 ]).unwrap();
+
+        let summary = listing_of(&[ OsStr::new("--oneliner-summary"), path.as_os_str() ]);
+        assert_eq!(summary, "2 files, 1 dir, 1.5k\n");
+
+        let summary = listing_of(&[ OsStr::new("--oneliner-summary"), OsStr::new("-a"), OsStr::new("-I"), OsStr::new("two"), path.as_os_str() ]);
+        assert_eq!(summary, "2 files, 1 dir, 3.0k\n");
+    }
+
+    #[test]
+    fn output_to_file() {
+        let path = TempDir::new("output");
+        let listing = path.join("listing");
+        std::fs::create_dir_all(path.join("dir")).unwrap();
+        std::fs::write(path.join("dir").join("one"), b"").unwrap();
+        std::fs::write(path.join("dir").join("two"), b"").unwrap();
+
+        let output = OsString::from(format!("--output={}", listing.display()));
+        let dir = path.join("dir");
+        let (options, input_paths) = parse(&[ output.as_os_str(), OsStr::new("--colour=auto"), dir.as_os_str() ]);
+
+        // A file can be given a width, with `COLUMNS` and `--grid`, but
+        // that still shouldn’t make it count as a terminal for colours.
+        let (writer, _) = output_for(&options, false).unwrap();
+        let exa = Exa::new(options, input_paths, writer, Some(80));
+        assert_eq!(exa.run().unwrap(), exits::SUCCESS);
+
+        assert_eq!(std::fs::read_to_string(&listing).unwrap(), "one  two\n");
+    }
+
+    #[test]
+    fn watch_lists_again_after_a_change() {
+        // Adds a file the first time it’s waited on, then stops.
+        struct OneChange(PathBuf, bool);
+        impl watch::Trigger for OneChange {
+            fn wait(&mut self) -> bool {
+                if self.1 {
+                    return false;
+                }
+
+                std::fs::write(self.0.join("two"), b"").unwrap();
+                self.1 = true;
+                true
+            }
+        }
+
+        let path = TempDir::new("watch");
+        let dir = path.join("dir");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("one"), b"").unwrap();
+
+        let (options, input_paths) = parse(&[ OsStr::new("--watch"), dir.as_os_str() ]);
+
+        let listing = path.join("listing");
+        let writer = pager::Writer::to_file(&listing).unwrap();
+        let exa = Exa::new(options, input_paths, writer, None);
+        assert_eq!(exa.watch(OneChange(dir.clone(), false)).unwrap(), exits::SUCCESS);
+
+        let clear = watch::CLEAR_SCREEN;
+        assert_eq!(std::fs::read_to_string(&listing).unwrap(), format!("{}one\n{}one\ntwo\n", clear, clear));
+    }
+
+    #[test]
+    fn output_to_missing_directory() {
+        let path = TempDir::new("output-missing");
+        assert!(pager::Writer::to_file(&path.join("nowhere").join("listing")).is_err());
+    }
+}
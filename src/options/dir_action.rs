@@ -1,6 +1,6 @@
 //! Parsing the options for `DirAction`.
 
-use crate::options::parser::MatchedFlags;
+use crate::options::parser::{Arg, MatchedFlags};
 use crate::options::{flags, OptionsError, NumberSource};
 
 use crate::fs::dir_action::{DirAction, RecurseOptions};
@@ -28,6 +28,18 @@ impl DirAction {
             else if tree && as_file {
                 return Err(OptionsError::Conflict(&flags::TREE, &flags::LIST_DIRS));
             }
+            else if ! recurse && ! tree && matches.count(&flags::MAX_TOTAL) > 0 {
+                return Err(OptionsError::Useless2(&flags::MAX_TOTAL, &flags::RECURSE, &flags::TREE));
+            }
+            else if ! tree && matches.has(&flags::SEPARATE_TREES)? {
+                return Err(OptionsError::Useless(&flags::SEPARATE_TREES, false, &flags::TREE));
+            }
+            else if ! recurse && ! tree && matches.count(&flags::DESCEND_ONLY) > 0 {
+                return Err(OptionsError::Useless2(&flags::DESCEND_ONLY, &flags::RECURSE, &flags::TREE));
+            }
+            else if ! recurse && ! tree && matches.count(&flags::MAX_WIDTH) > 0 {
+                return Err(OptionsError::Useless2(&flags::MAX_WIDTH, &flags::RECURSE, &flags::TREE));
+            }
         }
 
         if tree && can_tree {
@@ -53,22 +65,37 @@ impl RecurseOptions {
     /// Determine which files should be recursed into, based on the `--level`
     /// flag’s value, and whether the `--tree` flag was passed, which was
     /// determined earlier. The maximum level should be a number, and this
-    /// will fail with an `Err` if it isn’t.
+    /// will fail with an `Err` if it isn’t, as will a `--descend-only`
+    /// pattern that isn’t a valid glob.
     pub fn deduce(matches: &MatchedFlags<'_>, tree: bool) -> Result<Self, OptionsError> {
-        if let Some(level) = matches.get(&flags::LEVEL)? {
-            let arg_str = level.to_string_lossy();
+        let max_depth = Self::deduce_number(matches, &flags::LEVEL)?;
+        let max_total = Self::deduce_number(matches, &flags::MAX_TOTAL)?;
+        let max_width = Self::deduce_number(matches, &flags::MAX_WIDTH)?;
+        let separate_trees = tree && matches.has(&flags::SEPARATE_TREES)?;
+        let descend_only = match matches.get(&flags::DESCEND_ONLY)? {
+            Some(glob)  => Some(glob::Pattern::new(&glob.to_string_lossy())?),
+            None        => None,
+        };
+
+        Ok(Self { tree, max_depth, max_total, separate_trees, descend_only, max_width })
+    }
+
+    /// Parses the value of the given flag as a number, if it was passed.
+    fn deduce_number(matches: &MatchedFlags<'_>, flag: &'static Arg) -> Result<Option<usize>, OptionsError> {
+        if let Some(value) = matches.get(flag)? {
+            let arg_str = value.to_string_lossy();
             match arg_str.parse() {
-                Ok(l) => {
-                    Ok(Self { tree, max_depth: Some(l) })
+                Ok(n) => {
+                    Ok(Some(n))
                 }
                 Err(e) => {
-                    let source = NumberSource::Arg(&flags::LEVEL);
+                    let source = NumberSource::Arg(flag);
                     Err(OptionsError::FailedParse(arg_str.to_string(), source, e))
                 }
             }
         }
         else {
-            Ok(Self { tree, max_depth: None })
+            Ok(None)
         }
     }
 }
@@ -88,7 +115,7 @@ mod test {
                 use crate::options::test::parse_for_test;
                 use crate::options::test::Strictnesses::*;
 
-                static TEST_ARGS: &[&Arg] = &[&flags::RECURSE, &flags::LIST_DIRS, &flags::TREE, &flags::LEVEL ];
+                static TEST_ARGS: &[&Arg] = &[&flags::RECURSE, &flags::LIST_DIRS, &flags::TREE, &flags::LEVEL, &flags::MAX_TOTAL, &flags::SEPARATE_TREES, &flags::DESCEND_ONLY, &flags::MAX_WIDTH ];
                 for result in parse_for_test($inputs.as_ref(), TEST_ARGS, $stricts, |mf| $type::deduce(mf, true)) {
                     assert_eq!(result, $result);
                 }
@@ -106,19 +133,19 @@ mod test {
 
     // Recursing
     use self::DirAction::Recurse;
-    test!(rec_short:       DirAction <- ["-R"];                           Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: None })));
-    test!(rec_long:        DirAction <- ["--recurse"];                    Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: None })));
-    test!(rec_lim_short:   DirAction <- ["-RL4"];                         Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(4) })));
-    test!(rec_lim_short_2: DirAction <- ["-RL=5"];                        Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(5) })));
-    test!(rec_lim_long:    DirAction <- ["--recurse", "--level", "666"];  Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(666) })));
-    test!(rec_lim_long_2:  DirAction <- ["--recurse", "--level=0118"];    Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(118) })));
-    test!(tree:            DirAction <- ["--tree"];                       Both => Ok(Recurse(RecurseOptions { tree: true,  max_depth: None })));
-    test!(rec_tree:        DirAction <- ["--recurse", "--tree"];          Both => Ok(Recurse(RecurseOptions { tree: true,  max_depth: None })));
-    test!(rec_short_tree:  DirAction <- ["-TR"];                          Both => Ok(Recurse(RecurseOptions { tree: true,  max_depth: None })));
+    test!(rec_short:       DirAction <- ["-R"];                           Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: None, max_total: None, separate_trees: false, descend_only: None, max_width: None })));
+    test!(rec_long:        DirAction <- ["--recurse"];                    Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: None, max_total: None, separate_trees: false, descend_only: None, max_width: None })));
+    test!(rec_lim_short:   DirAction <- ["-RL4"];                         Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(4), max_total: None, separate_trees: false, descend_only: None, max_width: None })));
+    test!(rec_lim_short_2: DirAction <- ["-RL=5"];                        Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(5), max_total: None, separate_trees: false, descend_only: None, max_width: None })));
+    test!(rec_lim_long:    DirAction <- ["--recurse", "--level", "666"];  Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(666), max_total: None, separate_trees: false, descend_only: None, max_width: None })));
+    test!(rec_lim_long_2:  DirAction <- ["--recurse", "--level=0118"];    Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(118), max_total: None, separate_trees: false, descend_only: None, max_width: None })));
+    test!(tree:            DirAction <- ["--tree"];                       Both => Ok(Recurse(RecurseOptions { tree: true,  max_depth: None, max_total: None, separate_trees: false, descend_only: None, max_width: None })));
+    test!(rec_tree:        DirAction <- ["--recurse", "--tree"];          Both => Ok(Recurse(RecurseOptions { tree: true,  max_depth: None, max_total: None, separate_trees: false, descend_only: None, max_width: None })));
+    test!(rec_short_tree:  DirAction <- ["-TR"];                          Both => Ok(Recurse(RecurseOptions { tree: true,  max_depth: None, max_total: None, separate_trees: false, descend_only: None, max_width: None })));
 
     // Overriding --list-dirs, --recurse, and --tree
-    test!(dirs_recurse:    DirAction <- ["--list-dirs", "--recurse"];     Last => Ok(Recurse(RecurseOptions { tree: false, max_depth: None })));
-    test!(dirs_tree:       DirAction <- ["--list-dirs", "--tree"];        Last => Ok(Recurse(RecurseOptions { tree: true,  max_depth: None })));
+    test!(dirs_recurse:    DirAction <- ["--list-dirs", "--recurse"];     Last => Ok(Recurse(RecurseOptions { tree: false, max_depth: None, max_total: None, separate_trees: false, descend_only: None, max_width: None })));
+    test!(dirs_tree:       DirAction <- ["--list-dirs", "--tree"];        Last => Ok(Recurse(RecurseOptions { tree: true,  max_depth: None, max_total: None, separate_trees: false, descend_only: None, max_width: None })));
     test!(just_level:      DirAction <- ["--level=4"];                    Last => Ok(DirAction::List));
 
     test!(dirs_recurse_2:  DirAction <- ["--list-dirs", "--recurse"]; Complain => Err(OptionsError::Conflict(&flags::RECURSE, &flags::LIST_DIRS)));
@@ -126,7 +153,35 @@ mod test {
     test!(just_level_2:    DirAction <- ["--level=4"];                Complain => Err(OptionsError::Useless2(&flags::LEVEL, &flags::RECURSE, &flags::TREE)));
 
 
+    // Limiting the total number of entries
+    test!(max_total:       DirAction <- ["-R", "--max-total=5"];          Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: None, max_total: Some(5), separate_trees: false, descend_only: None, max_width: None })));
+    test!(max_total_2:     DirAction <- ["--max-total=5"];                Last => Ok(DirAction::List));
+    test!(max_total_3:     DirAction <- ["--max-total=5"];            Complain => Err(OptionsError::Useless2(&flags::MAX_TOTAL, &flags::RECURSE, &flags::TREE)));
+    test!(max_total_4:     DirAction <- ["--tree", "--max-total=5"];      Both => Ok(Recurse(RecurseOptions { tree: true,  max_depth: None, max_total: Some(5), separate_trees: false, descend_only: None, max_width: None })));
+
+
+    // Listing each argument as its own tree
+    test!(separate:        DirAction <- ["--tree", "--separate-trees"];   Both => Ok(Recurse(RecurseOptions { tree: true,  max_depth: None, max_total: None, separate_trees: true, descend_only: None, max_width: None })));
+    test!(separate_2:      DirAction <- ["-R", "--separate-trees"];       Last => Ok(Recurse(RecurseOptions { tree: false, max_depth: None, max_total: None, separate_trees: false, descend_only: None, max_width: None })));
+    test!(separate_3:      DirAction <- ["-R", "--separate-trees"];   Complain => Err(OptionsError::Useless(&flags::SEPARATE_TREES, false, &flags::TREE)));
+
+
     // Overriding levels
-    test!(overriding_1:    DirAction <- ["-RL=6", "-L=7"];                Last => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(7) })));
+    test!(overriding_1:    DirAction <- ["-RL=6", "-L=7"];                Last => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(7), max_total: None, separate_trees: false, descend_only: None, max_width: None })));
     test!(overriding_2:    DirAction <- ["-RL=6", "-L=7"];            Complain => Err(OptionsError::Duplicate(Flag::Short(b'L'), Flag::Short(b'L'))));
+    test!(overriding_3:    DirAction <- ["--tree", "--level=2", "--level=5"];  Last => Ok(Recurse(RecurseOptions { tree: true, max_depth: Some(5), max_total: None, separate_trees: false, descend_only: None, max_width: None })));
+    test!(overriding_4:    DirAction <- ["--tree", "--level=2", "--level=5"];  Complain => Err(OptionsError::Duplicate(Flag::Long("level"), Flag::Long("level"))));
+
+
+    // Only descending into some directories
+    test!(descend:         DirAction <- ["--tree", "--descend-only=src"]; Both => Ok(Recurse(RecurseOptions { tree: true,  max_depth: None, max_total: None, separate_trees: false, descend_only: Some(glob::Pattern::new("src").unwrap()), max_width: None })));
+    test!(descend_2:       DirAction <- ["--descend-only=src"];           Last => Ok(DirAction::List));
+    test!(descend_3:       DirAction <- ["--descend-only=src"];       Complain => Err(OptionsError::Useless2(&flags::DESCEND_ONLY, &flags::RECURSE, &flags::TREE)));
+    test!(descend_4:       DirAction <- ["-R", "--descend-only=[src"];    Both => Err(OptionsError::FailedGlobPattern(glob::Pattern::new("[src").unwrap_err().to_string())));
+
+
+    // Limiting how many directories get descended into
+    test!(max_width:       DirAction <- ["-R", "--max-width=3"];          Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: None, max_total: None, separate_trees: false, descend_only: None, max_width: Some(3) })));
+    test!(max_width_2:     DirAction <- ["--max-width=3"];                Last => Ok(DirAction::List));
+    test!(max_width_3:     DirAction <- ["--max-width=3"];            Complain => Err(OptionsError::Useless2(&flags::MAX_WIDTH, &flags::RECURSE, &flags::TREE)));
 }
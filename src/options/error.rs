@@ -41,6 +41,9 @@ pub enum OptionsError {
 
     /// A glob ignore was given that failed to be parsed as a pattern.
     FailedGlobPattern(String),
+
+    /// The stat view was asked for with more than one file to show.
+    StatNeedsOneFile(usize),
 }
 
 /// The source of a string that failed to be parsed as a number.
@@ -93,6 +96,7 @@ impl fmt::Display for OptionsError {
             Self::TreeAllAll                 => write!(f, "Option --tree is useless given --all --all"),
             Self::FailedParse(s, n, e)       => write!(f, "Value {:?} not valid for {}: {}", s, n, e),
             Self::FailedGlobPattern(ref e)   => write!(f, "Failed to parse glob pattern: {}", e),
+            Self::StatNeedsOneFile(count)    => write!(f, "Option --stat can only show one file, but {} were given", count),
         }
     }
 }
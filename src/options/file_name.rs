@@ -1,28 +1,145 @@
+use std::ffi::OsStr;
+use std::path::Path;
+
 use crate::options::{flags, OptionsError, NumberSource};
 use crate::options::parser::MatchedFlags;
 use crate::options::vars::{self, Vars};
 
-use crate::output::file_name::{Options, Classify, ShowIcons};
+use crate::output::file_name::{Options, Classify, Indicators, ShowIcons, SymlinkColours, MarkEmpty, MarkUnreadable, SymlinkPath, RelativeTo, QuoteNames, BidiIsolate};
 
 
 impl Options {
     pub fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
         let classify = Classify::deduce(matches)?;
         let show_icons = ShowIcons::deduce(matches, vars)?;
+        let symlink_colours = SymlinkColours::deduce(matches)?;
+        let mark_empty = MarkEmpty::deduce(matches)?;
+        let mark_unreadable = MarkUnreadable::deduce(matches)?;
+        let symlink_path = SymlinkPath::deduce(matches)?;
+        let relative_to = RelativeTo::deduce(matches)?;
+        let quote_names = QuoteNames::deduce(matches)?;
+        let bidi_isolate = BidiIsolate::deduce(matches)?;
+
+        Ok(Self { classify, show_icons, symlink_colours, mark_empty, mark_unreadable, symlink_path, relative_to, quote_names, bidi_isolate })
+    }
+}
 
-        Ok(Self { classify, show_icons })
+impl RelativeTo {
+
+    /// Works out the directory to show paths relative to, with the current
+    /// directory being used if `--relative-to` isn’t given one.
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Option<Self>, OptionsError> {
+        if let Some(dir) = matches.get(&flags::RELATIVE_TO)? {
+            Ok(Some(Self::new(Path::new(dir))))
+        }
+        else if matches.has(&flags::RELATIVE_TO)? {
+            Ok(Some(Self::new(Path::new("."))))
+        }
+        else {
+            Ok(None)
+        }
     }
 }
 
 impl Classify {
     fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        if let Some(chars) = matches.get(&flags::CLASSIFY_CHARS)? {
+            return Ok(Self::AddFileIndicators(Indicators::deduce(chars)?));
+        }
+
         let flagged = matches.has(&flags::CLASSIFY)?;
 
-        if flagged { Ok(Self::AddFileIndicators) }
+        if flagged { Ok(Self::AddFileIndicators(Indicators::ALL)) }
               else { Ok(Self::JustFilenames) }
     }
 }
 
+impl Indicators {
+
+    /// Picks which classes of file get indicators from a comma-separated
+    /// list of their names, which implies `--classify` on its own.
+    fn deduce(chars: &OsStr) -> Result<Self, OptionsError> {
+        let list = match chars.to_str() {
+            Some(list)  => list,
+            None        => return Err(OptionsError::BadArgument(&flags::CLASSIFY_CHARS, chars.into())),
+        };
+
+        let mut indicators = Self::NONE;
+        for word in list.split(',') {
+            let class = match word {
+                "dir"     => Self::DIRECTORY,
+                "exec"    => Self::EXECUTABLE,
+                "link"    => Self::SYMLINK,
+                "pipe"    => Self::PIPE,
+                "socket"  => Self::SOCKET,
+                _         => return Err(OptionsError::BadArgument(&flags::CLASSIFY_CHARS, word.into())),
+            };
+
+            indicators = indicators.with(class);
+        }
+
+        Ok(indicators)
+    }
+}
+
+impl SymlinkColours {
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let flagged = matches.has(&flags::SYMLINK_TARGET_COLOR)?;
+
+        if flagged { Ok(Self::Target) }
+              else { Ok(Self::Link) }
+    }
+}
+
+impl MarkEmpty {
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let flagged = matches.has(&flags::MARK_EMPTY)?;
+
+        if flagged { Ok(Self::AddMarker) }
+              else { Ok(Self::Off) }
+    }
+}
+
+impl MarkUnreadable {
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let flagged = matches.has(&flags::MARK_UNREADABLE)?;
+
+        if flagged { Ok(Self::AddOverlay) }
+              else { Ok(Self::Off) }
+    }
+}
+
+impl SymlinkPath {
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        if matches.has(&flags::SHORT_SYMLINK_TARGETS)? {
+            return Ok(Self::Hidden);
+        }
+
+        let flagged = matches.has_where(|f| f.matches(&flags::COLOR_SYMLINK_PATH) || f.matches(&flags::COLOUR_SYMLINK_PATH))?;
+
+        if flagged.is_some() { Ok(Self::ColourComponents) }
+                        else { Ok(Self::Plain) }
+    }
+}
+
+impl QuoteNames {
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let flagged = matches.has(&flags::QUOTE)?;
+
+        if flagged { Ok(Self::Shell) }
+              else { Ok(Self::Off) }
+    }
+}
+
+impl BidiIsolate {
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let flagged = matches.has(&flags::BIDI_ISOLATE)?;
+
+        if flagged { Ok(Self::Isolate) }
+              else { Ok(Self::Off) }
+    }
+}
+
 impl ShowIcons {
     pub fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
         if matches.has(&flags::NO_ICONS)? || !matches.has(&flags::ICONS)? {
@@ -44,3 +161,49 @@ impl ShowIcons {
         }
     }
 }
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::ffi::OsString;
+    use crate::options::flags;
+    use crate::options::parser::Flag;
+
+    macro_rules! test {
+        ($name:ident: $type:ident <- $inputs:expr; $stricts:expr => $result:expr) => {
+            #[test]
+            fn $name() {
+                use crate::options::parser::Arg;
+                use crate::options::test::parse_for_test;
+                use crate::options::test::Strictnesses::*;
+
+                static TEST_ARGS: &[&Arg] = &[ &flags::CLASSIFY, &flags::CLASSIFY_CHARS, &flags::RELATIVE_TO ];
+                for result in parse_for_test($inputs.as_ref(), TEST_ARGS, $stricts, |mf| $type::deduce(mf)) {
+                    assert_eq!(result, $result);
+                }
+            }
+        };
+    }
+
+
+    // Default behaviour
+    test!(empty:       Classify <- [];                                Both => Ok(Classify::JustFilenames));
+    test!(everything:  Classify <- ["--classify"];                    Both => Ok(Classify::AddFileIndicators(Indicators::ALL)));
+
+    // Picking indicators
+    test!(dir:         Classify <- ["--classify-chars=dir"];          Both => Ok(Classify::AddFileIndicators(Indicators::DIRECTORY)));
+    test!(dir_exec:    Classify <- ["--classify-chars=dir,exec"];     Both => Ok(Classify::AddFileIndicators(Indicators::DIRECTORY.with(Indicators::EXECUTABLE))));
+    test!(with_flag:   Classify <- ["-F", "--classify-chars=link"];   Both => Ok(Classify::AddFileIndicators(Indicators::SYMLINK)));
+    test!(overridden:  Classify <- ["--classify-chars=dir", "--classify-chars=pipe"];  Last => Ok(Classify::AddFileIndicators(Indicators::PIPE)));
+    test!(duplicate:   Classify <- ["--classify-chars=dir", "--classify-chars=pipe"];  Complain => Err(OptionsError::Duplicate(Flag::Long("classify-chars"), Flag::Long("classify-chars"))));
+
+    // Errors
+    test!(unknown:     Classify <- ["--classify-chars=dir,door"];     Both => Err(OptionsError::BadArgument(&flags::CLASSIFY_CHARS, OsString::from("door"))));
+    test!(empty_list:  Classify <- ["--classify-chars", ""];          Both => Err(OptionsError::BadArgument(&flags::CLASSIFY_CHARS, OsString::from(""))));
+
+    // Relative paths
+    test!(no_base:     RelativeTo <- [];                              Both => Ok(None));
+    test!(base:        RelativeTo <- ["--relative-to=/tmp/../usr"];   Both => Ok(Some(RelativeTo::new(Path::new("/usr")))));
+    test!(base_here:   RelativeTo <- ["--relative-to"];               Both => Ok(Some(RelativeTo::new(Path::new(".")))));
+}
@@ -1,26 +1,56 @@
 //! Parsing the options for `FileFilter`.
 
 use crate::fs::DotFilter;
-use crate::fs::filter::{FileFilter, SortField, SortCase, IgnorePatterns, GitIgnore};
+use crate::fs::filter::{FileFilter, SortField, SortCase, SortOrder, IgnorePatterns, ModifiedWindow, GitIgnore, IgnoreFiles, IgnoreFileCache, TypeOrder, AtimeWarning, DotfileOrder};
 
-use crate::options::{flags, OptionsError};
-use crate::options::parser::MatchedFlags;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::options::{flags, vars, OptionsError, NumberSource, Vars};
+use crate::options::parser::{Arg, MatchedFlags};
 
 
 impl FileFilter {
 
     /// Determines which of all the file filter options to use.
-    pub fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+    pub fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
+        let mut ignore_patterns = IgnorePatterns::deduce(matches)?;
+        ignore_patterns.extend(IgnorePatterns::deduce_global(matches, vars)?);
+
         Ok(Self {
             list_dirs_first:  matches.has(&flags::DIRS_FIRST)?,
+            dotfile_order:    DotfileOrder::deduce(matches)?,
             reverse:          matches.has(&flags::REVERSE)?,
+            type_order:       TypeOrder::deduce(matches)?,
+            atime_warning:    AtimeWarning::deduce(matches)?,
             only_dirs:        matches.has(&flags::ONLY_DIRS)?,
+            modified_window:  ModifiedWindow::deduce(matches)?,
             sort_field:       SortField::deduce(matches)?,
+            secondary_sort:   SortField::deduce_secondary(matches)?,
+            sort_order:       SortOrder::deduce(matches)?,
             dot_filter:       DotFilter::deduce(matches)?,
-            ignore_patterns:  IgnorePatterns::deduce(matches)?,
+            ignore_patterns,
             git_ignore:       GitIgnore::deduce(matches)?,
+            ignore_files:     IgnoreFiles::deduce(matches)?,
+            max_entries:      Self::deduce_max_entries(matches)?,
         })
     }
+
+    /// Determines the most entries to list from each directory, from the
+    /// `--max-entries` argument, which has to be a number if it’s given.
+    fn deduce_max_entries(matches: &MatchedFlags<'_>) -> Result<Option<usize>, OptionsError> {
+        let value = match matches.get(&flags::MAX_ENTRIES)? {
+            Some(v)  => v.to_string_lossy(),
+            None     => return Ok(None),
+        };
+
+        match value.parse() {
+            Ok(n)   => Ok(Some(n)),
+            Err(e)  => Err(OptionsError::FailedParse(value.to_string(), NumberSource::Arg(&flags::MAX_ENTRIES), e)),
+        }
+    }
 }
 
 impl SortField {
@@ -30,15 +60,45 @@ impl SortField {
     /// Returns the default sort field if none is given, or `Err` if the
     /// value doesn’t correspond to a sort field we know about.
     fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let compound = matches.has(&flags::COMPOUND_EXTENSIONS)?;
+
         let word = match matches.get(&flags::SORT)? {
             Some(w)  => w,
             None     => return Ok(Self::default()),
         };
 
+        // The compound extension modifier changes sorting by extension, and
+        // leaves any other sort alone, as it changes colouring too.
+        match Self::from_word(&flags::SORT, word)? {
+            Self::Extension(case) if compound => Ok(Self::CompoundExtension(case)),
+            field                             => Ok(field),
+        }
+    }
+
+    /// Determines which sort field to use to break ties, based on the
+    /// `--sort2` argument, if one is given. This has to be used with `--sort`,
+    /// because there’s nothing for it to break ties in otherwise.
+    fn deduce_secondary(matches: &MatchedFlags<'_>) -> Result<Option<Self>, OptionsError> {
+        let word = match matches.get(&flags::SORT2)? {
+            Some(w)  => w,
+            None     => return Ok(None),
+        };
+
+        if matches.get(&flags::SORT)?.is_none() {
+            return Err(OptionsError::Useless(&flags::SORT2, false, &flags::SORT));
+        }
+
+        Self::from_word(&flags::SORT2, word).map(Some)
+    }
+
+    /// Turns the value of one of the sort arguments into a sort field, or
+    /// returns `Err` if it doesn’t correspond to one we know about.
+    fn from_word(flag: &'static Arg, word: &OsStr) -> Result<Self, OptionsError> {
+
         // Get String because we can’t match an OsStr
         let word = match word.to_str() {
             Some(w)  => w,
-            None     => return Err(OptionsError::BadArgument(&flags::SORT, word.into()))
+            None     => return Err(OptionsError::BadArgument(flag, word.into()))
         };
 
         let field = match word {
@@ -54,6 +114,9 @@ impl SortField {
             ".Name" | ".Filename" => {
                 Self::NameMixHidden(SortCase::ABCabc)
             }
+            "dict" | "dictionary" => {
+                Self::Name(SortCase::Dictionary)
+            }
             "size" | "filesize" => {
                 Self::Size
             }
@@ -95,11 +158,17 @@ impl SortField {
             "type" => {
                 Self::FileType
             }
+            "num" | "numeric" => {
+                Self::Numeric
+            }
+            "child-count" => {
+                Self::ChildCount
+            }
             "none" => {
                 Self::Unsorted
             }
             _ => {
-                return Err(OptionsError::BadArgument(&flags::SORT, word.into()));
+                return Err(OptionsError::BadArgument(flag, word.into()));
             }
         };
 
@@ -146,6 +215,29 @@ impl Default for SortField {
 }
 
 
+impl SortOrder {
+
+    /// Determines how to compare file names based on the `--sort-order`
+    /// argument, comparing them naturally if it isn’t given.
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let word = match matches.get(&flags::SORT_ORDER)? {
+            Some(w)  => w,
+            None     => return Ok(Self::Natural),
+        };
+
+        if word == "natural" {
+            Ok(Self::Natural)
+        }
+        else if word == "byte" {
+            Ok(Self::Byte)
+        }
+        else {
+            Err(OptionsError::BadArgument(&flags::SORT_ORDER, word.into()))
+        }
+    }
+}
+
+
 impl DotFilter {
 
     /// Determines the dot filter based on how many `--all` options were
@@ -187,12 +279,16 @@ impl IgnorePatterns {
         // match anything, rather than, say, `None`.
         let inputs = match matches.get(&flags::IGNORE_GLOB)? {
             Some(is)  => is,
+            None if matches.is_strict() && matches.has(&flags::IGNORE_MATCH_PATH)? => {
+                return Err(OptionsError::Useless(&flags::IGNORE_MATCH_PATH, false, &flags::IGNORE_GLOB));
+            }
             None      => return Ok(Self::empty()),
         };
 
         // Awkwardly, though, a glob pattern can be invalid, and we need to
         // deal with invalid patterns somehow.
-        let (patterns, mut errors) = Self::parse_from_iter(inputs.to_string_lossy().split('|'));
+        let (mut patterns, mut errors) = Self::parse_from_iter(inputs.to_string_lossy().split('|'));
+        patterns.match_path = matches.has(&flags::IGNORE_MATCH_PATH)?;
 
         // It can actually return more than one glob error,
         // but we only use one. (TODO)
@@ -201,6 +297,125 @@ impl IgnorePatterns {
             None     => Ok(patterns),
         }
     }
+
+    /// Determines the set of glob patterns in the user’s global ignore file,
+    /// `exa/ignore` in their configuration directory, unless the
+    /// `--no-global-ignore` argument is given. The file has one pattern per
+    /// line, with blank lines and `#` comments skipped. A file that doesn’t
+    /// exist, or can’t be read, has no patterns in it.
+    pub fn deduce_global<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
+        if matches.has(&flags::NO_GLOBAL_IGNORE)? {
+            return Ok(Self::empty());
+        }
+
+        let contents = match global_ignore_path(vars).and_then(|path| fs::read_to_string(path).ok()) {
+            Some(c)  => c,
+            None     => return Ok(Self::empty()),
+        };
+
+        let lines = contents.lines()
+                            .map(str::trim)
+                            .filter(|line| ! line.is_empty() && ! line.starts_with('#'));
+
+        let (patterns, mut errors) = Self::parse_from_iter(lines);
+        match errors.pop() {
+            Some(e)  => Err(e.into()),
+            None     => Ok(patterns),
+        }
+    }
+}
+
+/// The path to the user’s global ignore file, which goes in the XDG config
+/// directory, or `~/.config` if that isn’t set.
+fn global_ignore_path<V: Vars>(vars: &V) -> Option<PathBuf> {
+    let config = match vars.get(vars::XDG_CONFIG_HOME).filter(|dir| ! dir.is_empty()) {
+        Some(dir)  => PathBuf::from(dir),
+        None       => PathBuf::from(vars.get(vars::HOME).filter(|dir| ! dir.is_empty())?).join(".config"),
+    };
+
+    Some(config.join("exa").join("ignore"))
+}
+
+
+impl ModifiedWindow {
+
+    /// Determines how long ago files must have been modified to be shown,
+    /// from the `--newer-than` and `--older-than` arguments. Either end of
+    /// the window is left open if its argument isn’t given.
+    pub fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        Ok(Self {
+            newer_than: deduce_duration(matches, &flags::NEWER_THAN)?,
+            older_than: deduce_duration(matches, &flags::OLDER_THAN)?,
+        })
+    }
+}
+
+/// Parses the value of an argument that takes a length of time, such as
+/// `7d` or `2h`: a whole number followed by a unit of `s`, `m`, `h`, `d`,
+/// or `w`, for seconds, minutes, hours, days, or weeks.
+fn deduce_duration(matches: &MatchedFlags<'_>, flag: &'static Arg) -> Result<Option<Duration>, OptionsError> {
+    let word = match matches.get(flag)? {
+        Some(w)  => w,
+        None     => return Ok(None),
+    };
+
+    let word = match word.to_str() {
+        Some(w)  => w,
+        None     => return Err(OptionsError::BadArgument(flag, word.into())),
+    };
+
+    let unit_start = word.char_indices().last().map_or(0, |(index, _)| index);
+    let (number, unit) = word.split_at(unit_start);
+
+    let seconds = match unit {
+        "s"  => 1,
+        "m"  => 60,
+        "h"  => 60 * 60,
+        "d"  => 24 * 60 * 60,
+        "w"  => 7 * 24 * 60 * 60,
+        _    => return Err(OptionsError::BadArgument(flag, word.into())),
+    };
+
+    match number.parse::<u64>() {
+        Ok(n)   => Ok(Some(Duration::from_secs(n.saturating_mul(seconds)))),
+        Err(e)  => Err(OptionsError::FailedParse(word.to_string(), NumberSource::Arg(flag), e)),
+    }
+}
+
+
+impl DotfileOrder {
+    pub fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        if matches.has(&flags::DOTFILES_LAST)? {
+            Ok(Self::Last)
+        }
+        else {
+            Ok(Self::Mixed)
+        }
+    }
+}
+
+
+impl TypeOrder {
+    pub fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        if matches.has(&flags::TYPE_STABLE)? {
+            Ok(Self::Stable)
+        }
+        else {
+            Ok(Self::Reversible)
+        }
+    }
+}
+
+
+impl AtimeWarning {
+    pub fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        if matches.has(&flags::WARN_ATIME)? {
+            Ok(Self::CheckAndWarn)
+        }
+        else {
+            Ok(Self::Off)
+        }
+    }
 }
 
 
@@ -216,23 +431,41 @@ impl GitIgnore {
 }
 
 
+impl IgnoreFiles {
+    pub fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        if matches.has(&flags::RESPECT_IGNORE_FILES)? {
+            Ok(Self::Respect(IgnoreFileCache::default()))
+        }
+        else {
+            Ok(Self::Off)
+        }
+    }
+}
+
+
 #[cfg(test)]
 mod test {
     use super::*;
     use std::ffi::OsString;
     use crate::options::flags;
     use crate::options::parser::Flag;
+    use crate::fs::test::TempDir;
 
     macro_rules! test {
         ($name:ident: $type:ident <- $inputs:expr; $stricts:expr => $result:expr) => {
+            test!($name: $type::deduce <- $inputs; $stricts => $result);
+        };
+
+        ($name:ident: $type:ident :: $func:ident <- $inputs:expr; $stricts:expr => $result:expr) => {
             #[test]
             fn $name() {
                 use crate::options::parser::Arg;
                 use crate::options::test::parse_for_test;
                 use crate::options::test::Strictnesses::*;
 
-                static TEST_ARGS: &[&Arg] = &[ &flags::SORT, &flags::ALL, &flags::TREE, &flags::IGNORE_GLOB, &flags::GIT_IGNORE ];
-                for result in parse_for_test($inputs.as_ref(), TEST_ARGS, $stricts, |mf| $type::deduce(mf)) {
+                static TEST_ARGS: &[&Arg] = &[ &flags::SORT, &flags::ALL, &flags::TREE, &flags::IGNORE_GLOB, &flags::GIT_IGNORE, &flags::COMPOUND_EXTENSIONS, &flags::SORT2, &flags::SORT_ORDER,
+                                               &flags::IGNORE_MATCH_PATH, &flags::NEWER_THAN, &flags::OLDER_THAN, &flags::MAX_ENTRIES ];
+                for result in parse_for_test($inputs.as_ref(), TEST_ARGS, $stricts, |mf| $type::$func(mf)) {
                     assert_eq!(result, $result);
                 }
             }
@@ -251,6 +484,7 @@ mod test {
         test!(one_short:     SortField <- ["-saccessed"];      Both => Ok(SortField::AccessedDate));
         test!(lowercase:     SortField <- ["--sort", "name"];  Both => Ok(SortField::Name(SortCase::AaBbCc)));
         test!(uppercase:     SortField <- ["--sort", "Name"];  Both => Ok(SortField::Name(SortCase::ABCabc)));
+        test!(dictionary:    SortField <- ["--sort=dictionary"];  Both => Ok(SortField::Name(SortCase::Dictionary)));
         test!(old:           SortField <- ["--sort", "new"];   Both => Ok(SortField::ModifiedDate));
         test!(oldest:        SortField <- ["--sort=newest"];   Both => Ok(SortField::ModifiedDate));
         test!(new:           SortField <- ["--sort", "old"];   Both => Ok(SortField::ModifiedAge));
@@ -260,6 +494,15 @@ mod test {
         test!(mix_hidden_lowercase:     SortField <- ["--sort", ".name"];  Both => Ok(SortField::NameMixHidden(SortCase::AaBbCc)));
         test!(mix_hidden_uppercase:     SortField <- ["--sort", ".Name"];  Both => Ok(SortField::NameMixHidden(SortCase::ABCabc)));
 
+        test!(numeric:       SortField <- ["--sort=numeric"];  Both => Ok(SortField::Numeric));
+        test!(child_count:   SortField <- ["--sort=child-count"];  Both => Ok(SortField::ChildCount));
+
+        // Compound extensions
+        test!(compound:      SortField <- ["--sort=ext", "--compound-extensions"];  Both => Ok(SortField::CompoundExtension(SortCase::AaBbCc)));
+        test!(compound_2:    SortField <- ["--compound-extensions", "-sExt"];       Both => Ok(SortField::CompoundExtension(SortCase::ABCabc)));
+        test!(compound_3:    SortField <- ["--compound-extensions"];                Both => Ok(SortField::default()));
+        test!(compound_4:    SortField <- ["--compound-extensions", "--sort=size"];  Both => Ok(SortField::Size));
+
         // Errors
         test!(error:         SortField <- ["--sort=colour"];   Both => Err(OptionsError::BadArgument(&flags::SORT, OsString::from("colour"))));
 
@@ -271,6 +514,32 @@ mod test {
     }
 
 
+    mod secondary_sorts {
+        use super::*;
+
+        test!(empty:         SortField::deduce_secondary <- [];                                Both => Ok(None));
+        test!(size_name:     SortField::deduce_secondary <- ["--sort=size", "--sort2=name"];   Both => Ok(Some(SortField::Name(SortCase::AaBbCc))));
+        #[cfg(unix)]
+        test!(size_inode:    SortField::deduce_secondary <- ["-ssize", "--sort2", "inode"];    Both => Ok(Some(SortField::FileInode)));
+        test!(type_size:     SortField::deduce_secondary <- ["--sort=type", "--sort2=size"];   Both => Ok(Some(SortField::Size)));
+
+        // Errors
+        test!(no_primary:    SortField::deduce_secondary <- ["--sort2=name"];                  Both => Err(OptionsError::Useless(&flags::SORT2, false, &flags::SORT)));
+        test!(bad_word:      SortField::deduce_secondary <- ["--sort=size", "--sort2=colour"]; Both => Err(OptionsError::BadArgument(&flags::SORT2, OsString::from("colour"))));
+    }
+
+
+    mod sort_orders {
+        use super::*;
+
+        test!(empty:    SortOrder <- [];                       Both => Ok(SortOrder::Natural));
+        test!(natural:  SortOrder <- ["--sort-order=natural"]; Both => Ok(SortOrder::Natural));
+        test!(byte:     SortOrder <- ["--sort-order", "byte"]; Both => Ok(SortOrder::Byte));
+        test!(error:    SortOrder <- ["--sort-order=ascii"];   Both => Err(OptionsError::BadArgument(&flags::SORT_ORDER, OsString::from("ascii"))));
+        test!(last:     SortOrder <- ["--sort-order=byte", "--sort-order=natural"];  Last => Ok(SortOrder::Natural));
+    }
+
+
     mod dot_filters {
         use super::*;
 
@@ -311,6 +580,55 @@ mod test {
         test!(overridden_2: IgnorePatterns <- ["-I", "*.OGG", "-I*.MP3"];          Last => Ok(IgnorePatterns::from_iter(vec![ pat("*.MP3") ])));
         test!(overridden_3: IgnorePatterns <- ["-I=*.ogg",    "-I", "*.mp3"];  Complain => Err(OptionsError::Duplicate(Flag::Short(b'I'), Flag::Short(b'I'))));
         test!(overridden_4: IgnorePatterns <- ["-I", "*.OGG", "-I*.MP3"];      Complain => Err(OptionsError::Duplicate(Flag::Short(b'I'), Flag::Short(b'I'))));
+
+        // Matching paths
+        fn path_pats(strings: Vec<&'static str>) -> IgnorePatterns {
+            let mut pats = strings.into_iter().map(pat).collect::<IgnorePatterns>();
+            pats.match_path = true;
+            pats
+        }
+
+        test!(paths:        IgnorePatterns <- ["-I", "src/*.rs", "--ignore-match-path"];  Both => Ok(path_pats(vec![ "src/*.rs" ])));
+        test!(paths_alone:  IgnorePatterns <- ["--ignore-match-path"];                    Last => Ok(IgnorePatterns::empty()));
+        test!(paths_alone_2: IgnorePatterns <- ["--ignore-match-path"];               Complain => Err(OptionsError::Useless(&flags::IGNORE_MATCH_PATH, false, &flags::IGNORE_GLOB)));
+
+        // The global ignore file
+        #[test]
+        fn global_ignore_file() {
+            use crate::options::parser::Arg;
+            use crate::options::test::parse_for_test;
+            use crate::options::test::Strictnesses::*;
+
+            static TEST_ARGS: &[&Arg] = &[ &flags::IGNORE_GLOB, &flags::NO_GLOBAL_IGNORE ];
+
+            let config = TempDir::new("global-ignore");
+            std::fs::create_dir_all(config.join("exa")).unwrap();
+            std::fs::write(config.join("exa").join("ignore"), "# backups\n*.bak\n\n").unwrap();
+            let vars = Some(config.clone().into_os_string());
+
+            let ignored = |inputs: &[&str]| parse_for_test(inputs, TEST_ARGS, Both, |mf| FileFilter::deduce(mf, &vars).map(|f| f.ignore_patterns));
+
+            for result in ignored(&[]) {
+                assert_eq!(result, Ok(IgnorePatterns::from_iter(vec![ pat("*.bak") ])));
+            }
+
+            for result in ignored(&["-I", "*.tmp"]) {
+                assert_eq!(result, Ok(IgnorePatterns::from_iter(vec![ pat("*.tmp"), pat("*.bak") ])));
+            }
+
+            for result in ignored(&["--no-global-ignore"]) {
+                assert_eq!(result, Ok(IgnorePatterns::empty()));
+            }
+        }
+    }
+
+
+    mod max_entries {
+        use super::*;
+
+        test!(none:        FileFilter::deduce_max_entries <- [];                    Both => Ok(None));
+        test!(two:         FileFilter::deduce_max_entries <- ["--max-entries=2"];   Both => Ok(Some(2)));
+        test!(not_number:  FileFilter::deduce_max_entries <- ["--max-entries=lots"]; Both => Err(OptionsError::FailedParse("lots".to_string(), NumberSource::Arg(&flags::MAX_ENTRIES), "lots".parse::<usize>().unwrap_err())));
     }
 
 
@@ -320,4 +638,26 @@ mod test {
         test!(off:  GitIgnore <- [];                Both => Ok(GitIgnore::Off));
         test!(on:   GitIgnore <- ["--git-ignore"];  Both => Ok(GitIgnore::CheckAndIgnore));
     }
+
+
+    mod modified_windows {
+        use super::*;
+        use std::time::Duration;
+
+        const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+        fn window(newer_than: Option<Duration>, older_than: Option<Duration>) -> ModifiedWindow {
+            ModifiedWindow { newer_than, older_than }
+        }
+
+        test!(empty:       ModifiedWindow <- [];                                     Both => Ok(window(None, None)));
+        test!(days:        ModifiedWindow <- ["--newer-than=7d"];                    Both => Ok(window(Some(7 * DAY), None)));
+        test!(hours:       ModifiedWindow <- ["--older-than", "2h"];                 Both => Ok(window(None, Some(Duration::from_secs(2 * 60 * 60)))));
+        test!(weeks:       ModifiedWindow <- ["--newer-than=2w"];                    Both => Ok(window(Some(14 * DAY), None)));
+        test!(both:        ModifiedWindow <- ["--newer-than=7d", "--older-than=1d"]; Both => Ok(window(Some(7 * DAY), Some(DAY))));
+
+        test!(no_unit:     ModifiedWindow <- ["--newer-than=7"];                     Both => Err(OptionsError::BadArgument(&flags::NEWER_THAN, OsString::from("7"))));
+        test!(bad_unit:    ModifiedWindow <- ["--newer-than=7y"];                    Both => Err(OptionsError::BadArgument(&flags::NEWER_THAN, OsString::from("7y"))));
+        test!(no_number:   ModifiedWindow <- ["--older-than=d"];                     Both => Err(OptionsError::FailedParse("d".to_string(), NumberSource::Arg(&flags::OLDER_THAN), "".parse::<u64>().unwrap_err())));
+    }
 }
@@ -4,46 +4,114 @@ use crate::options::parser::{Arg, Args, TakesValue, Values};
 // exa options
 pub static VERSION: Arg = Arg { short: Some(b'v'), long: "version",  takes_value: TakesValue::Forbidden };
 pub static HELP:    Arg = Arg { short: Some(b'?'), long: "help",     takes_value: TakesValue::Forbidden };
+pub static STRICT:  Arg = Arg { short: None,       long: "strict",   takes_value: TakesValue::Forbidden };
 
 // display options
 pub static ONE_LINE: Arg = Arg { short: Some(b'1'), long: "oneline",  takes_value: TakesValue::Forbidden };
 pub static LONG:     Arg = Arg { short: Some(b'l'), long: "long",     takes_value: TakesValue::Forbidden };
 pub static GRID:     Arg = Arg { short: Some(b'G'), long: "grid",     takes_value: TakesValue::Forbidden };
 pub static ACROSS:   Arg = Arg { short: Some(b'x'), long: "across",   takes_value: TakesValue::Forbidden };
+pub static DOWN:     Arg = Arg { short: None,       long: "down",     takes_value: TakesValue::Forbidden };
+pub static STRICT_GRID: Arg = Arg { short: None,    long: "strict-grid", takes_value: TakesValue::Forbidden };
+pub static WRAP_NAMES: Arg = Arg { short: None,     long: "wrap-names", takes_value: TakesValue::Forbidden };
 pub static RECURSE:  Arg = Arg { short: Some(b'R'), long: "recurse",  takes_value: TakesValue::Forbidden };
 pub static TREE:     Arg = Arg { short: Some(b'T'), long: "tree",     takes_value: TakesValue::Forbidden };
+pub static SEPARATE_TREES: Arg = Arg { short: None, long: "separate-trees", takes_value: TakesValue::Forbidden };
+pub static STAT:     Arg = Arg { short: None,       long: "stat",     takes_value: TakesValue::Forbidden };
+pub static COMPLETE_DIRS: Arg = Arg { short: None,  long: "complete-dirs", takes_value: TakesValue::Forbidden };
+pub static ONELINER_SUMMARY: Arg = Arg { short: None, long: "oneliner-summary", takes_value: TakesValue::Forbidden };
 pub static CLASSIFY: Arg = Arg { short: Some(b'F'), long: "classify", takes_value: TakesValue::Forbidden };
+pub static CLASSIFY_CHARS: Arg = Arg { short: None, long: "classify-chars", takes_value: TakesValue::Necessary(None) };
+pub static MARK_EMPTY: Arg = Arg { short: None,     long: "mark-empty", takes_value: TakesValue::Forbidden };
+pub static MARK_UNREADABLE: Arg = Arg { short: None, long: "mark-unreadable", takes_value: TakesValue::Forbidden };
+pub static SUMMARY:  Arg = Arg { short: None,       long: "summary",  takes_value: TakesValue::Forbidden };
+pub static FILESYSTEM_INFO: Arg = Arg { short: None, long: "filesystem-info", takes_value: TakesValue::Forbidden };
+pub static SYMLINK_TARGET_COLOR: Arg = Arg { short: None, long: "symlink-target-color", takes_value: TakesValue::Forbidden };
+pub static SHORT_SYMLINK_TARGETS: Arg = Arg { short: None, long: "short-symlink-targets", takes_value: TakesValue::Forbidden };
+pub static RELATIVE_TO: Arg = Arg { short: None,    long: "relative-to", takes_value: TakesValue::Optional(None) };
+pub static QUOTE:    Arg = Arg { short: None,       long: "quote",    takes_value: TakesValue::Forbidden };
+pub static BIDI_ISOLATE: Arg = Arg { short: None, long: "bidi-isolate", takes_value: TakesValue::Forbidden };
+pub static OUTPUT:   Arg = Arg { short: None,       long: "output",   takes_value: TakesValue::Necessary(None) };
 
 pub static COLOR:  Arg = Arg { short: None, long: "color",  takes_value: TakesValue::Necessary(Some(COLOURS)) };
 pub static COLOUR: Arg = Arg { short: None, long: "colour", takes_value: TakesValue::Necessary(Some(COLOURS)) };
 const COLOURS: &[&str] = &["always", "auto", "never"];
 
+pub static PAGING: Arg = Arg { short: None, long: "paging", takes_value: TakesValue::Necessary(Some(PAGINGS)) };
+const PAGINGS: &[&str] = &["auto", "always", "never"];
+pub static WATCH: Arg = Arg { short: None, long: "watch", takes_value: TakesValue::Forbidden };
+
+pub static GROUP_BY: Arg = Arg { short: None, long: "group-by", takes_value: TakesValue::Necessary(Some(GROUP_BYS)) };
+const GROUP_BYS: &[&str] = &["extension", "none"];
+
 pub static COLOR_SCALE:  Arg = Arg { short: None, long: "color-scale",  takes_value: TakesValue::Forbidden };
 pub static COLOUR_SCALE: Arg = Arg { short: None, long: "colour-scale", takes_value: TakesValue::Forbidden };
 
+pub static COLOR_SYMLINK_PATH:  Arg = Arg { short: None, long: "color-symlink-path",  takes_value: TakesValue::Forbidden };
+pub static COLOUR_SYMLINK_PATH: Arg = Arg { short: None, long: "colour-symlink-path", takes_value: TakesValue::Forbidden };
+
+pub static COLOR_BY:  Arg = Arg { short: None, long: "color-by",  takes_value: TakesValue::Necessary(Some(COLOUR_BYS)) };
+pub static COLOUR_BY: Arg = Arg { short: None, long: "colour-by", takes_value: TakesValue::Necessary(Some(COLOUR_BYS)) };
+const COLOUR_BYS: &[&str] = &["extension", "content"];
+
+pub static COLOR_SCHEME:  Arg = Arg { short: None, long: "color-scheme",  takes_value: TakesValue::Necessary(Some(COLOUR_SCHEMES)) };
+pub static COLOUR_SCHEME: Arg = Arg { short: None, long: "colour-scheme", takes_value: TakesValue::Necessary(Some(COLOUR_SCHEMES)) };
+const COLOUR_SCHEMES: &[&str] = &["dark", "light", "auto"];
+
+// not listed in the help text, as it’s only for debugging themes
+pub static DEBUG_COLOURS: Arg = Arg { short: None, long: "debug-colours", takes_value: TakesValue::Forbidden };
+
 // filtering and sorting options
 pub static ALL:         Arg = Arg { short: Some(b'a'), long: "all",         takes_value: TakesValue::Forbidden };
 pub static LIST_DIRS:   Arg = Arg { short: Some(b'd'), long: "list-dirs",   takes_value: TakesValue::Forbidden };
 pub static LEVEL:       Arg = Arg { short: Some(b'L'), long: "level",       takes_value: TakesValue::Necessary(None) };
+pub static MAX_TOTAL:   Arg = Arg { short: None,       long: "max-total",   takes_value: TakesValue::Necessary(None) };
+pub static MAX_ENTRIES: Arg = Arg { short: None,       long: "max-entries", takes_value: TakesValue::Necessary(None) };
+pub static DESCEND_ONLY: Arg = Arg { short: None,      long: "descend-only", takes_value: TakesValue::Necessary(None) };
+pub static MAX_WIDTH:   Arg = Arg { short: None,       long: "max-width",   takes_value: TakesValue::Necessary(None) };
 pub static REVERSE:     Arg = Arg { short: Some(b'r'), long: "reverse",     takes_value: TakesValue::Forbidden };
+pub static TYPE_STABLE: Arg = Arg { short: None,       long: "type-stable", takes_value: TakesValue::Forbidden };
+pub static WARN_ATIME:  Arg = Arg { short: None,       long: "warn-atime",  takes_value: TakesValue::Forbidden };
 pub static SORT:        Arg = Arg { short: Some(b's'), long: "sort",        takes_value: TakesValue::Necessary(Some(SORTS)) };
+pub static SORT2:       Arg = Arg { short: None,       long: "sort2",       takes_value: TakesValue::Necessary(Some(SORTS)) };
+pub static SORT_ORDER:  Arg = Arg { short: None,       long: "sort-order",  takes_value: TakesValue::Necessary(Some(SORT_ORDERS)) };
 pub static IGNORE_GLOB: Arg = Arg { short: Some(b'I'), long: "ignore-glob", takes_value: TakesValue::Necessary(None) };
+pub static IGNORE_MATCH_PATH: Arg = Arg { short: None, long: "ignore-match-path", takes_value: TakesValue::Forbidden };
 pub static GIT_IGNORE:  Arg = Arg { short: None, long: "git-ignore",           takes_value: TakesValue::Forbidden };
+pub static RESPECT_IGNORE_FILES: Arg = Arg { short: None, long: "respect-ignore-files", takes_value: TakesValue::Forbidden };
+pub static NO_GLOBAL_IGNORE: Arg = Arg { short: None, long: "no-global-ignore", takes_value: TakesValue::Forbidden };
 pub static DIRS_FIRST:  Arg = Arg { short: None, long: "group-directories-first",  takes_value: TakesValue::Forbidden };
+pub static DOTFILES_LAST: Arg = Arg { short: None, long: "dotfiles-last",          takes_value: TakesValue::Forbidden };
 pub static ONLY_DIRS:   Arg = Arg { short: Some(b'D'), long: "only-dirs", takes_value: TakesValue::Forbidden };
-const SORTS: Values = &[ "name", "Name", "size", "extension",
+pub static NEWER_THAN:  Arg = Arg { short: None,       long: "newer-than", takes_value: TakesValue::Necessary(None) };
+pub static OLDER_THAN:  Arg = Arg { short: None,       long: "older-than", takes_value: TakesValue::Necessary(None) };
+pub static COMPOUND_EXTENSIONS: Arg = Arg { short: None, long: "compound-extensions", takes_value: TakesValue::Forbidden };
+const SORT_ORDERS: Values = &[ "natural", "byte" ];
+const SORTS: Values = &[ "name", "Name", "dictionary", "size", "extension",
                          "Extension", "modified", "changed", "accessed",
-                         "created", "inode", "type", "none" ];
+                         "created", "inode", "type", "numeric", "child-count",
+                         "none" ];
 
 // display options
 pub static BINARY:     Arg = Arg { short: Some(b'b'), long: "binary",     takes_value: TakesValue::Forbidden };
 pub static BYTES:      Arg = Arg { short: Some(b'B'), long: "bytes",      takes_value: TakesValue::Forbidden };
+pub static SIZE_BOTH:  Arg = Arg { short: None,       long: "size-both",  takes_value: TakesValue::Forbidden };
+pub static HUMAN_ABOVE: Arg = Arg { short: None,      long: "human-above", takes_value: TakesValue::Necessary(None) };
 pub static GROUP:      Arg = Arg { short: Some(b'g'), long: "group",      takes_value: TakesValue::Forbidden };
+pub static MERGE_OWNER: Arg = Arg { short: None,      long: "merge-owner", takes_value: TakesValue::Forbidden };
 pub static NUMERIC:    Arg = Arg { short: Some(b'n'), long: "numeric",    takes_value: TakesValue::Forbidden };
+pub static TRUNCATE_NAMES: Arg = Arg { short: None,   long: "truncate-names", takes_value: TakesValue::Necessary(None) };
 pub static HEADER:     Arg = Arg { short: Some(b'h'), long: "header",     takes_value: TakesValue::Forbidden };
+pub static SUMMARY_ROW: Arg = Arg { short: None,      long: "summary-row", takes_value: TakesValue::Forbidden };
+pub static LINK_TARGET_COLUMN: Arg = Arg { short: None, long: "link-target-column", takes_value: TakesValue::Forbidden };
+pub static BORDERS:    Arg = Arg { short: None,       long: "borders",    takes_value: TakesValue::Forbidden };
+pub static NO_LEAD_PAD: Arg = Arg { short: None,      long: "no-lead-pad", takes_value: TakesValue::Forbidden };
 pub static ICONS:      Arg = Arg { short: None,       long: "icons",      takes_value: TakesValue::Forbidden };
 pub static INODE:      Arg = Arg { short: Some(b'i'), long: "inode",      takes_value: TakesValue::Forbidden };
+pub static DEVICE_INODE: Arg = Arg { short: None,     long: "device-inode", takes_value: TakesValue::Forbidden };
+pub static FILE_VERSION: Arg = Arg { short: None,     long: "file-version", takes_value: TakesValue::Forbidden };
 pub static LINKS:      Arg = Arg { short: Some(b'H'), long: "links",      takes_value: TakesValue::Forbidden };
+pub static REVERSE_LINKS: Arg = Arg { short: None,    long: "reverse-links", takes_value: TakesValue::Forbidden };
 pub static MODIFIED:   Arg = Arg { short: Some(b'm'), long: "modified",   takes_value: TakesValue::Forbidden };
 pub static CHANGED:    Arg = Arg { short: None,       long: "changed",    takes_value: TakesValue::Forbidden };
 pub static BLOCKS:     Arg = Arg { short: Some(b'S'), long: "blocks",     takes_value: TakesValue::Forbidden };
@@ -52,7 +120,18 @@ pub static ACCESSED:   Arg = Arg { short: Some(b'u'), long: "accessed",   takes_
 pub static CREATED:    Arg = Arg { short: Some(b'U'), long: "created",    takes_value: TakesValue::Forbidden };
 pub static TIME_STYLE: Arg = Arg { short: None,       long: "time-style", takes_value: TakesValue::Necessary(Some(TIME_STYLES)) };
 const TIMES: Values = &["modified", "changed", "accessed", "created"];
-const TIME_STYLES: Values = &["default", "long-iso", "full-iso", "iso"];
+const TIME_STYLES: Values = &["default", "long-iso", "full-iso", "iso", "iso-seconds"];
+pub static MONTH_NAMES: Arg = Arg { short: None,      long: "month-names", takes_value: TakesValue::Necessary(None) };
+pub static NANOSECONDS: Arg = Arg { short: None, long: "nanoseconds", takes_value: TakesValue::Forbidden };
+pub static AGE:        Arg = Arg { short: None,       long: "age",         takes_value: TakesValue::Forbidden };
+pub static SIZE_PERCENT: Arg = Arg { short: None,     long: "size-percent", takes_value: TakesValue::Forbidden };
+pub static COLOR_AGE:  Arg = Arg { short: None,       long: "color-age",  takes_value: TakesValue::Forbidden };
+pub static COLOUR_AGE: Arg = Arg { short: None,       long: "colour-age", takes_value: TakesValue::Forbidden };
+pub static SIZE_COLOR:  Arg = Arg { short: None,      long: "size-color",  takes_value: TakesValue::Necessary(Some(SIZE_COLOURS)) };
+pub static SIZE_COLOUR: Arg = Arg { short: None,      long: "size-colour", takes_value: TakesValue::Necessary(Some(SIZE_COLOURS)) };
+const SIZE_COLOURS: &[&str] = &["magnitude", "by-type"];
+pub static SIZE_BAR:   Arg = Arg { short: None,       long: "size-bar",   takes_value: TakesValue::Forbidden };
+pub static MARK_SPARSE: Arg = Arg { short: None,      long: "mark-sparse", takes_value: TakesValue::Forbidden };
 
 // suppressing columns
 pub static NO_PERMISSIONS: Arg = Arg { short: None, long: "no-permissions", takes_value: TakesValue::Forbidden };
@@ -63,22 +142,32 @@ pub static NO_ICONS: Arg = Arg { short: None, long: "no-icons", takes_value: Tak
 
 // optional feature options
 pub static GIT:       Arg = Arg { short: None,       long: "git",               takes_value: TakesValue::Forbidden };
+pub static GIT_COL_WIDTH: Arg = Arg { short: None,   long: "git-col-width",     takes_value: TakesValue::Necessary(None) };
+pub static GIT_MAX_FILES: Arg = Arg { short: None,   long: "git-max-files",     takes_value: TakesValue::Necessary(None) };
 pub static EXTENDED:  Arg = Arg { short: Some(b'@'), long: "extended",          takes_value: TakesValue::Forbidden };
 pub static OCTAL:     Arg = Arg { short: None,       long: "octal-permissions", takes_value: TakesValue::Forbidden };
+pub static EFFECTIVE_PERMISSIONS: Arg = Arg { short: None, long: "effective-permissions", takes_value: TakesValue::Forbidden };
+pub static CHECKSUM:  Arg = Arg { short: None,       long: "checksum",          takes_value: TakesValue::Necessary(Some(CHECKSUMS)) };
+const CHECKSUMS: Values = &["crc32", "md5"];
+pub static ENCODING:  Arg = Arg { short: None,       long: "encoding",          takes_value: TakesValue::Forbidden };
+pub static VOLUME:    Arg = Arg { short: None,       long: "volume",            takes_value: TakesValue::Forbidden };
+pub static TYPE_COLUMN: Arg = Arg { short: None,     long: "type-column",       takes_value: TakesValue::Forbidden };
+pub static MOUNT_INFO: Arg = Arg { short: None,      long: "mount-info",        takes_value: TakesValue::Forbidden };
+pub static SECURITY_SUMMARY: Arg = Arg { short: None, long: "security-summary", takes_value: TakesValue::Forbidden };
 
 
 pub static ALL_ARGS: Args = Args(&[
-    &VERSION, &HELP,
+    &VERSION, &HELP, &STRICT,
 
-    &ONE_LINE, &LONG, &GRID, &ACROSS, &RECURSE, &TREE, &CLASSIFY,
-    &COLOR, &COLOUR, &COLOR_SCALE, &COLOUR_SCALE,
+    &ONE_LINE, &LONG, &GRID, &ACROSS, &DOWN, &STRICT_GRID, &WRAP_NAMES, &RECURSE, &TREE, &SEPARATE_TREES, &STAT, &COMPLETE_DIRS, &ONELINER_SUMMARY, &CLASSIFY, &CLASSIFY_CHARS, &MARK_EMPTY, &MARK_UNREADABLE, &SUMMARY, &FILESYSTEM_INFO, &SYMLINK_TARGET_COLOR, &SHORT_SYMLINK_TARGETS, &RELATIVE_TO, &QUOTE, &BIDI_ISOLATE, &OUTPUT,
+    &COLOR, &COLOUR, &COLOR_SCALE, &COLOUR_SCALE, &COLOR_SYMLINK_PATH, &COLOUR_SYMLINK_PATH, &COLOR_BY, &COLOUR_BY, &COLOR_SCHEME, &COLOUR_SCHEME, &DEBUG_COLOURS, &PAGING, &WATCH, &GROUP_BY,
 
-    &ALL, &LIST_DIRS, &LEVEL, &REVERSE, &SORT, &DIRS_FIRST,
-    &IGNORE_GLOB, &GIT_IGNORE, &ONLY_DIRS,
+    &ALL, &LIST_DIRS, &LEVEL, &MAX_TOTAL, &MAX_ENTRIES, &DESCEND_ONLY, &REVERSE, &TYPE_STABLE, &WARN_ATIME, &SORT, &SORT2, &SORT_ORDER, &DIRS_FIRST, &DOTFILES_LAST,
+    &IGNORE_GLOB, &IGNORE_MATCH_PATH, &GIT_IGNORE, &RESPECT_IGNORE_FILES, &NO_GLOBAL_IGNORE, &ONLY_DIRS, &NEWER_THAN, &OLDER_THAN, &COMPOUND_EXTENSIONS,
 
-    &BINARY, &BYTES, &GROUP, &NUMERIC, &HEADER, &ICONS, &INODE, &LINKS, &MODIFIED, &CHANGED,
-    &BLOCKS, &TIME, &ACCESSED, &CREATED, &TIME_STYLE,
+    &BINARY, &BYTES, &SIZE_BOTH, &HUMAN_ABOVE, &GROUP, &MERGE_OWNER, &NUMERIC, &TRUNCATE_NAMES, &HEADER, &SUMMARY_ROW, &LINK_TARGET_COLUMN, &BORDERS, &NO_LEAD_PAD, &ICONS, &INODE, &DEVICE_INODE, &FILE_VERSION, &LINKS, &REVERSE_LINKS, &MODIFIED, &CHANGED,
+    &BLOCKS, &TIME, &ACCESSED, &CREATED, &TIME_STYLE, &MONTH_NAMES, &NANOSECONDS, &AGE, &COLOR_AGE, &COLOUR_AGE, &SIZE_COLOR, &SIZE_COLOUR, &SIZE_PERCENT, &SIZE_BAR, &MARK_SPARSE,
     &NO_PERMISSIONS, &NO_FILESIZE, &NO_USER, &NO_TIME, &NO_ICONS,
 
-    &GIT, &EXTENDED, &OCTAL
+    &GIT, &GIT_COL_WIDTH, &GIT_MAX_FILES, &EXTENDED, &OCTAL, &EFFECTIVE_PERMISSIONS, &CHECKSUM, &ENCODING, &VOLUME, &TYPE_COLUMN, &MOUNT_INFO, &SECURITY_SUMMARY
 ]);
@@ -11,17 +11,42 @@ static USAGE_PART1: &str = "Usage:
 META OPTIONS
   -?, --help         show list of command-line options
   -v, --version      show version of exa
+  --strict           complain about repeated or useless options
 
 DISPLAY OPTIONS
   -1, --oneline      display one entry per line
   -l, --long         display extended file metadata as a table
   -G, --grid         display entries as a grid (default)
   -x, --across       sort the grid across, rather than downwards
+  --down             sort the grid downwards, overriding --across
+  --strict-grid      keep to a one-column grid when names are too wide
+  --wrap-names       break names too wide for the terminal over several lines
   -R, --recurse      recurse into directories
   -T, --tree         recurse into directories as a tree
+  --separate-trees   list each directory argument as its own tree
+  --stat             list every detail of a single file, one per line
+  --complete-dirs    list directory names separated by NULs, for shell completion
+  --oneliner-summary print one line of totals, such as '12 files, 3 dirs, 4.2M'
   -F, --classify     display type indicator by file names
+  --classify-chars KINDS  only show these indicators (dir, exec, link, pipe, socket)
+  --mark-empty       mark empty files and directories
+  --mark-unreadable  mark files you don't have permission to read
+  --summary          show each directory's entry count and total size
+  --filesystem-info  show the size and free space of each directory's filesystem
+  --symlink-target-color  colour symlinks like the files they point to
+  --short-symlink-targets  show just the names of symlink targets, not their paths
+  --relative-to[=DIR]  show each file's path relative to a directory (default: .)
+  --quote            quote file names so they can be pasted into a shell
+  --bidi-isolate     keep right-to-left names from reordering the columns
+  --output=PATH      write the listing to a file instead of the terminal
   --colo[u]r=WHEN    when to use terminal colours (always, auto, never)
   --colo[u]r-scale   highlight levels of file sizes distinctly
+  --colo[u]r-symlink-path  colour the directories in symlink targets' paths
+  --colo[u]r-by=WHAT  what to colour files by (extension, content)
+  --colo[u]r-scheme=SCHEME  colours for the terminal's background (dark, light, auto)
+  --paging=WHEN      when to send output through $PAGER (auto, always, never)
+  --watch            list the files again whenever they change
+  --group-by=FIELD   list files in sections (extension, none)
   --icons            display icons
   --no-icons         don't display icons (always overrides --icons)
 
@@ -29,41 +54,85 @@ FILTERING AND SORTING OPTIONS
   -a, --all                  show hidden and 'dot' files
   -d, --list-dirs            list directories as files; don't list their contents
   -L, --level DEPTH          limit the depth of recursion
+  --max-total COUNT          stop recursing once this many entries are listed
+  --max-entries COUNT        list at most this many entries from each directory
+  --descend-only GLOB        only recurse into directories whose names match
+  --max-width COUNT          only recurse into this many directories in each one
   -r, --reverse              reverse the sort order
+  --type-stable              keep types in order when reversing a sort by type
+  --warn-atime               warn when sorting by access times that look unrecorded
   -s, --sort SORT_FIELD      which field to sort by
+  --sort2 SORT_FIELD         which field to sort by when --sort is a tie
+  --sort-order ORDER         how to compare file names (natural, byte)
+  --compound-extensions      sort and colour by 'tar.gz' rather than 'gz'
   --group-directories-first  list directories before other files
+  --dotfiles-last            list dotfiles after other files
   -D, --only-dirs            list only directories
-  -I, --ignore-glob GLOBS    glob patterns (pipe-separated) of files to ignore";
+  --newer-than DURATION      only list files modified within this long, such as 7d
+  --older-than DURATION      only list files modified longer ago than this, such as 2h
+  -I, --ignore-glob GLOBS    glob patterns (pipe-separated) of files to ignore
+  --ignore-match-path        match ignore globs against paths, not just names
+  --respect-ignore-files     ignore files mentioned in '.ignore' and '.fdignore'
+  --no-global-ignore         don't ignore the globs in the user's exa/ignore file";
 
   static USAGE_PART2: &str = "  \
-  Valid sort fields:         name, Name, extension, Extension, size, type,
-                             modified, accessed, created, inode, and none.
+  Valid sort fields:         name, Name, dictionary, extension, Extension,
+                             size, type, modified, accessed, created, inode,
+                             numeric, child-count, and none.
                              date, time, old, and new all refer to modified.
 
 LONG VIEW OPTIONS
   -b, --binary         list file sizes with binary prefixes
   -B, --bytes          list file sizes in bytes, without any prefixes
+  --size-both          list file sizes with binary and decimal prefixes
+  --human-above=SIZE   list sizes smaller than SIZE in bytes, without prefixes
+  --size-color=WHAT    what to colour sizes by (magnitude, by-type)
   -g, --group          list each file's group
+  --merge-owner        show user and group in one column, once if they match
   -h, --header         add a header row to each column
+  --summary-row        add a row with the total size and links under the table
+  --link-target-column list symlink targets in their own column after the names
+  --borders            draw lines between the columns, and under the header
+  --no-lead-pad        left-align the first column so lines don't start with spaces
   -H, --links          list each file's number of hard links
+  --reverse-links      list how many of the listed symlinks point at each file
   -i, --inode          list each file's inode number
+  --device-inode       list each file's device and inode number, as dev:inode
+  --file-version       list each file's generation number, if the filesystem has one
   -m, --modified       use the modified timestamp field
   -n, --numeric        list numeric user and group IDs
+  --truncate-names N   cut user and group names down to N characters
   -S, --blocks         show number of file system blocks
   -t, --time FIELD     which timestamp field to list (modified, accessed, created)
   -u, --accessed       use the accessed timestamp field
   -U, --created        use the created timestamp field
   --changed            use the changed timestamp field
-  --time-style         how to format timestamps (default, iso, long-iso, full-iso)
+  --time-style         how to format timestamps (default, iso, long-iso, iso-seconds, full-iso)
+  --month-names LOCALE which locale's month names the default time style uses
+  --nanoseconds        show the seconds and nanoseconds in timestamps
+  --age                also show how long ago the timestamp was, such as 3d
+  --colo[u]r-age       colour timestamps by whether they're from today, this week, or older
+  --size-percent       list each file's share of the total size listed
+  --size-bar           draw a bar of each file's size next to the largest
+  --mark-sparse        mark the sizes of sparse files with a '~'
   --no-permissions     suppress the permissions field
   --octal-permissions  list each file's permission in octal format
+  --effective-permissions  list what you can do with each file, such as r-x
+  --type-column        list each file's type as a word, such as dir or link
   --no-filesize        suppress the filesize field
   --no-user            suppress the user field
-  --no-time            suppress the time field";
+  --no-time            suppress the time field
+  --checksum ALGO      list a hash of each file's contents (crc32, md5)
+  --encoding           list a guess at each file's text encoding
+  --volume             list the name of the mount each file is on
+  --mount-info         mark bind mounts and their propagation (Linux)";
 
 static GIT_FILTER_HELP: &str = "  --git-ignore               ignore files mentioned in '.gitignore'";
-static GIT_VIEW_HELP:   &str = "  --git                list each file's Git status, if tracked or ignored";
-static EXTENDED_HELP:   &str = "  -@, --extended       list each file's extended attributes and sizes";
+static GIT_VIEW_HELP:   &str = "  --git                list each file's Git status, if tracked or ignored
+  --git-col-width=N    fix the width of the Git status column
+  --git-max-files=N    skip Git statuses in repositories with more files than this";
+static EXTENDED_HELP:   &str = "  -@, --extended       list each file's extended attributes and sizes
+  --security-summary   list each file's attribute count and ACL, as in 'x2+'";
 
 
 /// All the information needed to display the help text, which depends
@@ -73,8 +73,10 @@ use std::ffi::OsStr;
 
 use crate::fs::dir_action::DirAction;
 use crate::fs::filter::{FileFilter, GitIgnore};
-use crate::output::{View, Mode, details, grid_details};
-use crate::theme::Options as ThemeOptions;
+use crate::output::{View, Mode, DirHeadings, details, grid_details};
+use crate::output::groups::GroupBy;
+use crate::output::pager::Paging;
+use crate::theme::{Options as ThemeOptions, UseColours};
 
 mod dir_action;
 mod file_name;
@@ -119,6 +121,10 @@ pub struct Options {
 
     /// The options to make up the styles of the UI and file names.
     pub theme: ThemeOptions,
+
+    /// The most files a Git repository can have before exa stops looking
+    /// up the statuses of the files in it, if there’s a limit.
+    pub git_max_files: Option<usize>,
 }
 
 impl Options {
@@ -139,11 +145,17 @@ impl Options {
             Some(_)                      => Strictness::ComplainAboutRedundantArguments,
         };
 
-        let Matches { flags, frees } = match flags::ALL_ARGS.parse(args, strictness) {
+        let Matches { mut flags, frees } = match flags::ALL_ARGS.parse(args, strictness) {
             Ok(m)    => m,
             Err(pe)  => return OptionsResult::InvalidOptions(OptionsError::Parse(pe)),
         };
 
+        // Strict mode can be asked for on the command line as well as in the
+        // environment. Counting it means giving it twice isn’t an error.
+        if flags.count(&flags::STRICT) > 0 {
+            flags.make_strict();
+        }
+
         if let Some(help) = HelpString::deduce(&flags) {
             return OptionsResult::Help(help);
         }
@@ -153,6 +165,9 @@ impl Options {
         }
 
         match Self::deduce(&flags, vars) {
+            Ok(options) if options.view.mode == Mode::Stat && frees.len() > 1 => {
+                OptionsResult::InvalidOptions(OptionsError::StatNeedsOneFile(frees.len()))
+            }
             Ok(options)  => OptionsResult::Ok(options, frees),
             Err(oe)      => OptionsResult::InvalidOptions(oe),
         }
@@ -183,18 +198,58 @@ impl Options {
             )));
         }
 
-        let view = View::deduce(matches, vars)?;
-        let dir_action = DirAction::deduce(matches, matches!(view.mode, Mode::Details(_)))?;
-        let filter = FileFilter::deduce(matches)?;
-        let theme = ThemeOptions::deduce(matches, vars)?;
+        let mut view = View::deduce(matches, vars)?;
+        // The stat view describes a directory itself, rather than its contents.
+        let mut dir_action = if view.mode == Mode::Stat { DirAction::AsFile }
+                                                   else { DirAction::deduce(matches, matches!(view.mode, Mode::Details(_)))? };
+        let mut filter = FileFilter::deduce(matches, vars)?;
+        let mut theme = ThemeOptions::deduce(matches, vars)?;
+
+        // `--complete-dirs` is a preset of other options, so that completion
+        // scripts get plain directory names no matter what else is set.
+        if view.mode == Mode::NulSeparated {
+            dir_action = DirAction::List;
+            filter.only_dirs = true;
+            theme.use_colours = UseColours::Never;
+            view.paging = Paging::Never;
+            view.headings = DirHeadings::default();
+            view.group_by = GroupBy::Nothing;
+        }
+
+        // So is `--oneliner-summary`, whose line gets read by whatever
+        // status bar is running exa, rather than by a person.
+        if view.mode == Mode::OnelinerSummary {
+            dir_action = DirAction::List;
+            theme.use_colours = UseColours::Never;
+            view.paging = Paging::Never;
+            view.headings = DirHeadings::default();
+            view.group_by = GroupBy::Nothing;
+        }
+
+        let git_max_files = Self::deduce_git_max_files(matches)?;
+
+        Ok(Self { dir_action, filter, view, theme, git_max_files })
+    }
+
+    /// Determines the most files to look up Git statuses for, from the
+    /// `--git-max-files` argument, which has to be a number if it’s given.
+    fn deduce_git_max_files(matches: &MatchedFlags<'_>) -> Result<Option<usize>, OptionsError> {
+        let value = match matches.get(&flags::GIT_MAX_FILES)? {
+            Some(v)  => v.to_string_lossy(),
+            None     => return Ok(None),
+        };
 
-        Ok(Self { dir_action, filter, view, theme })
+        match value.parse() {
+            Ok(n)   => Ok(Some(n)),
+            Err(e)  => Err(OptionsError::FailedParse(value.to_string(), NumberSource::Arg(&flags::GIT_MAX_FILES), e)),
+        }
     }
 }
 
 
 /// The result of the `Options::getopts` function.
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum OptionsResult<'args> {
 
     /// The options were parsed successfully.
@@ -251,3 +306,113 @@ pub mod test {
         result
     }
 }
+
+
+#[cfg(test)]
+mod stat_test {
+    use super::*;
+
+    #[test]
+    fn one_file() {
+        let args = vec![ OsStr::new("--stat"), OsStr::new("Cargo.toml") ];
+        let opts = Options::parse(args, &None);
+        assert!(matches!(opts, OptionsResult::Ok(Options { view: View { mode: Mode::Stat, .. }, dir_action: DirAction::AsFile, .. }, _)));
+    }
+
+    #[test]
+    fn two_files() {
+        let args = vec![ OsStr::new("--stat"), OsStr::new("Cargo.toml"), OsStr::new("README.md") ];
+        let opts = Options::parse(args, &None);
+        assert!(matches!(opts, OptionsResult::InvalidOptions(OptionsError::StatNeedsOneFile(2))));
+    }
+}
+
+
+#[cfg(test)]
+mod complete_dirs_test {
+    use super::*;
+
+    #[test]
+    fn preset() {
+        let args = vec![ OsStr::new("--complete-dirs"), OsStr::new("--colour=always"), OsStr::new("--tree") ];
+        let opts = Options::parse(args, &None);
+        assert!(matches!(opts, OptionsResult::Ok(Options {
+            view: View { mode: Mode::NulSeparated, paging: Paging::Never, headings: DirHeadings { summary: false, .. }, group_by: GroupBy::Nothing, .. },
+            dir_action: DirAction::List,
+            filter: FileFilter { only_dirs: true, .. },
+            theme: ThemeOptions { use_colours: UseColours::Never, .. },
+            ..
+        }, _)));
+    }
+}
+
+
+#[cfg(test)]
+mod strict_test {
+    use super::*;
+    use crate::options::parser::Flag;
+
+    #[test]
+    fn sort_twice() {
+        let args = vec![ OsStr::new("--sort=name"), OsStr::new("--sort=size") ];
+        let opts = Options::parse(args, &None);
+        assert!(matches!(opts, OptionsResult::Ok(..)));
+    }
+
+    #[test]
+    fn sort_twice_strictly() {
+        let args = vec![ OsStr::new("--strict"), OsStr::new("--sort=name"), OsStr::new("--sort=size") ];
+        let opts = Options::parse(args, &None);
+        assert!(matches!(opts, OptionsResult::InvalidOptions(OptionsError::Duplicate(Flag::Long("sort"), Flag::Long("sort")))));
+    }
+
+    #[test]
+    fn strict_twice() {
+        let args = vec![ OsStr::new("--strict"), OsStr::new("--strict") ];
+        let opts = Options::parse(args, &None);
+        assert!(matches!(opts, OptionsResult::Ok(..)));
+    }
+
+    #[test]
+    fn useless_flag_strictly() {
+        let args = vec![ OsStr::new("--strict"), OsStr::new("--header") ];
+        let opts = Options::parse(args, &None);
+        assert!(matches!(opts, OptionsResult::InvalidOptions(OptionsError::Useless(_, false, _))));
+    }
+}
+
+
+#[cfg(test)]
+mod value_forms_test {
+    use super::*;
+
+    /// Describes the options that the given arguments parse into, so that
+    /// two different ways of writing them can be compared.
+    fn parsed(args: &[&'static str]) -> String {
+        match Options::parse(args.iter().map(OsStr::new), &None) {
+            OptionsResult::Ok(options, frees)  => format!("{:?} {:?}", options, frees),
+            otherwise                          => panic!("Unexpected result: {:?}", otherwise),
+        }
+    }
+
+    // Every value-taking flag goes through the same parser, so giving its
+    // value after an equals sign or as the next argument should be the same,
+    // and both should differ from leaving the flag out entirely.
+    macro_rules! test {
+        ($name:ident: $without:expr, $equals:expr, $separate:expr) => {
+            #[test]
+            fn $name() {
+                assert_eq!(parsed(&$equals), parsed(&$separate));
+                assert_ne!(parsed(&$equals), parsed(&$without));
+            }
+        };
+    }
+
+    test!(sort:        [],           [ "--sort=size" ],                [ "--sort", "size" ]);
+    test!(sort_short:  [],           [ "-s=size" ],                    [ "-s", "size" ]);
+    test!(time:        [ "--long" ], [ "--long", "--time=accessed" ],  [ "--long", "--time", "accessed" ]);
+    test!(level:       [ "--tree" ], [ "--tree", "--level=2" ],        [ "--tree", "--level", "2" ]);
+    test!(time_style:  [ "--long" ], [ "--long", "--time-style=iso" ], [ "--long", "--time-style", "iso" ]);
+    test!(colour:      [],           [ "--colour=always" ],            [ "--colour", "always" ]);
+    test!(color:       [],           [ "--color=always" ],             [ "--color", "always" ]);
+}
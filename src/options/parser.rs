@@ -457,6 +457,12 @@ impl<'a> MatchedFlags<'a> {
     pub fn is_strict(&self) -> bool {
         self.strictness == Strictness::ComplainAboutRedundantArguments
     }
+
+    /// Turns strict mode on after the arguments have been parsed, for when
+    /// one of the arguments is the one asking for it.
+    pub fn make_strict(&mut self) {
+        self.strictness = Strictness::ComplainAboutRedundantArguments;
+    }
 }
 
 
@@ -1,12 +1,14 @@
 use crate::options::{flags, vars, Vars, OptionsError};
 use crate::options::parser::MatchedFlags;
-use crate::theme::{Options, UseColours, ColourScale, Definitions};
+use crate::theme::{Options, UseColours, ColourScale, ColourBy, ColourScheme, Definitions};
 
 
 impl Options {
     pub fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
         let use_colours = UseColours::deduce(matches, vars)?;
         let colour_scale = ColourScale::deduce(matches)?;
+        let colour_by = ColourBy::deduce(matches)?;
+        let colour_scheme = ColourScheme::deduce(matches, vars)?;
 
         let definitions = if use_colours == UseColours::Never {
                 Definitions::default()
@@ -15,7 +17,9 @@ impl Options {
                 Definitions::deduce(vars)
             };
 
-        Ok(Self { use_colours, colour_scale, definitions })
+        let compound_extensions = matches.has(&flags::COMPOUND_EXTENSIONS)?;
+
+        Ok(Self { use_colours, colour_scale, colour_by, colour_scheme, definitions, compound_extensions })
     }
 }
 
@@ -24,7 +28,7 @@ impl UseColours {
     fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
         let default_value = match vars.get(vars::NO_COLOR) {
             Some(_) => Self::Never,
-            None => Self::Automatic,
+            None => Self::automatic(vars),
         };
 
         let word = match matches.get_where(|f| f.matches(&flags::COLOR) || f.matches(&flags::COLOUR))? {
@@ -36,7 +40,7 @@ impl UseColours {
             Ok(Self::Always)
         }
         else if word == "auto" || word == "automatic" {
-            Ok(Self::Automatic)
+            Ok(Self::automatic(vars))
         }
         else if word == "never" {
             Ok(Self::Never)
@@ -45,6 +49,18 @@ impl UseColours {
             Err(OptionsError::BadArgument(&flags::COLOR, word.into()))
         }
     }
+
+    /// Colours only get used automatically if the terminal can show them.
+    /// A dumb terminal can’t, and neither can one that doesn’t set `TERM`
+    /// at all on a platform where it’s always expected to be set.
+    fn automatic<V: Vars>(vars: &V) -> Self {
+        match vars.get(vars::TERM) {
+            Some(term) if term == "dumb"  => Self::Never,
+            #[cfg(unix)]
+            None                          => Self::Never,
+            _                             => Self::Automatic,
+        }
+    }
 }
 
 
@@ -60,6 +76,64 @@ impl ColourScale {
 }
 
 
+impl ColourBy {
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let word = match matches.get_where(|f| f.matches(&flags::COLOR_BY) || f.matches(&flags::COLOUR_BY))? {
+            Some(w)  => w,
+            None => return Ok(Self::Extension),
+        };
+
+        if word == "extension" {
+            Ok(Self::Extension)
+        }
+        else if word == "content" {
+            Ok(Self::Content)
+        }
+        else {
+            Err(OptionsError::BadArgument(&flags::COLOR_BY, word.into()))
+        }
+    }
+}
+
+
+impl ColourScheme {
+    fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
+        let word = match matches.get_where(|f| f.matches(&flags::COLOR_SCHEME) || f.matches(&flags::COLOUR_SCHEME))? {
+            Some(w)  => w,
+            None     => return Ok(Self::detect(vars)),
+        };
+
+        if word == "dark" {
+            Ok(Self::Dark)
+        }
+        else if word == "light" {
+            Ok(Self::Light)
+        }
+        else if word == "auto" || word == "automatic" {
+            Ok(Self::detect(vars))
+        }
+        else {
+            Err(OptionsError::BadArgument(&flags::COLOR_SCHEME, word.into()))
+        }
+    }
+
+    /// Guesses the terminal’s background from the `COLORFGBG` variable,
+    /// whose last number is the background’s colour. Colour 7 and the
+    /// bright colours from 9 up are light; the rest, and anything that
+    /// can’t be read, are taken to be dark.
+    fn detect<V: Vars>(vars: &V) -> Self {
+        let background = vars.get(vars::COLORFGBG)
+                             .and_then(|fgbg| fgbg.into_string().ok())
+                             .and_then(|fgbg| fgbg.rsplit(';').next().and_then(|bg| bg.parse::<u8>().ok()));
+
+        match background {
+            Some(bg) if bg == 7 || (9 ..= 15).contains(&bg)  => Self::Light,
+            _                                              => Self::Dark,
+        }
+    }
+}
+
+
 impl Definitions {
     fn deduce<V: Vars>(vars: &V) -> Self {
         let ls =  vars.get(vars::LS_COLORS) .map(|e| e.to_string_lossy().to_string());
@@ -80,7 +154,9 @@ mod terminal_test {
     use crate::options::test::Strictnesses::*;
 
     static TEST_ARGS: &[&Arg] = &[ &flags::COLOR,       &flags::COLOUR,
-                                   &flags::COLOR_SCALE, &flags::COLOUR_SCALE, ];
+                                   &flags::COLOR_SCALE, &flags::COLOUR_SCALE,
+                                   &flags::COLOR_BY,    &flags::COLOUR_BY,
+                                   &flags::COLOR_SCHEME, &flags::COLOUR_SCHEME, ];
 
     macro_rules! test {
         ($name:ident:  $type:ident <- $inputs:expr;  $stricts:expr => $result:expr) => {
@@ -126,6 +202,8 @@ mod terminal_test {
         ls: &'static str,
         exa: &'static str,
         no_color: &'static str,
+        colorfgbg: &'static str,
+        term: &'static str,
     }
 
     impl MockVars {
@@ -134,6 +212,8 @@ mod terminal_test {
                 ls: "",
                 exa: "",
                 no_color: "",
+                colorfgbg: "",
+                term: "xterm",
             }
         }
         fn with_no_color() -> MockVars {
@@ -141,6 +221,26 @@ mod terminal_test {
                 ls: "",
                 exa: "",
                 no_color: "true",
+                colorfgbg: "",
+                term: "xterm",
+            }
+        }
+        fn with_colorfgbg(colorfgbg: &'static str) -> MockVars {
+            MockVars {
+                ls: "",
+                exa: "",
+                no_color: "",
+                colorfgbg,
+                term: "xterm",
+            }
+        }
+        fn with_term(term: &'static str) -> MockVars {
+            MockVars {
+                ls: "",
+                exa: "",
+                no_color: "",
+                colorfgbg: "",
+                term,
             }
         }
     }
@@ -157,6 +257,12 @@ mod terminal_test {
             else if name == vars::NO_COLOR && ! self.no_color.is_empty() {
                 Some(OsString::from(self.no_color.clone()))
             }
+            else if name == vars::COLORFGBG && ! self.colorfgbg.is_empty() {
+                Some(OsString::from(self.colorfgbg))
+            }
+            else if name == vars::TERM && ! self.term.is_empty() {
+                Some(OsString::from(self.term))
+            }
             else {
                 None
             }
@@ -179,6 +285,24 @@ mod terminal_test {
     test!(no_u_auto:     UseColours <- ["--color=auto"], MockVars::empty();       Both => Ok(UseColours::Automatic));
     test!(no_u_never:    UseColours <- ["--color", "never"], MockVars::empty();   Both => Ok(UseColours::Never));
 
+    // Dumb terminals
+    test!(dumb_default:  UseColours <- [], MockVars::with_term("dumb");                 Both => Ok(UseColours::Never));
+    test!(dumb_auto:     UseColours <- ["--color=auto"], MockVars::with_term("dumb");   Both => Ok(UseColours::Never));
+    test!(dumb_always:   UseColours <- ["--colour=always"], MockVars::with_term("dumb"); Both => Ok(UseColours::Always));
+    #[cfg(unix)]
+    test!(unset_term:    UseColours <- ["--color=auto"], MockVars::with_term("");       Both => Ok(UseColours::Never));
+
+    #[test]
+    fn dumb_terminal_is_plain() {
+        use crate::theme::UiStyles;
+
+        let env = MockVars::with_term("dumb");
+        for options in parse_for_test(&["--colour=auto"], TEST_ARGS, Both, |mf| Options::deduce(mf, &env)) {
+            let theme = options.unwrap().to_theme(true);
+            assert_eq!(theme.ui, UiStyles::plain());
+        }
+    }
+
     // Errors
     test!(no_u_error:    UseColours <- ["--color=upstream"], MockVars::empty();   Both => err OptionsError::BadArgument(&flags::COLOR, OsString::from("upstream"))); // the error is for --color
     test!(u_error:       UseColours <- ["--colour=lovers"], MockVars::empty();    Both => err OptionsError::BadArgument(&flags::COLOR, OsString::from("lovers"))); // and so is this one!
@@ -203,4 +327,22 @@ mod terminal_test {
     test!(scale_6:  ColourScale <- ["--color-scale",                 ];   Complain => Ok(ColourScale::Gradient));
     test!(scale_7:  ColourScale <- [                 "--colour-scale"];   Complain => Ok(ColourScale::Gradient));
     test!(scale_8:  ColourScale <- [                                 ];   Complain => Ok(ColourScale::Fixed));
+
+    test!(by_none:       ColourBy <- [];                                          Both => Ok(ColourBy::Extension));
+    test!(by_extension:  ColourBy <- ["--colour-by=extension"];                   Both => Ok(ColourBy::Extension));
+    test!(by_content:    ColourBy <- ["--color-by=content"];                      Both => Ok(ColourBy::Content));
+    test!(by_error:      ColourBy <- ["--colour-by=magic"];                       Both => err OptionsError::BadArgument(&flags::COLOR_BY, OsString::from("magic")));
+    test!(by_last:       ColourBy <- ["--color-by=content", "--colour-by=extension"];  Last => Ok(ColourBy::Extension));
+    test!(by_twice:      ColourBy <- ["--color-by=content", "--colour-by=extension"];  Complain => err OptionsError::Duplicate(Flag::Long("color-by"), Flag::Long("colour-by")));
+
+    // --colour-scheme
+    test!(scheme_default:     ColourScheme <- [], MockVars::empty();                                       Both => Ok(ColourScheme::Dark));
+    test!(scheme_dark_bg:     ColourScheme <- [], MockVars::with_colorfgbg("15;0");                        Both => Ok(ColourScheme::Dark));
+    test!(scheme_light_bg:    ColourScheme <- [], MockVars::with_colorfgbg("0;15");                        Both => Ok(ColourScheme::Light));
+    test!(scheme_light_3:     ColourScheme <- [], MockVars::with_colorfgbg("0;default;7");                 Both => Ok(ColourScheme::Light));
+    test!(scheme_garbage:     ColourScheme <- [], MockVars::with_colorfgbg("default");                     Both => Ok(ColourScheme::Dark));
+    test!(scheme_auto_light:  ColourScheme <- ["--color-scheme=auto"], MockVars::with_colorfgbg("0;15");   Both => Ok(ColourScheme::Light));
+    test!(scheme_forced:      ColourScheme <- ["--colour-scheme=dark"], MockVars::with_colorfgbg("0;15");  Both => Ok(ColourScheme::Dark));
+    test!(scheme_light:       ColourScheme <- ["--colour-scheme", "light"], MockVars::empty();             Both => Ok(ColourScheme::Light));
+    test!(scheme_error:       ColourScheme <- ["--color-scheme=sepia"], MockVars::empty();                 Both => err OptionsError::BadArgument(&flags::COLOR_SCHEME, OsString::from("sepia")));
 }
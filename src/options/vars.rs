@@ -19,6 +19,32 @@ pub static TIME_STYLE: &str = "TIME_STYLE";
 /// See: <https://no-color.org/>
 pub static NO_COLOR: &str = "NO_COLOR";
 
+/// Environment variables used to pick the locale, which are checked, in
+/// this order, for whether the terminal uses UTF-8, and so whether it can
+/// draw table borders with box-drawing characters.
+pub static LC_ALL: &str = "LC_ALL";
+pub static LC_CTYPE: &str = "LC_CTYPE";
+pub static LANG: &str = "LANG";
+
+/// Environment variable used to choose the program that output gets sent
+/// through when paging is turned on.
+pub static PAGER: &str = "PAGER";
+
+/// Environment variable used to name the type of terminal, which is checked
+/// for `dumb` terminals that can’t show colours.
+pub static TERM: &str = "TERM";
+
+/// Environment variable set by some terminals to the colour numbers of their
+/// foreground and background, such as `15;0`, used to work out whether the
+/// background is light or dark.
+pub static COLORFGBG: &str = "COLORFGBG";
+
+/// Environment variables used to find the user’s configuration directory,
+/// where exa looks for its global ignore file. `XDG_CONFIG_HOME` is checked
+/// first, falling back to `.config` in the home directory.
+pub static XDG_CONFIG_HOME: &str = "XDG_CONFIG_HOME";
+pub static HOME: &str = "HOME";
+
 // exa-specific variables
 
 /// Environment variable used to colour exa’s interface when colours are
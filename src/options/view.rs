@@ -1,19 +1,38 @@
+use std::path::PathBuf;
+
+use crate::fs::checksum;
 use crate::fs::feature::xattr;
 use crate::options::{flags, OptionsError, NumberSource, Vars};
 use crate::options::parser::MatchedFlags;
-use crate::output::{View, Mode, TerminalWidth, grid, details};
+use crate::output::{View, Mode, DirHeadings, RunMode, TerminalWidth, grid, details};
+use crate::output::details::{HeaderRow, SummaryRow, LinkTargets, Xattrs};
 use crate::output::grid_details::{self, RowThreshold};
 use crate::output::file_name::Options as FileStyle;
-use crate::output::table::{TimeTypes, SizeFormat, UserFormat, Columns, Options as TableOptions};
-use crate::output::time::TimeFormat;
+use crate::output::groups::GroupBy;
+use crate::output::pager::Paging;
+use crate::output::table::{TimeTypes, SizeFormat, SizeColour, UserFormat, Borders, Columns, ColumnWidths, Options as TableOptions};
+use crate::output::time::{TimeFormat, MonthNames, SubSeconds};
 
 
 impl View {
     pub fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
         let mode = Mode::deduce(matches, vars)?;
-        let width = TerminalWidth::deduce(vars)?;
+        let width = TerminalWidth::deduce(matches, vars)?;
         let file_style = FileStyle::deduce(matches, vars)?;
-        Ok(Self { mode, width, file_style })
+        let paging = Paging::deduce(matches)?;
+        let headings = DirHeadings::deduce(matches)?;
+        let group_by = GroupBy::deduce(matches)?;
+        let output = matches.get(&flags::OUTPUT)?.map(PathBuf::from);
+        let run = RunMode::deduce(matches)?;
+
+        // Only the lines and grid views can be split into sections.
+        if group_by != GroupBy::Nothing && matches.is_strict() {
+            if let Mode::Details(_) | Mode::GridDetails(_) = mode {
+                return Err(OptionsError::Useless(&flags::GROUP_BY, true, &flags::LONG));
+            }
+        }
+
+        Ok(Self { mode, width, file_style, paging, headings, group_by, output, run })
     }
 }
 
@@ -29,6 +48,52 @@ impl Mode {
     /// This is complicated a little by the fact that `--grid` and `--tree`
     /// can also combine with `--long`, so care has to be taken to use the
     pub fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
+        // A tree can’t be drawn with one entry per flat line, so in strict
+        // mode, complain instead of picking whichever came last.
+        if matches.is_strict() && matches.has(&flags::TREE)? && matches.has(&flags::ONE_LINE)? {
+            return Err(OptionsError::Conflict(&flags::TREE, &flags::ONE_LINE));
+        }
+
+        // The stat view replaces every other view, rather than combining
+        // with any of them.
+        if matches.has(&flags::STAT)? {
+            if matches.is_strict() {
+                for other in &[ &flags::LONG, &flags::ONE_LINE, &flags::GRID, &flags::TREE ] {
+                    if matches.has(other)? {
+                        return Err(OptionsError::Conflict(&flags::STAT, *other));
+                    }
+                }
+            }
+
+            return Ok(Self::Stat);
+        }
+
+        // The same goes for listing directory names for completion.
+        if matches.has(&flags::COMPLETE_DIRS)? {
+            if matches.is_strict() {
+                for other in &[ &flags::LONG, &flags::ONE_LINE, &flags::GRID, &flags::TREE ] {
+                    if matches.has(other)? {
+                        return Err(OptionsError::Conflict(&flags::COMPLETE_DIRS, *other));
+                    }
+                }
+            }
+
+            return Ok(Self::NulSeparated);
+        }
+
+        // And for the single line of totals.
+        if matches.has(&flags::ONELINER_SUMMARY)? {
+            if matches.is_strict() {
+                for other in &[ &flags::LONG, &flags::ONE_LINE, &flags::GRID, &flags::TREE ] {
+                    if matches.has(other)? {
+                        return Err(OptionsError::Conflict(&flags::ONELINER_SUMMARY, *other));
+                    }
+                }
+            }
+
+            return Ok(Self::OnelinerSummary);
+        }
+
         let flag = matches.has_where_any(|f| f.matches(&flags::LONG) || f.matches(&flags::ONE_LINE)
                                           || f.matches(&flags::GRID) || f.matches(&flags::TREE));
 
@@ -63,6 +128,11 @@ impl Mode {
 
         if flag.matches(&flags::TREE) {
             let _ = matches.has(&flags::TREE)?;
+
+            if matches.is_strict() && matches.has(&flags::ACROSS)? {
+                return Err(OptionsError::Useless(&flags::ACROSS, true, &flags::TREE));
+            }
+
             let details = details::Options::deduce_tree(matches)?;
             return Ok(Self::Details(details));
         }
@@ -80,8 +150,12 @@ impl Mode {
         // If --long hasn’t been passed, then check if we need to warn the
         // user about flags that won’t have any effect.
         if matches.is_strict() {
-            for option in &[ &flags::BINARY, &flags::BYTES, &flags::INODE, &flags::LINKS,
-                             &flags::HEADER, &flags::BLOCKS, &flags::TIME, &flags::GROUP, &flags::NUMERIC ] {
+            for option in &[ &flags::BINARY, &flags::BYTES, &flags::INODE, &flags::DEVICE_INODE, &flags::LINKS,
+                             &flags::HEADER, &flags::BLOCKS, &flags::TIME, &flags::GROUP, &flags::MERGE_OWNER, &flags::SUMMARY_ROW, &flags::NUMERIC,
+                             &flags::FILE_VERSION, &flags::NANOSECONDS, &flags::NO_PERMISSIONS, &flags::AGE,
+                             &flags::SECURITY_SUMMARY, &flags::LINK_TARGET_COLUMN, &flags::SIZE_PERCENT, &flags::SIZE_BAR,
+                             &flags::MARK_SPARSE, &flags::EFFECTIVE_PERMISSIONS, &flags::ENCODING, &flags::SIZE_BOTH,
+                             &flags::COLOR_AGE, &flags::COLOUR_AGE, &flags::REVERSE_LINKS, &flags::VOLUME, &flags::TYPE_COLUMN, &flags::MOUNT_INFO, &flags::NO_LEAD_PAD ] {
                 if matches.has(option)? {
                     return Err(OptionsError::Useless(*option, false, &flags::LONG));
                 }
@@ -90,6 +164,27 @@ impl Mode {
             if matches.has(&flags::GIT)? {
                 return Err(OptionsError::Useless(&flags::GIT, false, &flags::LONG));
             }
+            else if matches.get(&flags::GIT_COL_WIDTH)?.is_some() {
+                return Err(OptionsError::Useless(&flags::GIT_COL_WIDTH, false, &flags::LONG));
+            }
+            else if matches.get(&flags::CHECKSUM)?.is_some() {
+                return Err(OptionsError::Useless(&flags::CHECKSUM, false, &flags::LONG));
+            }
+            else if matches.has(&flags::BORDERS)? {
+                return Err(OptionsError::Useless(&flags::BORDERS, false, &flags::LONG));
+            }
+            else if matches.get(&flags::TRUNCATE_NAMES)?.is_some() {
+                return Err(OptionsError::Useless(&flags::TRUNCATE_NAMES, false, &flags::LONG));
+            }
+            else if matches.get(&flags::MONTH_NAMES)?.is_some() {
+                return Err(OptionsError::Useless(&flags::MONTH_NAMES, false, &flags::LONG));
+            }
+            else if matches.get(&flags::HUMAN_ABOVE)?.is_some() {
+                return Err(OptionsError::Useless(&flags::HUMAN_ABOVE, false, &flags::LONG));
+            }
+            else if matches.get_where(|f| f.matches(&flags::SIZE_COLOR) || f.matches(&flags::SIZE_COLOUR))?.is_some() {
+                return Err(OptionsError::Useless(&flags::SIZE_COLOR, false, &flags::LONG));
+            }
             else if matches.has(&flags::LEVEL)? && ! matches.has(&flags::RECURSE)? && ! matches.has(&flags::TREE)? {
                 return Err(OptionsError::Useless2(&flags::LEVEL, &flags::RECURSE, &flags::TREE));
             }
@@ -101,9 +196,17 @@ impl Mode {
 
 
 impl grid::Options {
+
+    /// Determines the grid direction from whichever of `--across` and
+    /// `--down` was given last, so an alias that sets one can be
+    /// overridden by the other on the command-line.
     fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let flag = matches.has_where(|f| f.matches(&flags::ACROSS) || f.matches(&flags::DOWN))?;
+
         let grid = grid::Options {
-            across: matches.has(&flags::ACROSS)?,
+            across: flag.map_or(false, |f| f.matches(&flags::ACROSS)),
+            strict: matches.has(&flags::STRICT_GRID)?,
+            wrap_names: matches.has(&flags::WRAP_NAMES)?,
         };
 
         Ok(grid)
@@ -115,8 +218,10 @@ impl details::Options {
     fn deduce_tree(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
         let details = details::Options {
             table: None,
-            header: false,
-            xattr: xattr::ENABLED && matches.has(&flags::EXTENDED)?,
+            header: HeaderRow::Off,
+            summary_row: SummaryRow::Off,
+            link_targets: LinkTargets::AfterArrow,
+            xattr: Xattrs::deduce(matches)?,
         };
 
         Ok(details)
@@ -134,22 +239,89 @@ impl details::Options {
 
         Ok(details::Options {
             table: Some(TableOptions::deduce(matches, vars)?),
-            header: matches.has(&flags::HEADER)?,
-            xattr: xattr::ENABLED && matches.has(&flags::EXTENDED)?,
+            header: HeaderRow::deduce(matches)?,
+            summary_row: SummaryRow::deduce(matches)?,
+            link_targets: LinkTargets::deduce(matches)?,
+            xattr: Xattrs::deduce(matches)?,
         })
     }
 }
 
 
+impl HeaderRow {
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        if matches.has(&flags::HEADER)? {
+            Ok(Self::Show)
+        }
+        else {
+            Ok(Self::Off)
+        }
+    }
+}
+
+
+impl SummaryRow {
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        if matches.has(&flags::SUMMARY_ROW)? {
+            Ok(Self::Show)
+        }
+        else {
+            Ok(Self::Off)
+        }
+    }
+}
+
+
+impl LinkTargets {
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        if matches.has(&flags::LINK_TARGET_COLUMN)? {
+            Ok(Self::Column)
+        }
+        else {
+            Ok(Self::AfterArrow)
+        }
+    }
+}
+
+
+impl Xattrs {
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        if xattr::ENABLED && matches.has(&flags::EXTENDED)? {
+            Ok(Self::Show)
+        }
+        else {
+            Ok(Self::Off)
+        }
+    }
+}
+
+
 impl TerminalWidth {
-    fn deduce<V: Vars>(vars: &V) -> Result<Self, OptionsError> {
+
+    /// Reads the width from `COLUMNS`, if it’s set. Unless `--grid` was
+    /// passed explicitly, the width only counts for output to a terminal,
+    /// so piping exa somewhere still lists one file per line.
+    ///
+    /// Some shells export the variable with extra bits after the number,
+    /// so surrounding whitespace is trimmed and only the digits at the
+    /// start are used. It’s only an error if it doesn’t start with any.
+    fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
         use crate::options::vars;
 
         if let Some(columns) = vars.get(vars::COLUMNS).and_then(|s| s.into_string().ok()) {
-            match columns.parse() {
-                Ok(width) => {
+            let trimmed = columns.trim();
+            let digits = match trimmed.find(|c: char| ! c.is_ascii_digit()) {
+                Some(0) | None  => trimmed,
+                Some(end)       => &trimmed[.. end],
+            };
+
+            match digits.parse() {
+                Ok(width) if matches.has(&flags::GRID)? => {
                     Ok(Self::Set(width))
                 }
+                Ok(width) => {
+                    Ok(Self::SetForTerminal(width))
+                }
                 Err(e) => {
                     let source = NumberSource::Env(vars::COLUMNS);
                     Err(OptionsError::FailedParse(columns, source, e))
@@ -163,6 +335,77 @@ impl TerminalWidth {
 }
 
 
+impl Paging {
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let word = match matches.get(&flags::PAGING)? {
+            Some(w)  => w,
+            None     => return Ok(Self::Never),
+        };
+
+        if word == "auto" || word == "automatic" {
+            Ok(Self::Automatic)
+        }
+        else if word == "always" {
+            Ok(Self::Always)
+        }
+        else if word == "never" {
+            Ok(Self::Never)
+        }
+        else {
+            Err(OptionsError::BadArgument(&flags::PAGING, word.into()))
+        }
+    }
+}
+
+
+impl DirHeadings {
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        Ok(Self {
+            summary:          matches.has(&flags::SUMMARY)?,
+            filesystem_info:  matches.has(&flags::FILESYSTEM_INFO)?,
+        })
+    }
+}
+
+
+impl RunMode {
+
+    /// Printing the theme happens instead of listing anything, so it wins
+    /// over watching the listing.
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        if matches.has(&flags::DEBUG_COLOURS)? {
+            Ok(Self::DebugColours)
+        }
+        else if matches.has(&flags::WATCH)? {
+            Ok(Self::Watch)
+        }
+        else {
+            Ok(Self::Once)
+        }
+    }
+}
+
+
+impl GroupBy {
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let word = match matches.get(&flags::GROUP_BY)? {
+            Some(w)  => w,
+            None     => return Ok(Self::Nothing),
+        };
+
+        if word == "ext" || word == "extension" {
+            Ok(Self::Extension)
+        }
+        else if word == "none" {
+            Ok(Self::Nothing)
+        }
+        else {
+            Err(OptionsError::BadArgument(&flags::GROUP_BY, word.into()))
+        }
+    }
+}
+
+
 impl RowThreshold {
     fn deduce<V: Vars>(vars: &V) -> Result<Self, OptionsError> {
         use crate::options::vars;
@@ -188,10 +431,107 @@ impl RowThreshold {
 impl TableOptions {
     fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
         let time_format = TimeFormat::deduce(matches, vars)?;
+        let sub_seconds = SubSeconds::deduce(matches)?;
         let size_format = SizeFormat::deduce(matches)?;
+        let human_above = Self::deduce_human_above(matches)?;
         let user_format = UserFormat::deduce(matches)?;
         let columns = Columns::deduce(matches)?;
-        Ok(Self { size_format, time_format, user_format, columns })
+        let column_widths = ColumnWidths::deduce(matches)?;
+        let borders = Borders::deduce(matches, vars)?;
+        let mark_sparse = matches.has(&flags::MARK_SPARSE)?;
+        let colour_age = matches.has_where(|f| f.matches(&flags::COLOR_AGE) || f.matches(&flags::COLOUR_AGE))?.is_some();
+        let size_colour = SizeColour::deduce(matches)?;
+        let lead_pad = ! matches.has(&flags::NO_LEAD_PAD)?;
+        Ok(Self { size_format, human_above, time_format, sub_seconds, user_format, columns, column_widths, borders, mark_sparse, colour_age, size_colour, lead_pad })
+    }
+
+    /// Determines the size below which files’ sizes are shown in bytes,
+    /// from the `--human-above` argument.
+    fn deduce_human_above(matches: &MatchedFlags<'_>) -> Result<Option<u64>, OptionsError> {
+        let value = match matches.get(&flags::HUMAN_ABOVE)? {
+            Some(v)  => v,
+            None     => return Ok(None),
+        };
+
+        match value.to_str().and_then(parse_size) {
+            Some(size)  => Ok(Some(size)),
+            None        => Err(OptionsError::BadArgument(&flags::HUMAN_ABOVE, value.into())),
+        }
+    }
+}
+
+
+/// Parses a number of bytes, which can be followed by a decimal prefix such
+/// as ‘k’ or ‘M’, or a binary one such as ‘Ki’ or ‘Mi’, then an optional ‘B’.
+fn parse_size(input: &str) -> Option<u64> {
+    let input = input.strip_suffix('B').unwrap_or(input);
+    let split = input.find(|c: char| ! c.is_ascii_digit()).unwrap_or(input.len());
+    let (number, prefix) = input.split_at(split);
+
+    let multiplier: u64 = match prefix {
+        ""         => 1,
+        "k" | "K"  => 1_000,
+        "M"        => 1_000_000,
+        "G"        => 1_000_000_000,
+        "T"        => 1_000_000_000_000,
+        "Ki"       => 1 << 10,
+        "Mi"       => 1 << 20,
+        "Gi"       => 1 << 30,
+        "Ti"       => 1 << 40,
+        _          => return None,
+    };
+
+    number.parse::<u64>().ok()?.checked_mul(multiplier)
+}
+
+
+impl Borders {
+
+    /// Determines whether to draw borders between the table’s columns, and
+    /// if so, whether the locale allows them to be drawn with box-drawing
+    /// characters. The first locale variable that’s set gets used.
+    fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Option<Self>, OptionsError> {
+        use crate::options::vars;
+
+        if ! matches.has(&flags::BORDERS)? {
+            return Ok(None);
+        }
+
+        let locale = [ vars::LC_ALL, vars::LC_CTYPE, vars::LANG ].iter()
+                         .filter_map(|&name| vars.get(name))
+                         .find(|value| ! value.is_empty())
+                         .map(|value| value.to_string_lossy().to_lowercase())
+                         .unwrap_or_default();
+
+        if locale.contains("utf-8") || locale.contains("utf8") {
+            Ok(Some(Self::Unicode))
+        }
+        else {
+            Ok(Some(Self::Ascii))
+        }
+    }
+}
+
+
+impl ColumnWidths {
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let git = match matches.get(&flags::GIT_COL_WIDTH)? {
+            Some(width) => {
+                let arg_str = width.to_string_lossy();
+                match arg_str.parse() {
+                    Ok(w) => {
+                        Some(w)
+                    }
+                    Err(e) => {
+                        let source = NumberSource::Arg(&flags::GIT_COL_WIDTH);
+                        return Err(OptionsError::FailedParse(arg_str.to_string(), source, e));
+                    }
+                }
+            }
+            None => None,
+        };
+
+        Ok(Self { git })
     }
 }
 
@@ -204,14 +544,45 @@ impl Columns {
         let blocks = matches.has(&flags::BLOCKS)?;
         let group  = matches.has(&flags::GROUP)?;
         let inode  = matches.has(&flags::INODE)?;
+        let device_inode = matches.has(&flags::DEVICE_INODE)?;
+        let file_version = matches.has(&flags::FILE_VERSION)?;
         let links  = matches.has(&flags::LINKS)?;
         let octal  = matches.has(&flags::OCTAL)?;
+        let merge_owner = matches.has(&flags::MERGE_OWNER)?;
+        let age    = matches.has(&flags::AGE)?;
+        let checksum = Self::deduce_checksum(matches)?;
+        let encoding = matches.has(&flags::ENCODING)?;
+        let volume = matches.has(&flags::VOLUME)?;
+        let security_summary = xattr::ENABLED && matches.has(&flags::SECURITY_SUMMARY)?;
 
         let permissions = ! matches.has(&flags::NO_PERMISSIONS)?;
         let filesize =    ! matches.has(&flags::NO_FILESIZE)?;
         let user =        ! matches.has(&flags::NO_USER)?;
+        let size_percent = matches.has(&flags::SIZE_PERCENT)?;
+        let size_bar = matches.has(&flags::SIZE_BAR)?;
+        let effective_permissions = matches.has(&flags::EFFECTIVE_PERMISSIONS)?;
+        let reverse_links = matches.has(&flags::REVERSE_LINKS)?;
+        let file_type = matches.has(&flags::TYPE_COLUMN)?;
+        let mount_info = matches.has(&flags::MOUNT_INFO)?;
+
+        Ok(Self { time_types, inode, device_inode, file_version, links, blocks, group, git, octal, merge_owner, age, checksum, encoding, security_summary, permissions, filesize, user, size_percent, size_bar, effective_permissions, reverse_links, volume, file_type, mount_info })
+    }
 
-        Ok(Self { time_types, inode, links, blocks, group, git, octal, permissions, filesize, user })
+    fn deduce_checksum(matches: &MatchedFlags<'_>) -> Result<Option<checksum::Algorithm>, OptionsError> {
+        let word = match matches.get(&flags::CHECKSUM)? {
+            Some(w)  => w,
+            None     => return Ok(None),
+        };
+
+        if word == "crc32" {
+            Ok(Some(checksum::Algorithm::Crc32))
+        }
+        else if word == "md5" {
+            Ok(Some(checksum::Algorithm::Md5))
+        }
+        else {
+            Err(OptionsError::BadArgument(&flags::CHECKSUM, word.into()))
+        }
     }
 }
 
@@ -224,20 +595,64 @@ impl SizeFormat {
     /// The default mode is to use the decimal prefixes, as they are the
     /// most commonly-understood, and don’t involve trying to parse large
     /// strings of digits in your head. Changing the format to anything else
-    /// involves the `--binary` or `--bytes` flags, and these conflict with
-    /// each other.
+    /// involves the `--binary`, `--bytes`, or `--size-both` flags, and these
+    /// conflict with each other. Showing both prefixed forms next to a raw
+    /// byte count makes no sense, so that pair is always an error.
     fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
-        let flag = matches.has_where(|f| f.matches(&flags::BINARY) || f.matches(&flags::BYTES))?;
+        if matches.has(&flags::SIZE_BOTH)? && matches.has(&flags::BYTES)? {
+            return Err(OptionsError::Conflict(&flags::SIZE_BOTH, &flags::BYTES));
+        }
+
+        let flag = matches.has_where(|f| f.matches(&flags::BINARY) || f.matches(&flags::BYTES) || f.matches(&flags::SIZE_BOTH))?;
 
         Ok(match flag {
-            Some(f) if f.matches(&flags::BINARY)  => Self::BinaryBytes,
-            Some(f) if f.matches(&flags::BYTES)   => Self::JustBytes,
-            _                                     => Self::DecimalBytes,
+            Some(f) if f.matches(&flags::BINARY)     => Self::BinaryBytes,
+            Some(f) if f.matches(&flags::BYTES)      => Self::JustBytes,
+            Some(f) if f.matches(&flags::SIZE_BOTH)  => Self::BothBytes,
+            _                                        => Self::DecimalBytes,
         })
     }
 }
 
 
+impl SizeColour {
+
+    /// Determines what to colour the size column by, from the
+    /// `--size-color` argument. Sizes are coloured by their magnitude
+    /// unless it says otherwise.
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let word = match matches.get_where(|f| f.matches(&flags::SIZE_COLOR) || f.matches(&flags::SIZE_COLOUR))? {
+            Some(w)  => w,
+            None     => return Ok(Self::Magnitude),
+        };
+
+        if word == "magnitude" {
+            Ok(Self::Magnitude)
+        }
+        else if word == "by-type" {
+            Ok(Self::ByType)
+        }
+        else {
+            Err(OptionsError::BadArgument(&flags::SIZE_COLOR, word.into()))
+        }
+    }
+}
+
+
+impl MonthNames {
+
+    /// Determine where the default time format gets its month names from:
+    /// the locale given to `--month-names`, if there is one, or the user’s
+    /// own locale otherwise.
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        match matches.get(&flags::MONTH_NAMES)? {
+            Some(locale)  => Ok(Self::for_locale(&locale.to_string_lossy())),
+            None          => Ok(Self::Locale),
+        }
+    }
+}
+
+
 impl TimeFormat {
 
     /// Determine how time should be formatted in timestamp columns.
@@ -250,19 +665,28 @@ impl TimeFormat {
                 use crate::options::vars;
                 match vars.get(vars::TIME_STYLE) {
                     Some(ref t) if ! t.is_empty()  => t.clone(),
-                    _                              => return Ok(Self::DefaultFormat)
+                    _                              => return Ok(Self::DefaultFormat(MonthNames::deduce(matches)?))
                 }
             };
 
         if &word == "default" {
-            Ok(Self::DefaultFormat)
+            return Ok(Self::DefaultFormat(MonthNames::deduce(matches)?));
         }
-        else if &word == "iso" {
+
+        // Only the default format has any month names to change.
+        if matches.is_strict() && matches.get(&flags::MONTH_NAMES)?.is_some() {
+            return Err(OptionsError::Useless(&flags::MONTH_NAMES, true, &flags::TIME_STYLE));
+        }
+
+        if &word == "iso" {
             Ok(Self::ISOFormat)
         }
         else if &word == "long-iso" {
             Ok(Self::LongISO)
         }
+        else if &word == "iso-seconds" {
+            Ok(Self::ISOSeconds)
+        }
         else if &word == "full-iso" {
             Ok(Self::FullISO)
         }
@@ -273,10 +697,42 @@ impl TimeFormat {
 }
 
 
+impl SubSeconds {
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let flag = matches.has(&flags::NANOSECONDS)?;
+        Ok(if flag { Self::Nanoseconds } else { Self::Hidden })
+    }
+}
+
+
 impl UserFormat {
     fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
         let flag = matches.has(&flags::NUMERIC)?;
-        Ok(if flag { Self::Numeric } else { Self::Name })
+        let width = match matches.get(&flags::TRUNCATE_NAMES)? {
+            Some(width) => {
+                let arg_str = width.to_string_lossy();
+                match arg_str.parse() {
+                    Ok(w) => {
+                        Some(w)
+                    }
+                    Err(e) => {
+                        let source = NumberSource::Arg(&flags::TRUNCATE_NAMES);
+                        return Err(OptionsError::FailedParse(arg_str.to_string(), source, e));
+                    }
+                }
+            }
+            None => None,
+        };
+
+        if flag && width.is_some() && matches.is_strict() {
+            return Err(OptionsError::Useless(&flags::TRUNCATE_NAMES, true, &flags::NUMERIC));
+        }
+
+        Ok(match (flag, width) {
+            (true, _)         => Self::Numeric,
+            (false, Some(w))  => Self::Truncated(w),
+            (false, None)     => Self::Name,
+        })
     }
 }
 
@@ -360,8 +816,16 @@ mod test {
                                    &flags::CREATED, &flags::ACCESSED,
                                    &flags::HEADER, &flags::GROUP,  &flags::INODE, &flags::GIT,
                                    &flags::LINKS,  &flags::BLOCKS, &flags::LONG,  &flags::LEVEL,
-                                   &flags::GRID,   &flags::ACROSS, &flags::ONE_LINE, &flags::TREE,
-                                   &flags::NUMERIC ];
+                                   &flags::GRID,   &flags::ACROSS, &flags::DOWN, &flags::ONE_LINE, &flags::TREE,
+                                   &flags::NUMERIC, &flags::PAGING, &flags::GIT_COL_WIDTH,
+                                   &flags::FILE_VERSION, &flags::NANOSECONDS, &flags::NO_PERMISSIONS,
+                                   &flags::GROUP_BY, &flags::CHECKSUM, &flags::STAT, &flags::COMPLETE_DIRS, &flags::ONELINER_SUMMARY, &flags::AGE,
+                                   &flags::MERGE_OWNER, &flags::SUMMARY_ROW, &flags::DEVICE_INODE,
+                                   &flags::STRICT_GRID, &flags::WRAP_NAMES, &flags::SECURITY_SUMMARY, &flags::LINK_TARGET_COLUMN,
+                                   &flags::TRUNCATE_NAMES, &flags::BORDERS, &flags::SIZE_PERCENT, &flags::SIZE_BAR, &flags::MONTH_NAMES,
+                                   &flags::MARK_SPARSE, &flags::EFFECTIVE_PERMISSIONS, &flags::ENCODING, &flags::SIZE_BOTH,
+                                   &flags::COLOR_AGE, &flags::COLOUR_AGE, &flags::REVERSE_LINKS, &flags::VOLUME, &flags::HUMAN_ABOVE, &flags::TYPE_COLUMN, &flags::MOUNT_INFO,
+                                   &flags::SIZE_COLOR, &flags::SIZE_COLOUR, &flags::NO_LEAD_PAD ];
 
     macro_rules! test {
 
@@ -429,6 +893,44 @@ mod test {
     }
 
 
+    mod terminal_widths {
+        use super::*;
+        use crate::output::TerminalWidth;
+
+        test!(automatic:  TerminalWidth <- [], None;                          Both => like Ok(TerminalWidth::Automatic));
+        test!(columns:    TerminalWidth <- [], Some("80".into());             Both => like Ok(TerminalWidth::SetForTerminal(80)));
+        test!(grid:       TerminalWidth <- ["--grid"], Some("80".into());     Both => like Ok(TerminalWidth::Set(80)));
+        test!(long:       TerminalWidth <- ["--long"], Some("80".into());     Both => like Ok(TerminalWidth::SetForTerminal(80)));
+
+        // Extra bits around the number
+        test!(spaces:     TerminalWidth <- [], Some(" 80 ".into());           Both => like Ok(TerminalWidth::SetForTerminal(80)));
+        test!(trailing:   TerminalWidth <- [], Some("80x24".into());          Both => like Ok(TerminalWidth::SetForTerminal(80)));
+        test!(float:      TerminalWidth <- [], Some("80.0".into());           Both => like Ok(TerminalWidth::SetForTerminal(80)));
+        test!(wide:       TerminalWidth <- [], Some("wide".into());           Both => err OptionsError::FailedParse(String::from("wide"), NumberSource::Env("COLUMNS"), "wide".parse::<usize>().unwrap_err()));
+    }
+
+
+    mod borders {
+        use super::*;
+
+        test!(off:        Borders <- [], Some("en_GB.UTF-8".into());             Both => like Ok(None));
+        test!(unicode:    Borders <- ["--borders"], Some("en_GB.UTF-8".into());  Both => like Ok(Some(Borders::Unicode)));
+        test!(lowercase:  Borders <- ["--borders"], Some("C.utf8".into());       Both => like Ok(Some(Borders::Unicode)));
+        test!(ascii:      Borders <- ["--borders"], Some("C".into());            Both => like Ok(Some(Borders::Ascii)));
+        test!(no_locale:  Borders <- ["--borders"], None;                        Both => like Ok(Some(Borders::Ascii)));
+        test!(no_long:    Mode <- ["--borders"], None;                           Complain => err OptionsError::Useless(&flags::BORDERS, false, &flags::LONG));
+    }
+
+
+    mod lead_pad {
+        use super::*;
+
+        test!(on:        Mode <- ["--long"], None;                   Both => like Ok(Mode::Details(details::Options { table: Some(TableOptions { lead_pad: true, .. }), .. })));
+        test!(off:       Mode <- ["--long", "--no-lead-pad"], None;  Both => like Ok(Mode::Details(details::Options { table: Some(TableOptions { lead_pad: false, .. }), .. })));
+        test!(no_long:   Mode <- ["--no-lead-pad"], None;            Complain => err OptionsError::Useless(&flags::NO_LEAD_PAD, false, &flags::LONG));
+    }
+
+
     mod size_formats {
         use super::*;
 
@@ -449,6 +951,264 @@ mod test {
         test!(both_6:  SizeFormat <- ["--bytes",  "--binary"];  Complain => err OptionsError::Duplicate(Flag::Long("bytes"),  Flag::Long("binary")));
         test!(both_7:  SizeFormat <- ["--binary", "--bytes"];   Complain => err OptionsError::Duplicate(Flag::Long("binary"), Flag::Long("bytes")));
         test!(both_8:  SizeFormat <- ["--bytes",  "--bytes"];   Complain => err OptionsError::Duplicate(Flag::Long("bytes"),  Flag::Long("bytes")));
+
+        // Both prefixes at once
+        test!(size_both:  SizeFormat <- ["--size-both"];              Both => Ok(SizeFormat::BothBytes));
+        test!(both_9:     SizeFormat <- ["--binary", "--size-both"];  Last => Ok(SizeFormat::BothBytes));
+        test!(both_10:    SizeFormat <- ["--size-both", "--binary"];  Last => Ok(SizeFormat::BinaryBytes));
+        test!(with_bytes: SizeFormat <- ["--size-both", "--bytes"];   Both => err OptionsError::Conflict(&flags::SIZE_BOTH, &flags::BYTES));
+
+        #[test]
+        fn human_above_sizes() {
+            assert_eq!(parse_size("1k"),    Some(1_000));
+            assert_eq!(parse_size("1kB"),   Some(1_000));
+            assert_eq!(parse_size("4Ki"),   Some(4_096));
+            assert_eq!(parse_size("512"),   Some(512));
+            assert_eq!(parse_size("1.5M"),  None);
+            assert_eq!(parse_size("k"),     None);
+            assert_eq!(parse_size("2X"),    None);
+        }
+    }
+
+
+    mod pagings {
+        use super::*;
+
+        // Default behaviour
+        test!(empty:   Paging <- [];                      Both => Ok(Paging::Never));
+
+        // Individual settings
+        test!(auto:    Paging <- ["--paging=auto"];       Both => Ok(Paging::Automatic));
+        test!(always:  Paging <- ["--paging", "always"];  Both => Ok(Paging::Always));
+        test!(never:   Paging <- ["--paging=never"];      Both => Ok(Paging::Never));
+
+        // Overriding
+        test!(last:    Paging <- ["--paging=always", "--paging=never"];  Last => Ok(Paging::Never));
+        test!(twice:   Paging <- ["--paging=always", "--paging=never"];  Complain => err OptionsError::Duplicate(Flag::Long("paging"), Flag::Long("paging")));
+
+        // Errors
+        test!(sometimes: Paging <- ["--paging=sometimes"];  Both => err OptionsError::BadArgument(&flags::PAGING, OsString::from("sometimes")));
+    }
+
+
+    mod group_bys {
+        use super::*;
+
+        // Default behaviour
+        test!(empty:     GroupBy <- [];                          Both => Ok(GroupBy::Nothing));
+
+        // Individual settings
+        test!(extension: GroupBy <- ["--group-by=extension"];   Both => Ok(GroupBy::Extension));
+        test!(ext:       GroupBy <- ["--group-by", "ext"];      Both => Ok(GroupBy::Extension));
+        test!(none:      GroupBy <- ["--group-by=none"];        Both => Ok(GroupBy::Nothing));
+
+        // Errors
+        test!(size:      GroupBy <- ["--group-by=size"];        Both => err OptionsError::BadArgument(&flags::GROUP_BY, OsString::from("size")));
+    }
+
+
+    mod checksums {
+        use super::*;
+
+        // Default behaviour
+        test!(empty:     Columns <- [];                      Both => like Ok(Columns { checksum: None, .. }));
+
+        // Individual settings
+        test!(crc32:     Columns <- ["--checksum=crc32"];    Both => like Ok(Columns { checksum: Some(checksum::Algorithm::Crc32), .. }));
+        test!(md5:       Columns <- ["--checksum", "md5"];   Both => like Ok(Columns { checksum: Some(checksum::Algorithm::Md5), .. }));
+
+        // Overriding
+        test!(both:      Columns <- ["--checksum=md5", "--checksum=crc32"];  Last => like Ok(Columns { checksum: Some(checksum::Algorithm::Crc32), .. }));
+        test!(both_2:    Columns <- ["--checksum=md5", "--checksum=crc32"];  Complain => err OptionsError::Duplicate(Flag::Long("checksum"), Flag::Long("checksum")));
+
+        // Errors
+        test!(sha1:      Columns <- ["--checksum=sha1"];     Both => err OptionsError::BadArgument(&flags::CHECKSUM, OsString::from("sha1")));
+        test!(no_long:   Mode <- ["--checksum=md5"], None;   Complain => err OptionsError::Useless(&flags::CHECKSUM, false, &flags::LONG));
+    }
+
+
+    mod encoding {
+        use super::*;
+
+        test!(off:       Columns <- [];                      Both => like Ok(Columns { encoding: false, .. }));
+        test!(on:        Columns <- ["--encoding"];          Both => like Ok(Columns { encoding: true, .. }));
+        test!(no_long:   Mode <- ["--encoding"], None;       Complain => err OptionsError::Useless(&flags::ENCODING, false, &flags::LONG));
+    }
+
+
+    mod volume {
+        use super::*;
+
+        test!(off:       Columns <- [];                      Both => like Ok(Columns { volume: false, .. }));
+        test!(on:        Columns <- ["--volume"];            Both => like Ok(Columns { volume: true, .. }));
+        test!(no_long:   Mode <- ["--volume"], None;         Complain => err OptionsError::Useless(&flags::VOLUME, false, &flags::LONG));
+    }
+
+
+    mod type_column {
+        use super::*;
+
+        test!(off:       Columns <- [];                      Both => like Ok(Columns { file_type: false, .. }));
+        test!(on:        Columns <- ["--type-column"];       Both => like Ok(Columns { file_type: true, .. }));
+        test!(no_long:   Mode <- ["--type-column"], None;    Complain => err OptionsError::Useless(&flags::TYPE_COLUMN, false, &flags::LONG));
+    }
+
+
+    mod mount_info {
+        use super::*;
+
+        test!(off:       Columns <- [];                      Both => like Ok(Columns { mount_info: false, .. }));
+        test!(on:        Columns <- ["--mount-info"];        Both => like Ok(Columns { mount_info: true, .. }));
+        test!(no_long:   Mode <- ["--mount-info"], None;     Complain => err OptionsError::Useless(&flags::MOUNT_INFO, false, &flags::LONG));
+    }
+
+
+    mod merge_owner {
+        use super::*;
+
+        test!(off:       Columns <- [];                      Both => like Ok(Columns { merge_owner: false, .. }));
+        test!(on:        Columns <- ["--merge-owner"];       Both => like Ok(Columns { merge_owner: true, .. }));
+        test!(no_long:   Mode <- ["--merge-owner"], None;    Complain => err OptionsError::Useless(&flags::MERGE_OWNER, false, &flags::LONG));
+    }
+
+
+    mod security_summary {
+        use super::*;
+
+        test!(off:       Columns <- [];                             Both => like Ok(Columns { security_summary: false, .. }));
+        test!(on:        Columns <- ["--security-summary"];         Both => like Ok(Columns { security_summary: xattr::ENABLED, .. }));
+        test!(no_long:   Mode <- ["--security-summary"], None;      Complain => err OptionsError::Useless(&flags::SECURITY_SUMMARY, false, &flags::LONG));
+    }
+
+
+    mod size_percent {
+        use super::*;
+
+        test!(off:       Columns <- [];                             Both => like Ok(Columns { size_percent: false, .. }));
+        test!(on:        Columns <- ["--size-percent"];             Both => like Ok(Columns { size_percent: true, .. }));
+        test!(no_long:   Mode <- ["--size-percent"], None;          Complain => err OptionsError::Useless(&flags::SIZE_PERCENT, false, &flags::LONG));
+    }
+
+
+    mod size_bar {
+        use super::*;
+
+        test!(off:       Columns <- [];                             Both => like Ok(Columns { size_bar: false, .. }));
+        test!(on:        Columns <- ["--size-bar"];                 Both => like Ok(Columns { size_bar: true, .. }));
+        test!(no_long:   Mode <- ["--size-bar"], None;              Complain => err OptionsError::Useless(&flags::SIZE_BAR, false, &flags::LONG));
+    }
+
+
+    mod effective_permissions {
+        use super::*;
+
+        test!(off:       Columns <- [];                             Both => like Ok(Columns { effective_permissions: false, .. }));
+        test!(on:        Columns <- ["--effective-permissions"];    Both => like Ok(Columns { effective_permissions: true, .. }));
+        test!(no_long:   Mode <- ["--effective-permissions"], None; Complain => err OptionsError::Useless(&flags::EFFECTIVE_PERMISSIONS, false, &flags::LONG));
+    }
+
+
+    mod reverse_links {
+        use super::*;
+
+        test!(off:       Columns <- [];                             Both => like Ok(Columns { reverse_links: false, .. }));
+        test!(on:        Columns <- ["--reverse-links"];            Both => like Ok(Columns { reverse_links: true, .. }));
+        test!(no_long:   Mode <- ["--reverse-links"], None;         Complain => err OptionsError::Useless(&flags::REVERSE_LINKS, false, &flags::LONG));
+    }
+
+
+    mod mark_sparse {
+        use super::*;
+
+        test!(off:       Mode <- ["--long"], None;                  Both => like Ok(Mode::Details(details::Options { table: Some(TableOptions { mark_sparse: false, .. }), .. })));
+        test!(on:        Mode <- ["--long", "--mark-sparse"], None; Both => like Ok(Mode::Details(details::Options { table: Some(TableOptions { mark_sparse: true, .. }), .. })));
+        test!(no_long:   Mode <- ["--mark-sparse"], None;           Complain => err OptionsError::Useless(&flags::MARK_SPARSE, false, &flags::LONG));
+    }
+
+
+    mod colour_age {
+        use super::*;
+
+        test!(off:       Mode <- ["--long"], None;                  Both => like Ok(Mode::Details(details::Options { table: Some(TableOptions { colour_age: false, .. }), .. })));
+        test!(color:     Mode <- ["--long", "--color-age"], None;   Both => like Ok(Mode::Details(details::Options { table: Some(TableOptions { colour_age: true, .. }), .. })));
+        test!(colour:    Mode <- ["--long", "--colour-age"], None;  Both => like Ok(Mode::Details(details::Options { table: Some(TableOptions { colour_age: true, .. }), .. })));
+        test!(no_long:   Mode <- ["--colour-age"], None;            Complain => err OptionsError::Useless(&flags::COLOUR_AGE, false, &flags::LONG));
+    }
+
+
+    mod size_colour {
+        use super::*;
+
+        test!(empty:     SizeColour <- [];                         Both => Ok(SizeColour::Magnitude));
+        test!(by_type:   SizeColour <- ["--size-color=by-type"];   Both => Ok(SizeColour::ByType));
+        test!(colour:    SizeColour <- ["--size-colour=by-type"];  Both => Ok(SizeColour::ByType));
+        test!(magnitude: SizeColour <- ["--size-color=magnitude"]; Both => Ok(SizeColour::Magnitude));
+        test!(bad:       SizeColour <- ["--size-color=rainbow"];   Both => err OptionsError::BadArgument(&flags::SIZE_COLOR, OsString::from("rainbow")));
+        test!(no_long:   Mode <- ["--size-color=by-type"], None;   Complain => err OptionsError::Useless(&flags::SIZE_COLOR, false, &flags::LONG));
+    }
+
+
+    mod device_inode {
+        use super::*;
+
+        test!(off:       Columns <- ["--inode"];                    Both => like Ok(Columns { inode: true, device_inode: false, .. }));
+        test!(on:        Columns <- ["--device-inode"];             Both => like Ok(Columns { device_inode: true, .. }));
+        test!(no_long:   Mode <- ["--device-inode"], None;          Complain => err OptionsError::Useless(&flags::DEVICE_INODE, false, &flags::LONG));
+    }
+
+
+    mod link_target_columns {
+        use super::*;
+
+        test!(off:       Mode <- ["--long"], None;                           Both => like Ok(Mode::Details(details::Options { link_targets: LinkTargets::AfterArrow, .. })));
+        test!(on:        Mode <- ["--long", "--link-target-column"], None;   Both => like Ok(Mode::Details(details::Options { link_targets: LinkTargets::Column, .. })));
+        test!(no_long:   Mode <- ["--link-target-column"], None;             Complain => err OptionsError::Useless(&flags::LINK_TARGET_COLUMN, false, &flags::LONG));
+    }
+
+
+    mod summary_rows {
+        use super::*;
+
+        test!(off:       Mode <- ["--long"], None;                    Both => like Ok(Mode::Details(details::Options { summary_row: SummaryRow::Off, .. })));
+        test!(on:        Mode <- ["--long", "--summary-row"], None;   Both => like Ok(Mode::Details(details::Options { summary_row: SummaryRow::Show, .. })));
+        test!(no_long:   Mode <- ["--summary-row"], None;             Complain => err OptionsError::Useless(&flags::SUMMARY_ROW, false, &flags::LONG));
+    }
+
+
+    mod column_widths {
+        use super::*;
+
+        // Default behaviour
+        test!(empty:     ColumnWidths <- [];                        Both => Ok(ColumnWidths { git: None }));
+
+        // Git column
+        test!(git:       ColumnWidths <- ["--git-col-width=4"];     Both => Ok(ColumnWidths { git: Some(4) }));
+        test!(git_2:     ColumnWidths <- ["--git-col-width", "1"];  Both => Ok(ColumnWidths { git: Some(1) }));
+
+        // Overriding
+        test!(last:      ColumnWidths <- ["--git-col-width=4", "--git-col-width=3"];  Last => Ok(ColumnWidths { git: Some(3) }));
+        test!(twice:     ColumnWidths <- ["--git-col-width=4", "--git-col-width=3"];  Complain => err OptionsError::Duplicate(Flag::Long("git-col-width"), Flag::Long("git-col-width")));
+
+        // Errors
+        test!(wide:      ColumnWidths <- ["--git-col-width=wide"];  Both => like Err(OptionsError::FailedParse(_, NumberSource::Arg(_), _)));
+    }
+
+
+    mod user_formats {
+        use super::*;
+
+        test!(names:       UserFormat <- [];                                    Both => Ok(UserFormat::Name));
+        test!(numeric:     UserFormat <- ["--numeric"];                         Both => Ok(UserFormat::Numeric));
+        test!(truncated:   UserFormat <- ["--truncate-names=8"];                Both => Ok(UserFormat::Truncated(8)));
+        test!(truncated_2: UserFormat <- ["--truncate-names", "12"];            Both => Ok(UserFormat::Truncated(12)));
+
+        // Numbers don’t get truncated
+        test!(both:        UserFormat <- ["--numeric", "--truncate-names=8"];   Last => Ok(UserFormat::Numeric));
+        test!(both_2:      UserFormat <- ["--numeric", "--truncate-names=8"];   Complain => err OptionsError::Useless(&flags::TRUNCATE_NAMES, true, &flags::NUMERIC));
+
+        // Errors
+        test!(wide:        UserFormat <- ["--truncate-names=wide"];             Both => like Err(OptionsError::FailedParse(_, NumberSource::Arg(_), _)));
+        test!(no_long:     Mode <- ["--truncate-names=8"], None;               Complain => err OptionsError::Useless(&flags::TRUNCATE_NAMES, false, &flags::LONG));
     }
 
 
@@ -459,13 +1219,14 @@ mod test {
         // implement PartialEq.
 
         // Default behaviour
-        test!(empty:     TimeFormat <- [], None;                            Both => like Ok(TimeFormat::DefaultFormat));
+        test!(empty:     TimeFormat <- [], None;                            Both => like Ok(TimeFormat::DefaultFormat(MonthNames::Locale)));
 
         // Individual settings
-        test!(default:   TimeFormat <- ["--time-style=default"], None;      Both => like Ok(TimeFormat::DefaultFormat));
+        test!(default:   TimeFormat <- ["--time-style=default"], None;      Both => like Ok(TimeFormat::DefaultFormat(MonthNames::Locale)));
         test!(iso:       TimeFormat <- ["--time-style", "iso"], None;       Both => like Ok(TimeFormat::ISOFormat));
         test!(long_iso:  TimeFormat <- ["--time-style=long-iso"], None;     Both => like Ok(TimeFormat::LongISO));
         test!(full_iso:  TimeFormat <- ["--time-style", "full-iso"], None;  Both => like Ok(TimeFormat::FullISO));
+        test!(iso_secs:  TimeFormat <- ["--time-style=iso-seconds"], None;  Both => like Ok(TimeFormat::ISOSeconds));
 
         // Overriding
         test!(actually:  TimeFormat <- ["--time-style=default", "--time-style", "iso"], None;  Last => like Ok(TimeFormat::ISOFormat));
@@ -483,6 +1244,13 @@ mod test {
 
         // If the time-style argument is given, `TIME_STYLE` is overriding.
         test!(override_env:     TimeFormat <- ["--time-style=full-iso"], Some("long-iso".into());  Both => like Ok(TimeFormat::FullISO));
+
+        // Month names
+        test!(months:       TimeFormat <- ["--month-names=es_ES.UTF-8"], None;                      Both => like Ok(TimeFormat::DefaultFormat(MonthNames::Fixed(["ene", ..]))));
+        test!(months_dflt:  TimeFormat <- ["--time-style=default", "--month-names=de"], None;       Both => like Ok(TimeFormat::DefaultFormat(MonthNames::Fixed([_, _, "Mär", ..]))));
+        test!(months_iso:   TimeFormat <- ["--time-style=iso", "--month-names=de"], None;           Last => like Ok(TimeFormat::ISOFormat));
+        test!(months_iso_2: TimeFormat <- ["--time-style=iso", "--month-names=de"], None;           Complain => err OptionsError::Useless(&flags::MONTH_NAMES, true, &flags::TIME_STYLE));
+        test!(months_long:  Mode <- ["--month-names=de"], None;                                     Complain => err OptionsError::Useless(&flags::MONTH_NAMES, false, &flags::LONG));
     }
 
 
@@ -546,6 +1314,13 @@ mod test {
         test!(grid:          Mode <- ["--grid"], None;    Both => like Ok(Mode::Grid(GridOptions { across: false, .. })));
         test!(across:        Mode <- ["--across"], None;  Both => like Ok(Mode::Grid(GridOptions { across: true,  .. })));
         test!(gracross:      Mode <- ["-xG"], None;       Both => like Ok(Mode::Grid(GridOptions { across: true,  .. })));
+        test!(down:          Mode <- ["--down"], None;    Both => like Ok(Mode::Grid(GridOptions { across: false, .. })));
+        test!(across_down:   Mode <- ["--across", "--down"], None;  Last => like Ok(Mode::Grid(GridOptions { across: false, .. })));
+        test!(down_across:   Mode <- ["--down", "--across"], None;  Last => like Ok(Mode::Grid(GridOptions { across: true,  .. })));
+        test!(loose_grid:    Mode <- ["--grid"], None;    Both => like Ok(Mode::Grid(GridOptions { strict: false, .. })));
+        test!(strict_grid:   Mode <- ["--strict-grid"], None;  Both => like Ok(Mode::Grid(GridOptions { strict: true, .. })));
+        test!(wrap_names:    Mode <- ["--wrap-names"], None;   Both => like Ok(Mode::Grid(GridOptions { wrap_names: true, .. })));
+        test!(across_down_2: Mode <- ["--across", "--down"], None;  Complain => err OptionsError::Duplicate(Flag::Long("across"), Flag::Long("down")));
 
         // Lines views
         test!(lines:         Mode <- ["--oneline"], None;     Both => like Ok(Mode::Lines));
@@ -561,6 +1336,23 @@ mod test {
 
         // Options that do nothing with --long
         test!(long_across:   Mode <- ["--long", "--across"],   None;  Last => like Ok(Mode::Details(_)));
+        test!(tree_across:   Mode <- ["--tree", "--across"],   None;  Last => like Ok(Mode::Details(_)));
+        test!(tree_across_2: Mode <- ["--tree", "--across"],   None;  Complain => err OptionsError::Useless(&flags::ACROSS, true, &flags::TREE));
+
+        // The stat view
+        test!(stat:          Mode <- ["--stat"],               None;  Both => like Ok(Mode::Stat));
+        test!(stat_long:     Mode <- ["--long", "--stat"],     None;  Last => like Ok(Mode::Stat));
+        test!(stat_long_2:   Mode <- ["--long", "--stat"],     None;  Complain => err OptionsError::Conflict(&flags::STAT, &flags::LONG));
+
+        // Directory names for completion
+        test!(complete:      Mode <- ["--complete-dirs"],             None;  Both => like Ok(Mode::NulSeparated));
+        test!(complete_grid: Mode <- ["--grid", "--complete-dirs"],   None;  Last => like Ok(Mode::NulSeparated));
+        test!(complete_grid_2: Mode <- ["--grid", "--complete-dirs"], None;  Complain => err OptionsError::Conflict(&flags::COMPLETE_DIRS, &flags::GRID));
+
+        // A single line of totals
+        test!(oneliner:      Mode <- ["--oneliner-summary"],          None;  Both => like Ok(Mode::OnelinerSummary));
+        test!(oneliner_long: Mode <- ["--long", "--oneliner-summary"], None; Last => like Ok(Mode::OnelinerSummary));
+        test!(oneliner_long_2: Mode <- ["--long", "--oneliner-summary"], None; Complain => err OptionsError::Conflict(&flags::ONELINER_SUMMARY, &flags::LONG));
 
         // Options that do nothing without --long
         test!(just_header:   Mode <- ["--header"],   None;  Last => like Ok(Mode::Grid(_)));
@@ -568,6 +1360,9 @@ mod test {
         test!(just_inode:    Mode <- ["--inode"],    None;  Last => like Ok(Mode::Grid(_)));
         test!(just_links:    Mode <- ["--links"],    None;  Last => like Ok(Mode::Grid(_)));
         test!(just_blocks:   Mode <- ["--blocks"],   None;  Last => like Ok(Mode::Grid(_)));
+        test!(just_version:  Mode <- ["--file-version"], None;  Last => like Ok(Mode::Grid(_)));
+        test!(just_nanos:    Mode <- ["--nanoseconds"],  None;  Last => like Ok(Mode::Grid(_)));
+        test!(just_no_perms: Mode <- ["--no-permissions"], None;  Last => like Ok(Mode::Grid(_)));
         test!(just_binary:   Mode <- ["--binary"],   None;  Last => like Ok(Mode::Grid(_)));
         test!(just_bytes:    Mode <- ["--bytes"],    None;  Last => like Ok(Mode::Grid(_)));
         test!(just_numeric:  Mode <- ["--numeric"],  None;  Last => like Ok(Mode::Grid(_)));
@@ -580,6 +1375,11 @@ mod test {
         test!(just_inode_2:  Mode <- ["--inode"],    None;  Complain => err OptionsError::Useless(&flags::INODE,   false, &flags::LONG));
         test!(just_links_2:  Mode <- ["--links"],    None;  Complain => err OptionsError::Useless(&flags::LINKS,   false, &flags::LONG));
         test!(just_blocks_2: Mode <- ["--blocks"],   None;  Complain => err OptionsError::Useless(&flags::BLOCKS,  false, &flags::LONG));
+        test!(just_version2: Mode <- ["--file-version"], None;  Complain => err OptionsError::Useless(&flags::FILE_VERSION, false, &flags::LONG));
+        test!(just_age:      Mode <- ["--age"],          None;  Last => like Ok(Mode::Grid(_)));
+        test!(just_age2:     Mode <- ["--age"],          None;  Complain => err OptionsError::Useless(&flags::AGE, false, &flags::LONG));
+        test!(just_nanos_2:  Mode <- ["--nanoseconds"],  None;  Complain => err OptionsError::Useless(&flags::NANOSECONDS,  false, &flags::LONG));
+        test!(just_no_perms2: Mode <- ["--no-permissions"], None; Complain => err OptionsError::Useless(&flags::NO_PERMISSIONS, false, &flags::LONG));
         test!(just_binary_2: Mode <- ["--binary"],   None;  Complain => err OptionsError::Useless(&flags::BINARY,  false, &flags::LONG));
         test!(just_bytes_2:  Mode <- ["--bytes"],    None;  Complain => err OptionsError::Useless(&flags::BYTES,   false, &flags::LONG));
         test!(just_numeric2: Mode <- ["--numeric"],  None;  Complain => err OptionsError::Useless(&flags::NUMERIC, false, &flags::LONG));
@@ -592,7 +1392,10 @@ mod test {
         test!(lgt:           Mode <- ["--long", "--grid", "--tree"],    None;  Both => like Ok(Mode::Details(_)));
         test!(tgl:           Mode <- ["--tree", "--grid", "--long"],    None;  Both => like Ok(Mode::GridDetails(_)));
         test!(tlg:           Mode <- ["--tree", "--long", "--grid"],    None;  Both => like Ok(Mode::GridDetails(_)));
-        test!(ot:            Mode <- ["--oneline", "--tree"],           None;  Both => like Ok(Mode::Details(_)));
+        test!(ot:            Mode <- ["--oneline", "--tree"],           None;  Last => like Ok(Mode::Details(_)));
+        test!(to:            Mode <- ["--tree", "--oneline"],           None;  Last => like Ok(Mode::Lines));
+        test!(ot_2:          Mode <- ["--oneline", "--tree"],           None;  Complain => err OptionsError::Conflict(&flags::TREE, &flags::ONE_LINE));
+        test!(to_2:          Mode <- ["--tree", "--oneline"],           None;  Complain => err OptionsError::Conflict(&flags::TREE, &flags::ONE_LINE));
         test!(og:            Mode <- ["--oneline", "--grid"],           None;  Both => like Ok(Mode::Grid(_)));
         test!(tg:            Mode <- ["--tree", "--grid"],              None;  Both => like Ok(Mode::Grid(_)));
     }
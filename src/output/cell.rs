@@ -1,10 +1,11 @@
 //! The `TextCell` type for the details and lines views.
 
 use std::iter::Sum;
+use std::mem;
 use std::ops::{Add, Deref, DerefMut};
 
 use ansi_term::{Style, ANSIString, ANSIStrings};
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 
 /// An individual cell that holds text in a table, used in the details and
@@ -94,6 +95,39 @@ impl TextCell {
         (*self.width) += *other.width;
         self.contents.0.extend(other.contents.0);
     }
+
+    /// Cuts this cell down so it’s no wider than the given width, dropping
+    /// characters from the end. Each string keeps its style.
+    pub fn truncate(&mut self, width: usize) {
+        if *self.width <= width {
+            return;
+        }
+
+        let mut remaining = width;
+        let mut strings = Vec::new();
+
+        'strings: for string in &self.contents.0 {
+            let mut text = String::new();
+
+            for c in string.chars() {
+                let char_width = UnicodeWidthChar::width(c).unwrap_or(0);
+                if char_width > remaining {
+                    if ! text.is_empty() {
+                        strings.push(string.style_ref().paint(text));
+                    }
+                    break 'strings;
+                }
+
+                remaining -= char_width;
+                text.push(c);
+            }
+
+            strings.push(string.style_ref().paint(text));
+        }
+
+        self.contents = strings.into();
+        self.width = DisplayWidth::from(width - remaining);
+    }
 }
 
 
@@ -167,6 +201,44 @@ impl TextCellContents {
             .sum()
     }
 
+    /// Breaks these contents into lines that are each no wider than the
+    /// given width, keeping each string’s style. A character that’s wider
+    /// than the width on its own still gets a line to itself.
+    pub fn wrap(&self, width: usize) -> Vec<Self> {
+        let mut lines = Vec::new();
+        let mut line = Vec::new();
+        let mut remaining = width;
+
+        for string in &self.0 {
+            let mut text = String::new();
+
+            for c in string.chars() {
+                let char_width = UnicodeWidthChar::width(c).unwrap_or(0);
+                if char_width > remaining && remaining < width {
+                    if ! text.is_empty() {
+                        line.push(string.style_ref().paint(mem::take(&mut text)));
+                    }
+
+                    lines.push(Self(mem::take(&mut line)));
+                    remaining = width;
+                }
+
+                remaining = remaining.saturating_sub(char_width);
+                text.push(c);
+            }
+
+            if ! text.is_empty() {
+                line.push(string.style_ref().paint(text));
+            }
+        }
+
+        if ! line.is_empty() || lines.is_empty() {
+            lines.push(Self(line));
+        }
+
+        lines
+    }
+
     /// Promotes these contents to a full cell containing them alongside
     /// their calculated width.
     pub fn promote(self) -> TextCell {
@@ -261,6 +333,18 @@ mod width_unit_test {
         assert_eq!(*cell, 14);
     }
 
+    #[test]
+    fn wide_characters() {
+        let cell = DisplayWidth::from("日本語");
+        assert_eq!(*cell, 6);
+    }
+
+    #[test]
+    fn combining_marks() {
+        let cell = DisplayWidth::from("cafe\u{301}");
+        assert_eq!(*cell, 4);
+    }
+
     #[test]
     fn addition() {
         let cell_one = DisplayWidth::from("/usr/bin/");
@@ -62,18 +62,19 @@
 
 use std::io::{self, Write};
 use std::mem::MaybeUninit;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::vec::IntoIter as VecIntoIter;
 
 use ansi_term::Style;
 use scoped_threadpool::Pool;
 
 use crate::fs::{Dir, File};
-use crate::fs::dir_action::RecurseOptions;
+use crate::fs::dir_action::{EntryBudget, RecurseOptions};
 use crate::fs::feature::git::GitCache;
 use crate::fs::feature::xattr::{Attribute, FileAttributes};
 use crate::fs::filter::FileFilter;
-use crate::output::cell::TextCell;
+use crate::output::cell::{TextCell, TextCellContents};
+use crate::output::escape;
 use crate::output::file_name::Options as FileStyle;
 use crate::output::table::{Table, Options as TableOptions, Row as TableRow};
 use crate::output::tree::{TreeTrunk, TreeParams, TreeDepth};
@@ -101,10 +102,51 @@ pub struct Options {
     pub table: Option<TableOptions>,
 
     /// Whether to show a header line or not.
-    pub header: bool,
+    pub header: HeaderRow,
+
+    /// Whether to show a line with the totals of the summable columns
+    /// under the table.
+    pub summary_row: SummaryRow,
+
+    /// Where to show each symlink’s target.
+    pub link_targets: LinkTargets,
 
     /// Whether to show each file’s extended attributes.
-    pub xattr: bool,
+    pub xattr: Xattrs,
+}
+
+/// Whether to show a line naming each column above the table.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum HeaderRow {
+    Show,
+    Off,
+}
+
+/// Whether to show a line totalling up the summable columns under the
+/// table.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum SummaryRow {
+    Show,
+    Off,
+}
+
+/// Where to show the target of each symlink.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum LinkTargets {
+
+    /// After the file name, with an arrow in between.
+    AfterArrow,
+
+    /// In a column of its own after the file names, lined up.
+    Column,
+}
+
+/// Whether to list each file’s extended attributes underneath it. Files
+/// with any get an `@` in their permissions either way.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Xattrs {
+    Show,
+    Off,
 }
 
 
@@ -118,7 +160,11 @@ pub struct Render<'a> {
     /// Whether to recurse through directories with a tree view, and if so,
     /// which options to use. This field is only relevant here if the `tree`
     /// field of the RecurseOptions is `true`.
-    pub recurse: Option<RecurseOptions>,
+    pub recurse: Option<&'a RecurseOptions>,
+
+    /// How many more entries a tree is allowed to list, if there’s a limit,
+    /// shared with the rest of the listing.
+    pub budget: Option<&'a EntryBudget>,
 
     /// How to sort and filter the files after getting their details.
     pub filter: &'a FileFilter,
@@ -127,6 +173,10 @@ pub struct Render<'a> {
     pub git_ignoring: bool,
 
     pub git: Option<&'a GitCache>,
+
+    /// Entries in the directory being listed that were read from it but
+    /// couldn’t then be statted, which get rows of their own.
+    pub unstatted: Vec<(PathBuf, io::Error)>,
 }
 
 
@@ -163,23 +213,76 @@ impl<'a> Render<'a> {
 
             let mut table = Table::new(table, self.git, self.theme);
 
-            if self.opts.header {
+            if self.opts.header == HeaderRow::Show {
                 let header = table.header_row();
                 table.add_widths(&header);
                 rows.push(self.render_header(header));
             }
 
+            // These go before the files, as they do for each directory in a
+            // tree, so that the last file can still end its branch.
+            for (path, error) in &self.unstatted {
+                rows.push(self.render_unstatted(Some(&table), path, error, TreeParams::new(self.starting_depth(), false)));
+            }
+
             // This is weird, but I can’t find a way around it:
             // https://internals.rust-lang.org/t/should-option-mut-t-implement-copy/3715/6
             let mut table = Some(table);
-            self.add_files_to_table(&mut pool, &mut table, &mut rows, &self.files, TreeDepth::root());
+            self.add_files_to_table(&mut pool, &mut table, &mut rows, &self.files, self.starting_depth(), self.dir.map(|d| d.path.as_path()));
+
+            let mut table = table.unwrap();
+            let git_tally = table.git_tally();
+
+            // Each file’s share of the total size, its size next to the
+            // largest, and the number of symlinks pointing at it can only
+            // be worked out once every file has been added, so they get
+            // filled in last.
+            for cells in rows.iter_mut().filter_map(|row| row.cells.as_mut()) {
+                table.add_relative_cells(cells);
+                table.add_widths(cells);
+            }
 
-            for row in self.iterate_with_table(table.unwrap(), rows) {
-                writeln!(w, "{}", row.strings())?
+            if self.opts.summary_row == SummaryRow::Show {
+                let summary = table.summary_row();
+                table.add_widths(&summary);
+                rows.push(self.render_summary(summary));
+            }
+
+            if self.opts.link_targets == LinkTargets::Column {
+                align_link_targets(&mut rows);
+            }
+
+            let mut header_rule = if self.opts.header == HeaderRow::Show {
+                let name_width = rows.iter().map(|row| row.tree.width() + *row.name.width).max().unwrap_or(0);
+                table.header_rule(name_width)
+            }
+            else {
+                None
+            };
+
+            for row in self.iterate_with_table(table, rows) {
+                writeln!(w, "{}", row.strings())?;
+
+                // The header is always the first row.
+                if let Some(rule) = header_rule.take() {
+                    writeln!(w, "{}", rule.strings())?;
+                }
+            }
+
+            if let Some(tally) = git_tally {
+                writeln!(w, "{}", self.theme.ui.punctuation.paint(tally.summary()))?;
+
+                if let Some(note) = tally.skipped_note() {
+                    writeln!(w, "{}", self.theme.ui.punctuation.paint(note))?;
+                }
             }
         }
         else {
-            self.add_files_to_table(&mut pool, &mut None, &mut rows, &self.files, TreeDepth::root());
+            for (path, error) in &self.unstatted {
+                rows.push(self.render_unstatted(None, path, error, TreeParams::new(self.starting_depth(), false)));
+            }
+
+            self.add_files_to_table(&mut pool, &mut None, &mut rows, &self.files, self.starting_depth(), self.dir.map(|d| d.path.as_path()));
 
             for row in self.iterate(rows) {
                 writeln!(w, "{}", row.strings())?
@@ -191,7 +294,7 @@ impl<'a> Render<'a> {
 
     /// Adds files to the table, possibly recursively. This is easily
     /// parallelisable, and uses a pool of threads.
-    fn add_files_to_table<'dir>(&self, pool: &mut Pool, table: &mut Option<Table<'a>>, rows: &mut Vec<Row>, src: &[File<'dir>], depth: TreeDepth) {
+    fn add_files_to_table<'dir>(&self, pool: &mut Pool, table: &mut Option<Table<'a>>, rows: &mut Vec<Row>, src: &[File<'dir>], depth: TreeDepth, root: Option<&Path>) {
         use std::sync::{Arc, Mutex};
         use log::*;
         use crate::fs::feature::xattr;
@@ -237,7 +340,7 @@ impl<'a> Render<'a> {
                                 xattrs.extend(xs);
                             }
                             Err(e) => {
-                                if self.opts.xattr {
+                                if self.opts.xattr == Xattrs::Show {
                                     errors.push((e, None));
                                 }
                                 else {
@@ -250,13 +353,15 @@ impl<'a> Render<'a> {
                     let table_row = table.as_ref()
                                          .map(|t| t.row_for_file(file, ! xattrs.is_empty()));
 
-                    if ! self.opts.xattr {
+                    if self.opts.xattr == Xattrs::Off {
                         xattrs.clear();
                     }
 
+                    // The roots of a tree were named on the command-line, so
+                    // they get descended into whatever their names are.
                     let mut dir = None;
                     if let Some(r) = self.recurse {
-                        if file.is_directory() && r.tree && ! r.is_too_deep(depth.0) {
+                        if file.is_directory() && r.tree && ! r.is_too_deep(depth.0) && (depth.0 == 0 || r.descends_into(&file.name)) {
                             match file.to_dir() {
                                 Ok(d) => {
                                     dir = Some(d);
@@ -276,29 +381,69 @@ impl<'a> Render<'a> {
 
         // this is safe because all entries have been initialized above
         let mut file_eggs = unsafe { std::mem::transmute::<_, Vec<Egg<'_>>>(file_eggs) };
-        self.filter.sort_files(&mut file_eggs);
+        self.filter.sort_and_limit(&mut file_eggs);
+
+        // Only the first few subdirectories get descended into when there’s
+        // a width limit, with a note above the rest saying how many were
+        // left out. Like with `--descend-only`, the roots are exempt.
+        if let Some(r) = self.recurse {
+            let mut skipped = 0;
+            for (index, egg) in file_eggs.iter_mut().filter(|e| e.dir.is_some()).enumerate() {
+                if depth.0 > 0 && r.is_too_wide(index) {
+                    egg.dir = None;
+                    skipped += 1;
+                }
+            }
+
+            if skipped > 0 {
+                rows.push(self.render_note(r.width_note(skipped), TreeParams::new(depth, false)));
+            }
+        }
+
+        // The roots were named on the command-line, so they don’t count
+        // towards the limit on how many entries get listed.
+        if let Some(budget) = self.budget {
+            if depth.0 > 0 {
+                budget.spend(&mut file_eggs);
+            }
+        }
 
         for (tree_params, egg) in depth.iterate_over(file_eggs.into_iter()) {
             let mut files = Vec::new();
-            let mut errors = egg.errors;
+            let errors = egg.errors;
 
             if let (Some(ref mut t), Some(row)) = (table.as_mut(), egg.table_row.as_ref()) {
                 t.add_widths(row);
+                t.add_to_git_tally(egg.file);
+                t.add_to_totals(egg.file);
             }
 
-            let file_name = self.file_style.for_file(egg.file, self.theme)
-                                .with_link_paths()
-                                .paint()
-                                .promote();
+            let (file_name, link_target) = if self.opts.link_targets == LinkTargets::Column {
+                let file_name = self.file_style.for_file(egg.file, self.theme);
+                (file_name.paint().promote(), file_name.paint_link_target().map(TextCellContents::promote))
+            }
+            else {
+                let file_name = self.file_style.for_file(egg.file, self.theme)
+                                    .with_link_paths()
+                                    .paint()
+                                    .promote();
+                (file_name, None)
+            };
 
             let row = Row {
-                tree:   tree_params,
-                cells:  egg.table_row,
-                name:   file_name,
+                tree:        tree_params,
+                cells:       egg.table_row,
+                name:        file_name,
+                link_target,
             };
 
             rows.push(row);
 
+            // An entry that was read from its directory but couldn’t then
+            // be statted — it was deleted in the meantime, say — still gets
+            // a row of its own, but with nothing in any of its columns.
+            let mut unstatted = Vec::new();
+
             if let Some(ref dir) = egg.dir {
                 for file_to_add in dir.files(self.filter.dot_filter, self.git, self.git_ignoring) {
                     match file_to_add {
@@ -306,12 +451,15 @@ impl<'a> Render<'a> {
                             files.push(f);
                         }
                         Err((path, e)) => {
-                            errors.push((e, Some(path)));
+                            unstatted.push((path, e));
                         }
                     }
                 }
 
-                self.filter.filter_child_files(&mut files);
+                // Paths get matched from the directory the tree started at,
+                // which is either the one being listed or a root of the tree.
+                let root = root.unwrap_or(&egg.file.path);
+                self.filter.filter_child_files(&mut files, root);
 
                 if ! files.is_empty() {
                     for xattr in egg.xattrs {
@@ -322,32 +470,66 @@ impl<'a> Render<'a> {
                         rows.push(self.render_error(&error, TreeParams::new(depth.deeper(), false), path));
                     }
 
-                    self.add_files_to_table(pool, table, rows, &files, depth.deeper());
+                    for (path, error) in unstatted {
+                        rows.push(self.render_unstatted(table.as_ref(), &path, &error, TreeParams::new(depth.deeper(), false)));
+                    }
+
+                    self.add_files_to_table(pool, table, rows, &files, depth.deeper(), Some(root));
                     continue;
                 }
             }
 
             let count = egg.xattrs.len();
             for (index, xattr) in egg.xattrs.into_iter().enumerate() {
-                let params = TreeParams::new(depth.deeper(), errors.is_empty() && index == count - 1);
+                let params = TreeParams::new(depth.deeper(), errors.is_empty() && unstatted.is_empty() && index == count - 1);
                 let r = self.render_xattr(&xattr, params);
                 rows.push(r);
             }
 
             let count = errors.len();
             for (index, (error, path)) in errors.into_iter().enumerate() {
-                let params = TreeParams::new(depth.deeper(), index == count - 1);
+                let params = TreeParams::new(depth.deeper(), unstatted.is_empty() && index == count - 1);
                 let r = self.render_error(&error, params, path);
                 rows.push(r);
             }
+
+            let count = unstatted.len();
+            for (index, (path, error)) in unstatted.into_iter().enumerate() {
+                let params = TreeParams::new(depth.deeper(), index == count - 1);
+                let r = self.render_unstatted(table.as_ref(), &path, &error, params);
+                rows.push(r);
+            }
+        }
+    }
+
+    /// The depth to list the files at. A tree’s roots are usually at the
+    /// top, but when a directory gets a tree of its own under a heading, its
+    /// contents are branches one level down.
+    fn starting_depth(&self) -> TreeDepth {
+        match (self.dir, self.recurse) {
+            (Some(_), Some(r)) if r.tree  => TreeDepth::root().deeper(),
+            _                             => TreeDepth::root(),
         }
     }
 
     pub fn render_header(&self, header: TableRow) -> Row {
+        let link_target = if self.opts.link_targets == LinkTargets::Column { Some(TextCell::paint_str(self.theme.ui.header, "Target")) }
+                                                                            else { None };
+
         Row {
             tree:     TreeParams::new(TreeDepth::root(), false),
             cells:    Some(header),
             name:     TextCell::paint_str(self.theme.ui.header, "Name"),
+            link_target,
+        }
+    }
+
+    fn render_summary(&self, summary: TableRow) -> Row {
+        Row {
+            tree:     TreeParams::new(TreeDepth::root(), false),
+            cells:    Some(summary),
+            name:     TextCell::paint_str(self.theme.ui.header, "Total"),
+            link_target: None,
         }
     }
 
@@ -362,23 +544,51 @@ impl<'a> Render<'a> {
 
         // TODO: broken_symlink() doesn’t quite seem like the right name for
         // the style that’s being used here. Maybe split it in two?
-        let name = TextCell::paint(self.theme.broken_symlink(), error_message);
-        Row { cells: None, name, tree }
+        // The path gets escaped like a file name would, so a newline in it
+        // can’t split the row in two.
+        let mut bits = Vec::new();
+        escape(error_message, &mut bits, self.theme.broken_symlink(), self.theme.broken_control_char());
+        let name = TextCellContents::from(bits).promote();
+        Row { cells: None, name, tree, link_target: None }
+    }
+
+    /// A row for an entry that couldn’t be statted, with a dash in each of
+    /// the table’s columns and its name painted like an error.
+    fn render_unstatted(&self, table: Option<&Table<'a>>, path: &Path, error: &io::Error, tree: TreeParams) -> Row {
+        use log::*;
+        use crate::output::file_name::Colours;
+
+        warn!("Could not stat {:?}: {}", path, error);
+
+        let mut bits = Vec::new();
+        escape(File::filename(path), &mut bits, self.theme.broken_symlink(), self.theme.broken_control_char());
+        let name = TextCellContents::from(bits).promote();
+        let cells = table.map(Table::row_for_unstatted);
+        Row { cells, name, tree, link_target: None }
+    }
+
+    fn render_note(&self, note: String, tree: TreeParams) -> Row {
+        let name = TextCell::paint(self.theme.ui.punctuation, note);
+        Row { cells: None, name, tree, link_target: None }
     }
 
     fn render_xattr(&self, xattr: &Attribute, tree: TreeParams) -> Row {
-        let name = TextCell::paint(self.theme.ui.perms.attribute, format!("{} (len {})", xattr.name, xattr.size));
-        Row { cells: None, name, tree }
+        use crate::output::file_name::Colours;
+
+        let mut bits = Vec::new();
+        escape(format!("{} (len {})", xattr.name, xattr.size), &mut bits, self.theme.ui.perms.attribute, self.theme.control_char());
+        let name = TextCellContents::from(bits).promote();
+        Row { cells: None, name, tree, link_target: None }
     }
 
     pub fn render_file(&self, cells: TableRow, name: TextCell, tree: TreeParams) -> Row {
-        Row { cells: Some(cells), name, tree }
+        Row { cells: Some(cells), name, tree, link_target: None }
     }
 
     pub fn iterate_with_table(&'a self, table: Table<'a>, rows: Vec<Row>) -> TableIter<'a> {
         TableIter {
             tree_trunk: TreeTrunk::default(),
-            total_width: table.widths().total(),
+            total_width: table.total_width(),
             table,
             inner: rows.into_iter(),
             tree_style: self.theme.ui.punctuation,
@@ -411,6 +621,33 @@ pub struct Row {
 
     /// Information used to determine which symbols to display in a tree.
     pub tree: TreeParams,
+
+    /// The target of this file, if it’s a symlink and targets are being
+    /// shown in a column of their own.
+    pub link_target: Option<TextCell>,
+}
+
+
+/// Moves each row’s link target onto the end of its name, padding the names
+/// so that the targets line up in a column of their own. Only the rows for
+/// files count towards the width, so long attribute or error lines don’t
+/// push the targets across.
+fn align_link_targets(rows: &mut [Row]) {
+    let name_width = |row: &Row| row.tree.width() + *row.name.width;
+
+    let width = rows.iter()
+                    .filter(|row| row.cells.is_some() || row.link_target.is_some())
+                    .map(name_width)
+                    .max()
+                    .unwrap_or(0);
+
+    for row in rows {
+        if let Some(target) = row.link_target.take() {
+            let padding = width - name_width(row) + 1;
+            row.name.add_spaces(padding);
+            row.name.append(target);
+        }
+    }
 }
 
 
@@ -483,3 +720,348 @@ impl Iterator for Iter {
         })
     }
 }
+
+
+/// The settings the tests below have in common: no colours, file names
+/// without any decoration, a sort by name, and no extra details.
+#[cfg(test)]
+mod fixture {
+    use super::*;
+    use crate::fs::DotFilter;
+    use crate::fs::filter::{SortField, SortOrder, ModifiedWindow, IgnorePatterns, GitIgnore, IgnoreFiles, TypeOrder, AtimeWarning, DotfileOrder};
+    use crate::output::file_name::{Classify, ShowIcons, SymlinkColours, MarkEmpty, MarkUnreadable, SymlinkPath, QuoteNames, BidiIsolate};
+    use crate::theme::Options as ThemeOptions;
+
+    pub fn theme() -> Theme {
+        ThemeOptions::plain().to_theme(false)
+    }
+
+    pub fn file_style() -> FileStyle {
+        FileStyle {
+            classify: Classify::JustFilenames,
+            show_icons: ShowIcons::Off,
+            symlink_colours: SymlinkColours::Link,
+            mark_empty: MarkEmpty::Off,
+            mark_unreadable: MarkUnreadable::Off,
+            symlink_path: SymlinkPath::Plain,
+            relative_to: None,
+            quote_names: QuoteNames::Off,
+            bidi_isolate: BidiIsolate::Off,
+        }
+    }
+
+    pub fn filter() -> FileFilter {
+        FileFilter {
+            list_dirs_first: false,
+            dotfile_order: DotfileOrder::Mixed,
+            sort_field: SortField::default(),
+            secondary_sort: None,
+            sort_order: SortOrder::Natural,
+            reverse: false,
+            type_order: TypeOrder::Reversible,
+            atime_warning: AtimeWarning::Off,
+            only_dirs: false,
+            modified_window: ModifiedWindow::default(),
+            dot_filter: DotFilter::JustFiles,
+            ignore_patterns: IgnorePatterns::empty(),
+            git_ignore: GitIgnore::Off,
+            ignore_files: IgnoreFiles::Off,
+            max_entries: None,
+        }
+    }
+
+    pub fn opts(table: Option<TableOptions>) -> Options {
+        Options { table, header: HeaderRow::Off, summary_row: SummaryRow::Off, link_targets: LinkTargets::AfterArrow, xattr: Xattrs::Off }
+    }
+
+    /// The files with the given names in the directory, with it as their
+    /// parent.
+    pub fn files_in<'dir>(dir: &'dir Dir, names: &[&str]) -> Vec<File<'dir>> {
+        names.iter()
+             .map(|name| File::from_args(dir.path.join(name), dir, None).unwrap())
+             .collect()
+    }
+
+    /// Renders the given files in the details view, as a tree if there
+    /// are options for recursing, and returns the lines that come out.
+    pub fn render(files: Vec<File<'_>>, filter: &FileFilter, opts: &Options, recurse: Option<&RecurseOptions>) -> String {
+        let theme = theme();
+        let file_style = &file_style();
+
+        let mut output = Vec::new();
+        Render { dir: None, files, theme: &theme, file_style, opts, recurse, budget: None, filter, git_ignoring: false, git: None, unstatted: Vec::new() }
+            .render(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+}
+
+
+#[cfg(test)]
+mod reverse_test {
+    use super::*;
+    use super::fixture::*;
+    use crate::fs::DotFilter;
+    use crate::output::lines;
+    use crate::fs::test::TempDir;
+
+    /// `--reverse` should put a directory’s children in the same order
+    /// whether they get listed flat or as the first level of a tree.
+    #[test]
+    fn tree_reverses_like_flat() {
+        let path = TempDir::new("reverse");
+        std::fs::create_dir_all(path.join("bravo")).unwrap();
+        std::fs::write(path.join("bravo").join("inner"), b"").unwrap();
+        for name in &[ "charlie", "alpha", "delta" ] {
+            std::fs::write(path.join(name), b"").unwrap();
+        }
+
+        let filter = &FileFilter { reverse: true, .. filter() };
+
+        let dir = Dir::read_dir(path.clone()).unwrap();
+        let children = dir.files(DotFilter::JustFiles, None, false)
+                          .collect::<Result<Vec<_>, _>>()
+                          .unwrap();
+
+        let mut flat = Vec::new();
+        lines::Render { files: children, theme: &theme(), file_style: &file_style(), filter }.render(&mut flat).unwrap();
+        let flat = String::from_utf8(flat).unwrap();
+        let flat = flat.lines().collect::<Vec<_>>();
+
+        let recurse = RecurseOptions { tree: true, max_depth: None, max_total: None, separate_trees: false, descend_only: None, max_width: None };
+        let root = File::from_args(path.clone(), None, None).unwrap();
+
+        let tree = render(vec![ root ], filter, &opts(None), Some(&recurse));
+        let top_level = tree.lines()
+                            .filter(|line| line.starts_with('├') || line.starts_with('└'))
+                            .map(|line| line.rsplit(' ').next().unwrap())
+                            .collect::<Vec<_>>();
+
+        assert_eq!(flat, vec![ "delta", "charlie", "bravo", "alpha" ]);
+        assert_eq!(top_level, flat);
+    }
+}
+
+
+#[cfg(test)]
+mod descend_only_test {
+    use super::*;
+    use super::fixture::*;
+    use crate::fs::test::TempDir;
+
+    /// Directories that don’t match `--descend-only` should still be in
+    /// the tree, just without anything underneath them.
+    #[test]
+    fn only_matching_dirs_descended() {
+        let path = TempDir::new("descend-only");
+        std::fs::create_dir_all(path.join("src")).unwrap();
+        std::fs::create_dir_all(path.join("target").join("debug")).unwrap();
+        std::fs::write(path.join("src").join("main.rs"), b"").unwrap();
+
+        let recurse = RecurseOptions { tree: true, max_depth: None, max_total: None, separate_trees: false, descend_only: Some(glob::Pattern::new("src").unwrap()), max_width: None };
+        let root = File::from_args(path.clone(), None, None).unwrap();
+
+        let tree = render(vec![ root ], &filter(), &opts(None), Some(&recurse));
+        let names = tree.lines()
+                        .skip(1)
+                        .map(|line| line.rsplit(' ').next().unwrap())
+                        .collect::<Vec<_>>();
+
+        assert_eq!(names, vec![ "src", "main.rs", "target" ]);
+    }
+}
+
+
+#[cfg(test)]
+mod max_width_test {
+    use super::*;
+    use super::fixture::*;
+    use crate::fs::test::TempDir;
+
+    /// Past the `--max-width` limit, directories should still be in the
+    /// tree, just without anything underneath them.
+    #[test]
+    fn only_first_dirs_descended() {
+        let path = TempDir::new("max-width");
+        for i in 0 .. 10 {
+            let subdir = path.join(format!("d{}", i));
+            std::fs::create_dir_all(&subdir).unwrap();
+            std::fs::write(subdir.join("f"), b"").unwrap();
+        }
+
+        let recurse = RecurseOptions { tree: true, max_depth: None, max_total: None, separate_trees: false, descend_only: None, max_width: Some(3) };
+        let root = File::from_args(path.clone(), None, None).unwrap();
+
+        let tree = render(vec![ root ], &filter(), &opts(None), Some(&recurse));
+        let names = tree.lines()
+                        .skip(1)
+                        .map(|line| line.rsplit(' ').next().unwrap())
+                        .collect::<Vec<_>>();
+
+        assert_eq!(names, vec![ "into]", "d0", "f", "d1", "f", "d2", "f", "d3", "d4", "d5", "d6", "d7", "d8", "d9" ]);
+        assert!(tree.contains("[7 more directories not descended into]"));
+    }
+}
+
+
+#[cfg(test)]
+#[cfg(unix)]
+mod link_target_test {
+    use super::*;
+    use super::fixture::*;
+    use crate::fs::test::TempDir;
+
+    #[test]
+    fn target_in_its_own_column() {
+        let path = TempDir::new("link-target");
+        std::fs::write(path.join("destination"), b"").unwrap();
+        std::os::unix::fs::symlink("destination", path.join("ln")).unwrap();
+        std::os::unix::fs::symlink("nowhere", path.join("broken")).unwrap();
+
+        let dir = Dir::read_dir(path.clone()).unwrap();
+        let files = files_in(&dir, &[ "broken", "destination", "ln" ]);
+
+        let opts = &Options { link_targets: LinkTargets::Column, .. opts(None) };
+        let render = Render { dir: None, files, theme: &theme(), file_style: &file_style(), opts, recurse: None, filter: &filter(), git_ignoring: false, git: None, budget: None, unstatted: Vec::new() };
+
+        let mut rows = Vec::new();
+        render.add_files_to_table(&mut Pool::new(1), &mut None, &mut rows, &render.files, TreeDepth::root(), None);
+        align_link_targets(&mut rows);
+
+        let lines = render.iterate(rows)
+                          .map(|row| row.strings().to_string())
+                          .collect::<Vec<_>>();
+
+        assert_eq!(lines, vec![ "broken nowhere", "destination", "ln     destination" ]);
+        assert!(lines.iter().all(|line| ! line.contains("->")));
+    }
+}
+
+
+#[cfg(test)]
+#[cfg(unix)]
+mod size_percent_test {
+    use super::*;
+    use super::fixture::*;
+    use crate::output::table::Columns;
+    use crate::fs::test::TempDir;
+
+    #[test]
+    fn shares_of_the_total() {
+        let path = TempDir::new("size-percent");
+        std::fs::write(path.join("quarter"), [ 0; 25 ]).unwrap();
+        std::fs::write(path.join("rest"), [ 0; 75 ]).unwrap();
+
+        let table = TableOptions::plain(Columns { size_percent: true, .. Columns::none() });
+
+        let dir = Dir::read_dir(path.clone()).unwrap();
+        let files = files_in(&dir, &[ "quarter", "rest" ]);
+
+        let output = render(files, &filter(), &opts(Some(table)), None);
+
+        assert_eq!(output.lines().collect::<Vec<_>>(), vec![ "25% quarter", "75% rest" ]);
+    }
+}
+
+
+#[cfg(test)]
+#[cfg(unix)]
+mod control_char_test {
+    use super::*;
+    use super::fixture::*;
+    use crate::output::table::Columns;
+    use crate::fs::test::TempDir;
+
+    #[test]
+    fn newline_in_name() {
+        let path = TempDir::new("newline-name");
+        std::fs::write(path.join("a\nb"), b"abc").unwrap();
+        std::fs::write(path.join("c"), b"").unwrap();
+
+        let table = TableOptions::plain(Columns { filesize: true, .. Columns::none() });
+
+        let dir = Dir::read_dir(path.clone()).unwrap();
+        let files = files_in(&dir, &[ "a\nb", "c" ]);
+
+        let output = render(files, &filter(), &opts(Some(table)), None);
+
+        assert_eq!(output.lines().collect::<Vec<_>>(), vec![ "3 a\\nb", "0 c" ]);
+    }
+}
+
+
+#[cfg(test)]
+#[cfg(unix)]
+mod unstatted_test {
+    use super::*;
+    use super::fixture::*;
+    use std::os::unix::fs::PermissionsExt;
+    use crate::output::file_name::Colours;
+    use crate::output::table::Columns;
+    use crate::theme::{Options as ThemeOptions, UseColours};
+    use crate::fs::test::TempDir;
+
+    fn render_with(use_colours: UseColours, path: &Path) -> (Vec<String>, TextCell, Style) {
+        let theme = ThemeOptions { use_colours, .. ThemeOptions::plain() }.to_theme(false);
+
+        let table_options = TableOptions::plain(Columns { permissions: true, filesize: true, .. Columns::none() });
+
+        let opts = &opts(Some(table_options));
+        let render = Render { dir: None, files: Vec::new(), theme: &theme, file_style: &file_style(), opts, recurse: None, filter: &filter(), git_ignoring: false, git: None, budget: None, unstatted: Vec::new() };
+
+        // Statting can’t be made to fail reliably — permissions don’t stop
+        // root — so the failure gets handed over directly instead.
+        let file = File::from_args(path.join("present"), None, None).unwrap();
+        let error = io::Error::new(io::ErrorKind::NotFound, "vanished");
+
+        let mut table = Table::new(opts.table.as_ref().unwrap(), None, &theme);
+        let present = render.render_file(table.row_for_file(&file, false), TextCell::paint_str(Style::default(), "present"), TreeParams::new(TreeDepth::root(), false));
+        let gone = render.render_unstatted(Some(&table), &path.join("gone"), &error, TreeParams::new(TreeDepth::root(), false));
+        let gone_name = gone.name.clone();
+
+        let rows = vec![ present, gone ];
+        for cells in rows.iter().filter_map(|row| row.cells.as_ref()) {
+            table.add_widths(cells);
+        }
+
+        let lines = render.iterate_with_table(table, rows)
+                          .map(|row| row.strings().to_string())
+                          .collect();
+
+        (lines, gone_name, theme.broken_symlink())
+    }
+
+    #[test]
+    fn dashes_and_error_name() {
+        let path = TempDir::new("unstatted");
+        std::fs::write(path.join("present"), b"abc").unwrap();
+        std::fs::set_permissions(path.join("present"), std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        let (lines, _, _) = render_with(UseColours::Never, &path);
+        assert_eq!(lines, vec![ ".rw-r--r-- 3 present", "-          - gone" ]);
+
+        let (_, name, error_style) = render_with(UseColours::Always, &path);
+        assert_ne!(error_style, Style::default());
+        assert_eq!(name, TextCell::paint_str(error_style, "gone"));
+    }
+
+    #[test]
+    fn rows_in_a_flat_listing() {
+        let path = TempDir::new("unstatted-flat");
+        std::fs::write(path.join("present"), b"abc").unwrap();
+        std::fs::set_permissions(path.join("present"), std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        let dir = Dir::read_dir(path.clone()).unwrap();
+        let files = vec![ File::from_args(path.join("present"), Some(&dir), None).unwrap() ];
+        let unstatted = vec![ (path.join("gone"), io::Error::new(io::ErrorKind::NotFound, "vanished")) ];
+
+        let table_options = TableOptions::plain(Columns { permissions: true, filesize: true, .. Columns::none() });
+        let opts = &opts(Some(table_options));
+        let theme = theme();
+
+        let mut output = Vec::new();
+        Render { dir: Some(&dir), files, theme: &theme, file_style: &file_style(), opts, recurse: None, filter: &filter(), git_ignoring: false, git: None, budget: None, unstatted }
+            .render(&mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "-          - gone\n.rw-r--r-- 3 present\n");
+    }
+}
@@ -1,5 +1,5 @@
 use std::fmt::Debug;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
 use ansi_term::{ANSIString, Style};
 
@@ -11,7 +11,7 @@ use crate::output::render::FiletypeColours;
 
 
 /// Basically a file name factory.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub struct Options {
 
     /// Whether to append file class characters to file names.
@@ -19,13 +19,37 @@ pub struct Options {
 
     /// Whether to prepend icon characters before file names.
     pub show_icons: ShowIcons,
+
+    /// Whether to colour symlinks like the files they point to.
+    pub symlink_colours: SymlinkColours,
+
+    /// Whether to mark empty files and directories.
+    pub mark_empty: MarkEmpty,
+
+    /// Whether to mark files the current user can’t read.
+    pub mark_unreadable: MarkUnreadable,
+
+    /// How to paint the directories in a symlink’s target path.
+    pub symlink_path: SymlinkPath,
+
+    /// The directory to show each file’s path relative to, if any, instead
+    /// of just its name.
+    pub relative_to: Option<RelativeTo>,
+
+    /// Whether to wrap file names in quotes so they can be pasted into a
+    /// shell.
+    pub quote_names: QuoteNames,
+
+    /// Whether to keep right-to-left names from reordering the text
+    /// around them.
+    pub bidi_isolate: BidiIsolate,
 }
 
 impl Options {
 
     /// Create a new `FileName` that prints the given file’s name, painting it
     /// with the remaining arguments.
-    pub fn for_file<'a, 'dir, C>(self, file: &'a File<'dir>, colours: &'a C) -> FileName<'a, 'dir, C> {
+    pub fn for_file<'a, 'dir, C>(&'a self, file: &'a File<'dir>, colours: &'a C) -> FileName<'a, 'dir, C> {
         FileName {
             file,
             colours,
@@ -61,8 +85,8 @@ pub enum Classify {
     JustFilenames,
 
     /// Add a character after the file name depending on what class of file
-    /// it is.
-    AddFileIndicators,
+    /// it is, for the classes in the set.
+    AddFileIndicators(Indicators),
 }
 
 impl Default for Classify {
@@ -72,6 +96,36 @@ impl Default for Classify {
 }
 
 
+/// Which classes of file get a character after their names, as a set of
+/// bits.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Indicators(u8);
+
+impl Indicators {
+    pub const DIRECTORY:  Self = Self(1 << 0);
+    pub const EXECUTABLE: Self = Self(1 << 1);
+    pub const SYMLINK:    Self = Self(1 << 2);
+    pub const PIPE:       Self = Self(1 << 3);
+    pub const SOCKET:     Self = Self(1 << 4);
+
+    /// The set with nothing in it.
+    pub const NONE: Self = Self(0);
+
+    /// The set with every class in it, which is what `--classify` uses.
+    pub const ALL: Self = Self(0b1_1111);
+
+    /// This set with the other set’s classes added to it.
+    pub fn with(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Whether every class in the other set is in this one.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+
 /// Whether and how to show icons.
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
 pub enum ShowIcons {
@@ -85,6 +139,150 @@ pub enum ShowIcons {
 }
 
 
+/// Whether to add a marker after files and directories that have nothing
+/// in them.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum MarkEmpty {
+
+    /// Just display the file names.
+    Off,
+
+    /// Add a marker after zero-byte files and directories with no entries.
+    AddMarker,
+}
+
+impl Default for MarkEmpty {
+    fn default() -> Self {
+        Self::Off
+    }
+}
+
+
+/// Whether to quote file names so they’re safe to paste into a shell.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum QuoteNames {
+
+    /// Just display the file names.
+    Off,
+
+    /// Wrap each name, along with any path in front of it, in single
+    /// quotes, and leave it uncoloured.
+    Shell,
+}
+
+impl Default for QuoteNames {
+    fn default() -> Self {
+        Self::Off
+    }
+}
+
+
+/// Whether to wrap file names in Unicode bidirectional isolates. Without
+/// them, a name in a right-to-left script can make the terminal reorder the
+/// columns next to it, as the direction of the name carries on past its end.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum BidiIsolate {
+
+    /// Just display the file names.
+    Off,
+
+    /// Put a first strong isolate before each name, and a pop directional
+    /// isolate after it, so the name’s direction stays inside it.
+    Isolate,
+}
+
+impl Default for BidiIsolate {
+    fn default() -> Self {
+        Self::Off
+    }
+}
+
+
+/// Whether to paint the names of files that the current user can’t read
+/// differently.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum MarkUnreadable {
+
+    /// Paint them like any other file.
+    Off,
+
+    /// Paint them with the unreadable overlay on top of their usual style.
+    AddOverlay,
+}
+
+impl Default for MarkUnreadable {
+    fn default() -> Self {
+        Self::Off
+    }
+}
+
+
+/// Which colour to paint the names of symlinks.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum SymlinkColours {
+
+    /// Paint symlinks in the symlink colour, whatever they point to.
+    Link,
+
+    /// Paint symlinks in the colour of the file they point to, so a link to
+    /// a directory looks like a directory. Broken links still get painted
+    /// in the link colour.
+    Target,
+}
+
+impl Default for SymlinkColours {
+    fn default() -> Self {
+        Self::Link
+    }
+}
+
+
+/// How to paint the path leading up to a symlink’s target.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum SymlinkPath {
+
+    /// Paint the whole path in the symlink path colour.
+    Plain,
+
+    /// Paint each component of the path in the colour of its own file
+    /// type, so directories look like directories. This has to look up
+    /// every component on the filesystem.
+    ColourComponents,
+
+    /// Leave the path out, and show just the target’s own name.
+    Hidden,
+}
+
+impl Default for SymlinkPath {
+    fn default() -> Self {
+        Self::Plain
+    }
+}
+
+
+/// The directory to show paths relative to, already made absolute, along
+/// with the current directory it was resolved against. Relative file paths
+/// get resolved against that same directory, rather than looking it up
+/// again for every file.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct RelativeTo {
+    pub base: PathBuf,
+    pub current_dir: PathBuf,
+}
+
+impl RelativeTo {
+
+    /// Resolves the given directory against the current one. If the
+    /// current directory can’t be found, relative paths get left as they
+    /// are, which at least keeps them consistent with each other.
+    pub fn new(base: &Path) -> Self {
+        let current_dir = std::env::current_dir().unwrap_or_default();
+        let base = lexical_absolute(base, &current_dir);
+        Self { base, current_dir }
+    }
+}
+
+
 /// A **file name** holds all the information necessary to display the name
 /// of the given file. This is used in all of the views.
 pub struct FileName<'a, 'dir, C> {
@@ -101,7 +299,7 @@ pub struct FileName<'a, 'dir, C> {
     /// How to handle displaying links.
     link_style: LinkStyle,
 
-    options: Options,
+    options: &'a Options,
 }
 
 impl<'a, 'dir, C> FileName<'a, 'dir, C> {
@@ -138,7 +336,15 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
             }
         }
 
-        if self.file.parent_dir.is_none() {
+        let name_start = bits.len();
+
+        if let Some(relative_to) = &self.options.relative_to {
+            let relative = relative_path(&lexical_absolute(&self.file.path, &relative_to.current_dir), &relative_to.base);
+            if let Some(parent) = relative.parent() {
+                self.add_parent_bits(&mut bits, parent);
+            }
+        }
+        else if self.file.parent_dir.is_none() {
             if let Some(parent) = self.file.path.parent() {
                 self.add_parent_bits(&mut bits, parent);
             }
@@ -156,54 +362,35 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
             }
         }
 
+        // The quotes go around the path and the name, but not the icon
+        // before them or anything after them. Colours inside the quotes
+        // would only get in the way of copying the name out.
+        if self.options.quote_names == QuoteNames::Shell {
+            let name = bits.drain(name_start ..).fold(String::new(), |mut name, bit| { name.push_str(&bit); name });
+            bits.push(Style::default().paint(shell_quote(&name)));
+        }
+
+        // The isolates go outside any quotes, so the quotes stay next to the
+        // name they belong to.
+        if self.options.bidi_isolate == BidiIsolate::Isolate {
+            bits.insert(name_start, Style::default().paint(FIRST_STRONG_ISOLATE));
+            bits.push(Style::default().paint(POP_DIRECTIONAL_ISOLATE));
+        }
+
         if let (LinkStyle::FullLinkPaths, Some(target)) = (self.link_style, self.target.as_ref()) {
             match target {
                 FileTarget::Ok(target) => {
                     bits.push(Style::default().paint(" "));
                     bits.push(self.colours.normal_arrow().paint("->"));
                     bits.push(Style::default().paint(" "));
-
-                    if let Some(parent) = target.path.parent() {
-                        self.add_parent_bits(&mut bits, parent);
-                    }
-
-                    if ! target.name.is_empty() {
-                        let target_options = Options {
-                            classify: Classify::JustFilenames,
-                            show_icons: ShowIcons::Off,
-                        };
-
-                        let target_name = FileName {
-                            file: target,
-                            colours: self.colours,
-                            target: None,
-                            link_style: LinkStyle::FullLinkPaths,
-                            options: target_options,
-                        };
-
-                        for bit in target_name.coloured_file_name() {
-                            bits.push(bit);
-                        }
-
-                        if let Classify::AddFileIndicators = self.options.classify {
-                            if let Some(class) = self.classify_char(target) {
-                                bits.push(Style::default().paint(class));
-                            }
-                        }
-                    }
+                    self.add_target_bits(&mut bits, target);
                 }
 
                 FileTarget::Broken(broken_path) => {
                     bits.push(Style::default().paint(" "));
                     bits.push(self.colours.broken_symlink().paint("->"));
                     bits.push(Style::default().paint(" "));
-
-                    escape(
-                        broken_path.display().to_string(),
-                        &mut bits,
-                        self.colours.broken_filename(),
-                        self.colours.broken_control_char(),
-                    );
+                    self.add_broken_target_bits(&mut bits, broken_path);
                 }
 
                 FileTarget::Err(_) => {
@@ -211,15 +398,95 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
                 }
             }
         }
-        else if let Classify::AddFileIndicators = self.options.classify {
-            if let Some(class) = self.classify_char(self.file) {
+        else if let Classify::AddFileIndicators(indicators) = self.options.classify {
+            if let Some(class) = self.classify_char(self.file, indicators) {
                 bits.push(Style::default().paint(class));
             }
         }
 
+        if self.options.mark_empty == MarkEmpty::AddMarker && self.file.is_empty() {
+            bits.push(self.colours.empty_marker().paint(" (empty)"));
+        }
+
         bits.into()
     }
 
+    /// Paints just the target of a symlink, without the link’s own name or
+    /// an arrow, for views that list targets in a column of their own.
+    /// Returns nothing for files that aren’t links, or whose targets
+    /// couldn’t be read.
+    pub fn paint_link_target(&self) -> Option<TextCellContents> {
+        let mut bits = Vec::new();
+
+        match self.target.as_ref()? {
+            FileTarget::Ok(target)        => self.add_target_bits(&mut bits, target),
+            FileTarget::Broken(path)      => self.add_broken_target_bits(&mut bits, path),
+            FileTarget::Err(_)            => return None,
+        }
+
+        Some(bits.into())
+    }
+
+    /// Adds the path and name of a symlink’s target to the given bits
+    /// vector, coloured as the target file itself would be.
+    fn add_target_bits(&self, bits: &mut Vec<ANSIString<'_>>, target: &File<'_>) {
+        if let Some(parent) = target.path.parent() {
+            match self.options.symlink_path {
+                SymlinkPath::Plain             => self.add_parent_bits(bits, parent),
+                SymlinkPath::ColourComponents  => self.add_coloured_parent_bits(bits, parent),
+                SymlinkPath::Hidden            => {}
+            }
+        }
+
+        if ! target.name.is_empty() {
+            let target_options = Options {
+                classify: Classify::JustFilenames,
+                show_icons: ShowIcons::Off,
+                symlink_colours: SymlinkColours::Link,
+                mark_empty: MarkEmpty::Off,
+                mark_unreadable: MarkUnreadable::Off,
+                symlink_path: SymlinkPath::Plain,
+                relative_to: None,
+                quote_names: QuoteNames::Off,
+                bidi_isolate: BidiIsolate::Off,
+            };
+
+            let target_name = FileName {
+                file: target,
+                colours: self.colours,
+                target: None,
+                link_style: LinkStyle::FullLinkPaths,
+                options: &target_options,
+            };
+
+            for bit in target_name.coloured_file_name() {
+                bits.push(bit);
+            }
+
+            if let Classify::AddFileIndicators(indicators) = self.options.classify {
+                if let Some(class) = self.classify_char(target, indicators) {
+                    bits.push(Style::default().paint(class));
+                }
+            }
+        }
+    }
+
+    /// Adds the path of a symlink’s missing target to the given bits
+    /// vector, in the broken-link colours.
+    fn add_broken_target_bits(&self, bits: &mut Vec<ANSIString<'_>>, broken_path: &Path) {
+        let shown_path = match (self.options.symlink_path, broken_path.file_name()) {
+            (SymlinkPath::Hidden, Some(name))  => Path::new(name),
+            _                                  => broken_path,
+        };
+
+        escape(
+            shown_path.display().to_string(),
+            bits,
+            self.colours.broken_filename(),
+            self.colours.broken_control_char(),
+        );
+    }
+
     /// Adds the bits of the parent path to the given bits vector.
     /// The path gets its characters escaped based on the colours.
     fn add_parent_bits(&self, bits: &mut Vec<ANSIString<'_>>, parent: &Path) {
@@ -239,41 +506,85 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
         }
     }
 
+    /// Adds the bits of a symlink target’s parent path to the given bits
+    /// vector, painting each component by what it is on the filesystem.
+    /// The path is looked up relative to the symlink, as that’s where the
+    /// target path starts from.
+    fn add_coloured_parent_bits(&self, bits: &mut Vec<ANSIString<'_>>, parent: &Path) {
+        let separator = std::path::MAIN_SEPARATOR.to_string();
+        let mut so_far = PathBuf::new();
+
+        for component in parent.components() {
+            so_far.push(component);
+
+            match component {
+                Component::RootDir => {
+                    bits.push(self.colours.symlink_path().paint(separator.clone()));
+                }
+                Component::Prefix(_) => {
+                    bits.push(self.colours.symlink_path().paint(component.as_os_str().to_string_lossy().to_string()));
+                }
+                _ => {
+                    let style = match std::fs::symlink_metadata(self.file.reorient_target_path(&so_far)) {
+                        Ok(m) if m.is_dir()                    => self.colours.directory(),
+                        Ok(m) if m.file_type().is_symlink()    => self.colours.symlink(),
+                        _                                      => self.colours.symlink_path(),
+                    };
+
+                    escape(
+                        component.as_os_str().to_string_lossy().to_string(),
+                        bits,
+                        style,
+                        self.colours.control_char(),
+                    );
+                    bits.push(self.colours.symlink_path().paint(separator.clone()));
+                }
+            }
+        }
+    }
+
     /// The character to be displayed after a file when classifying is on, if
-    /// the file’s type has one associated with it.
+    /// the file’s type has one associated with it and that type is one of
+    /// the indicators being shown.
     #[cfg(unix)]
-    fn classify_char(&self, file: &File<'_>) -> Option<&'static str> {
-        if file.is_executable_file() {
-            Some("*")
+    fn classify_char(&self, file: &File<'_>, indicators: Indicators) -> Option<&'static str> {
+        let (class, character) = if file.is_executable_file() {
+            (Indicators::EXECUTABLE, "*")
         }
         else if file.is_directory() {
-            Some("/")
+            (Indicators::DIRECTORY, "/")
         }
         else if file.is_pipe() {
-            Some("|")
+            (Indicators::PIPE, "|")
         }
         else if file.is_link() {
-            Some("@")
+            (Indicators::SYMLINK, "@")
         }
         else if file.is_socket() {
-            Some("=")
+            (Indicators::SOCKET, "=")
         }
         else {
-            None
-        }
+            return None;
+        };
+
+        if indicators.contains(class) { Some(character) }
+                                 else { None }
     }
 
     #[cfg(windows)]
-    fn classify_char(&self, file: &File<'_>) -> Option<&'static str> {
-        if file.is_directory() {
-            Some("/")
+    fn classify_char(&self, file: &File<'_>, indicators: Indicators) -> Option<&'static str> {
+        let (class, character) = if file.is_directory() {
+            (Indicators::DIRECTORY, "/")
         }
         else if file.is_link() {
-            Some("@")
+            (Indicators::SYMLINK, "@")
         }
         else {
-            None
-        }
+            return None;
+        };
+
+        if indicators.contains(class) { Some(character) }
+                                 else { None }
     }
 
     /// Returns at least one ANSI-highlighted string representing this file’s
@@ -290,6 +601,14 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
         let file_style = self.style();
         let mut bits = Vec::new();
 
+        #[cfg(unix)]
+        let file_style = if self.options.mark_unreadable == MarkUnreadable::AddOverlay && self.file.is_unreadable() {
+            self.colours.unreadable_filename(file_style)
+        }
+        else {
+            file_style
+        };
+
         escape(
             self.file.name.clone(),
             &mut bits,
@@ -313,6 +632,18 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
             }
         }
 
+        if let (SymlinkColours::Target, Some(FileTarget::Ok(target))) = (self.options.symlink_colours, self.target.as_ref()) {
+            let target_name = FileName {
+                file: target,
+                colours: self.colours,
+                target: None,
+                link_style: self.link_style,
+                options: self.options,
+            };
+
+            return target_name.style();
+        }
+
         match self.file {
             f if f.is_directory()        => self.colours.directory(),
             #[cfg(unix)]
@@ -327,12 +658,70 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
             #[cfg(unix)]
             f if f.is_socket()           => self.colours.socket(),
             f if ! f.is_file()           => self.colours.special(),
-            _                            => self.colours.colour_file(self.file),
+            _                            => self.file.name_style(|| self.colours.colour_file(self.file)),
         }
     }
 }
 
 
+/// The mark that starts a bidirectional isolate, taking its direction from
+/// the first strongly-directional character after it.
+const FIRST_STRONG_ISOLATE: &str = "\u{2068}";
+
+/// The mark that ends a bidirectional isolate.
+const POP_DIRECTIONAL_ISOLATE: &str = "\u{2069}";
+
+
+/// Wraps the given text in single quotes for a shell. A single quote can’t
+/// appear inside them, even escaped, so each one ends the quoted part, adds
+/// an escaped quote, and starts a new quoted part.
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+
+/// Makes the given path absolute, by putting the current directory in front
+/// of it if it’s relative, and tidies away any `.` and `..` components. This
+/// is done without touching the filesystem, so symlinks are left alone.
+fn lexical_absolute(path: &Path, current_dir: &Path) -> PathBuf {
+    let joined = current_dir.join(path);
+
+    let mut tidied = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir     => {}
+            Component::ParentDir  => { let _ = tidied.pop(); }
+            _                     => tidied.push(component),
+        }
+    }
+    tidied
+}
+
+/// The path that leads from the base directory to the given path, going up
+/// through `..` for any part of the base that the path isn’t inside. Both
+/// paths should already be absolute.
+fn relative_path(path: &Path, base: &Path) -> PathBuf {
+    let mut path_components = path.components().peekable();
+    let mut base_components = base.components().peekable();
+
+    while let (Some(p), Some(b)) = (path_components.peek(), base_components.peek()) {
+        if p != b { break; }
+        let _ = path_components.next();
+        let _ = base_components.next();
+    }
+
+    let mut relative = PathBuf::new();
+    for _ in base_components {
+        relative.push(Component::ParentDir);
+    }
+    for component in path_components {
+        relative.push(component);
+    }
+
+    relative
+}
+
+
 /// The set of colours that are needed to paint a file name.
 pub trait Colours: FiletypeColours {
 
@@ -358,6 +747,12 @@ pub trait Colours: FiletypeColours {
     /// when the filename is being displayed as a broken link target.
     fn broken_control_char(&self) -> Style;
 
+    /// The style to paint the marker after an empty file or directory.
+    fn empty_marker(&self) -> Style;
+
+    /// Amends the style of a file that the current user can’t read.
+    fn unreadable_filename(&self, style: Style) -> Style;
+
     /// The style to paint a file that has its executable bit set.
     fn executable_file(&self) -> Style;
 
@@ -369,3 +764,241 @@ pub trait Colours: FiletypeColours {
 fn spaces(width: u32) -> String {
     (0 .. width).into_iter().map(|_| ' ').collect()
 }
+
+
+#[cfg(test)]
+#[cfg(unix)]
+mod test {
+    use super::*;
+    use crate::theme::{Options as ThemeOptions, UseColours, Theme};
+    use crate::fs::test::TempDir;
+
+    fn theme() -> Theme {
+        ThemeOptions { use_colours: UseColours::Always, .. ThemeOptions::plain() }.to_theme(false)
+    }
+
+    fn options(symlink_colours: SymlinkColours) -> Options {
+        Options {
+            classify: Classify::JustFilenames,
+            show_icons: ShowIcons::Off,
+            symlink_colours,
+            mark_empty: MarkEmpty::Off,
+            mark_unreadable: MarkUnreadable::Off,
+            symlink_path: SymlinkPath::Plain,
+            relative_to: None,
+            quote_names: QuoteNames::Off,
+            bidi_isolate: BidiIsolate::Off,
+        }
+    }
+
+    #[test]
+    fn symlink_target_colours() {
+        let path = TempDir::new("symlink");
+        std::fs::create_dir_all(path.join("target")).unwrap();
+        std::os::unix::fs::symlink(path.join("target"), path.join("link")).unwrap();
+        std::os::unix::fs::symlink(path.join("nowhere"), path.join("broken")).unwrap();
+
+        let theme = theme();
+        let link = File::from_args(path.join("link"), None, None).unwrap();
+        let broken = File::from_args(path.join("broken"), None, None).unwrap();
+
+        assert_eq!(options(SymlinkColours::Link).for_file(&link, &theme).style(), theme.ui.filekinds.symlink);
+        assert_eq!(options(SymlinkColours::Target).for_file(&link, &theme).style(), theme.ui.filekinds.directory);
+        assert_eq!(options(SymlinkColours::Target).for_file(&broken, &theme).style(), theme.ui.broken_symlink);
+    }
+
+    #[test]
+    fn symlink_path_colours() {
+        let path = TempDir::new("symlink-path");
+        std::fs::create_dir_all(path.join("dir")).unwrap();
+        std::fs::write(path.join("dir").join("file"), b"").unwrap();
+        std::os::unix::fs::symlink("dir/file", path.join("link")).unwrap();
+
+        let theme = theme();
+        let link = File::from_args(path.join("link"), None, None).unwrap();
+        let name = |symlink_path| {
+            let options = Options { symlink_path, .. options(SymlinkColours::Link) };
+            options.for_file(&link, &theme).with_link_paths().paint()
+        };
+
+        let plain = name(SymlinkPath::Plain);
+        assert!(plain.iter().any(|bit| &**bit == "dir" && *bit.style_ref() == theme.ui.symlink_path));
+
+        let coloured = name(SymlinkPath::ColourComponents);
+        assert!(coloured.iter().any(|bit| &**bit == "dir" && *bit.style_ref() == theme.ui.filekinds.directory));
+        assert!(! coloured.iter().any(|bit| &**bit == "dir" && *bit.style_ref() == theme.ui.symlink_path));
+    }
+
+    #[test]
+    fn short_symlink_targets() {
+        let path = TempDir::new("short-symlink");
+        std::os::unix::fs::symlink("/a/b/c", path.join("link")).unwrap();
+
+        let theme = theme();
+        let dir = crate::fs::Dir::read_dir(path.clone()).unwrap();
+        let link = File::from_args(path.join("link"), &dir, None).unwrap();
+        let name = |symlink_path| {
+            let options = Options { symlink_path, .. options(SymlinkColours::Link) };
+            options.for_file(&link, &theme).with_link_paths().paint().iter().map(|bit| &**bit).collect::<String>()
+        };
+
+        assert_eq!(name(SymlinkPath::Plain), "link -> /a/b/c");
+        assert_eq!(name(SymlinkPath::Hidden), "link -> c");
+    }
+
+    #[test]
+    fn some_classify_chars() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = TempDir::new("classify-chars");
+        std::fs::create_dir_all(path.join("dir")).unwrap();
+        std::fs::write(path.join("script"), b"#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(path.join("script"), std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let theme = theme();
+        let dir = crate::fs::Dir::read_dir(path.clone()).unwrap();
+        let name = |file_name, indicators| {
+            let file = File::from_args(path.join(file_name), &dir, None).unwrap();
+            let options = Options { classify: Classify::AddFileIndicators(indicators), .. options(SymlinkColours::Link) };
+            options.for_file(&file, &theme).paint().iter().map(|bit| &**bit).collect::<String>()
+        };
+
+        assert_eq!(name("dir", Indicators::ALL), "dir/");
+        assert_eq!(name("script", Indicators::ALL), "script*");
+        assert_eq!(name("dir", Indicators::DIRECTORY), "dir/");
+        assert_eq!(name("script", Indicators::DIRECTORY), "script");
+    }
+
+    #[test]
+    fn classify_chars_without_colour() {
+        use std::os::unix::fs::PermissionsExt;
+        use ansi_term::ANSIStrings;
+
+        let path = TempDir::new("classify-no-colour");
+        std::fs::create_dir_all(path.join("dir")).unwrap();
+        std::fs::write(path.join("script"), b"#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(path.join("script"), std::fs::Permissions::from_mode(0o755)).unwrap();
+        std::os::unix::fs::symlink("dir", path.join("link")).unwrap();
+
+        // The indicators are painted in the default style whatever the
+        // theme, so turning colours off shouldn’t turn them off too.
+        let theme = ThemeOptions::plain().to_theme(false);
+
+        let dir = crate::fs::Dir::read_dir(path.clone()).unwrap();
+        let name = |file_name| {
+            let file = File::from_args(path.join(file_name), &dir, None).unwrap();
+            let options = Options { classify: Classify::AddFileIndicators(Indicators::ALL), .. options(SymlinkColours::Link) };
+            ANSIStrings(&options.for_file(&file, &theme).paint()).to_string()
+        };
+
+        assert_eq!(name("dir"), "dir/");
+        assert_eq!(name("script"), "script*");
+        assert_eq!(name("link"), "link@");
+    }
+
+    #[test]
+    fn relative_to_parent() {
+        let path = TempDir::new("relative");
+        std::fs::create_dir_all(path.join("sub")).unwrap();
+        std::fs::write(path.join("sub").join("file"), b"").unwrap();
+
+        let theme = theme();
+        let dir = crate::fs::Dir::read_dir(path.join("sub")).unwrap();
+        let file = dir.files(crate::fs::DotFilter::JustFiles, None, false).next().unwrap().unwrap();
+        let name = |relative_to: &Path| {
+            let options = Options { relative_to: Some(RelativeTo::new(relative_to)), .. options(SymlinkColours::Link) };
+            options.for_file(&file, &theme).paint().iter().map(|bit| &**bit).collect::<String>()
+        };
+
+        assert_eq!(name(&path), "sub/file");
+        assert_eq!(name(&path.join("elsewhere")), "../sub/file");
+        assert_eq!(name(&path.join("sub").join("..").join("sub")), "file");
+    }
+
+    #[test]
+    fn unreadable_overlay() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new("unreadable");
+        let path = dir.join("file");
+        std::fs::write(&path, b"secret").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o000)).unwrap();
+
+        let theme = theme();
+        let file = File::from_args(path, None, None).unwrap();
+        let style = |mark_unreadable| {
+            let options = Options { mark_unreadable, .. options(SymlinkColours::Link) };
+            *options.for_file(&file, &theme).paint().iter().last().unwrap().style_ref()
+        };
+
+        let normal = theme.ui.filekinds.normal;
+        assert_eq!(style(MarkUnreadable::Off), normal);
+
+        // The superuser can read anything, so nothing gets marked for them.
+        if users::get_effective_uid() == 0 {
+            assert_eq!(style(MarkUnreadable::AddOverlay), normal);
+        }
+        else {
+            assert_eq!(style(MarkUnreadable::AddOverlay), normal.dimmed().strikethrough());
+        }
+    }
+
+    #[test]
+    fn name_classified_once() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use crate::theme::FileColours;
+
+        struct Counting(Arc<AtomicUsize>);
+        impl FileColours for Counting {
+            fn colour_file(&self, _file: &File<'_>) -> Option<Style> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                None
+            }
+        }
+
+        let dir = TempDir::new("classify");
+        let path = dir.join("file.png");
+        std::fs::write(&path, b"").unwrap();
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let theme = Theme { exts: Box::new(Counting(Arc::clone(&count))), .. theme() };
+        let file = File::from_args(path, None, None).unwrap();
+        let options = Options { show_icons: ShowIcons::On(1), .. options(SymlinkColours::Link) };
+
+        options.for_file(&file, &theme).paint();
+        options.for_file(&file, &theme).paint();
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn bidi_isolated_name() {
+        let path = TempDir::new("bidi");
+        std::fs::write(path.join("שלום.txt"), b"").unwrap();
+
+        let theme = theme();
+        let dir = crate::fs::Dir::read_dir(path.clone()).unwrap();
+        let file = File::from_args(path.join("שלום.txt"), &dir, None).unwrap();
+        let options = Options { bidi_isolate: BidiIsolate::Isolate, .. options(SymlinkColours::Link) };
+
+        let bits = options.for_file(&file, &theme).paint();
+        let text = bits.iter().map(|bit| &**bit).collect::<String>();
+        assert_eq!(text, "\u{2068}שלום.txt\u{2069}");
+    }
+
+    #[test]
+    fn shell_quoted_name() {
+        use ansi_term::ANSIStrings;
+
+        let path = TempDir::new("quote");
+        std::fs::write(path.join("it's a file"), b"").unwrap();
+
+        let theme = theme();
+        let dir = crate::fs::Dir::read_dir(path.clone()).unwrap();
+        let file = File::from_args(path.join("it's a file"), &dir, None).unwrap();
+        let options = Options { quote_names: QuoteNames::Shell, .. options(SymlinkColours::Link) };
+
+        let bits = options.for_file(&file, &theme).paint();
+        assert_eq!(ANSIStrings(&bits).to_string(), r"'it'\''s a file'");
+    }
+}
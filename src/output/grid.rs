@@ -11,6 +11,14 @@ use crate::theme::Theme;
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
 pub struct Options {
     pub across: bool,
+
+    /// Whether to keep to a one-column grid when the names are too wide to
+    /// fit, rather than dropping down to a plain list.
+    pub strict: bool,
+
+    /// Whether to break names that are too wide for the terminal over
+    /// several lines, rather than dropping down to a plain list.
+    pub wrap_names: bool,
 }
 
 impl Options {
@@ -31,6 +39,8 @@ pub struct Render<'a> {
 }
 
 impl<'a> Render<'a> {
+    /// Renders the grid. Each file name is painted and measured only once,
+    /// and the same cells get used however the names end up being laid out.
     pub fn render<W: Write>(mut self, w: &mut W) -> io::Result<()> {
         let mut grid = tg::Grid::new(tg::GridOptions {
             direction:  self.opts.direction(),
@@ -39,13 +49,15 @@ impl<'a> Render<'a> {
 
         grid.reserve(self.files.len());
 
-        self.filter.sort_files(&mut self.files);
-        for file in &self.files {
-            let filename = self.file_style.for_file(file, self.theme).paint();
+        self.filter.sort_and_limit(&mut self.files);
+        let names = self.files.iter()
+                        .map(|file| self.file_style.for_file(file, self.theme).paint())
+                        .collect::<Vec<_>>();
 
+        for name in &names {
             grid.add(tg::Cell {
-                contents:  filename.strings().to_string(),
-                width:     *filename.width(),
+                contents:  name.strings().to_string(),
+                width:     *name.width(),
                 alignment: tg::Alignment::Left,
             });
         }
@@ -53,16 +65,172 @@ impl<'a> Render<'a> {
         if let Some(display) = grid.fit_into_width(self.console_width) {
             write!(w, "{}", display)
         }
+        else if self.opts.wrap_names && names.iter().any(|name| *name.width() > self.console_width) {
+            // A name as wide as the terminal leaves no room for a second
+            // column, so each name gets its own lines, broken wherever
+            // they reach the edge of the screen.
+            for name in &names {
+                for line in name.wrap(self.console_width) {
+                    writeln!(w, "{}", line.strings())?;
+                }
+            }
+
+            Ok(())
+        }
+        else if self.opts.strict {
+            // The names still don’t fit in one column, but keep the
+            // output a grid anyway, so it’s laid out the same way however
+            // long the names get.
+            write!(w, "{}", grid.fit_into_columns(1))
+        }
         else {
             // File names too long for a grid - drop down to just listing them!
             // This isn’t *quite* the same as the lines view, which also
             // displays full link paths.
-            for file in &self.files {
-                let name_cell = self.file_style.for_file(file, self.theme).paint();
-                writeln!(w, "{}", name_cell.strings())?;
+            for name in &names {
+                writeln!(w, "{}", name.strings())?;
             }
 
             Ok(())
         }
     }
 }
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fs::{Dir, DotFilter};
+    use crate::fs::filter::{SortField, SortOrder, IgnorePatterns, ModifiedWindow, GitIgnore, IgnoreFiles, TypeOrder, AtimeWarning, DotfileOrder};
+    use crate::output::file_name::{Classify, ShowIcons, SymlinkColours, MarkEmpty, MarkUnreadable, SymlinkPath, QuoteNames, BidiIsolate};
+    use crate::theme::Options as ThemeOptions;
+    use std::path::Path;
+    use crate::fs::test::TempDir;
+
+    fn theme() -> Theme {
+        ThemeOptions::plain().to_theme(false)
+    }
+
+    fn file_style() -> FileStyle {
+        FileStyle {
+            classify: Classify::JustFilenames,
+            show_icons: ShowIcons::Off,
+            symlink_colours: SymlinkColours::Link,
+            mark_empty: MarkEmpty::Off,
+            mark_unreadable: MarkUnreadable::Off,
+            symlink_path: SymlinkPath::Plain,
+            relative_to: None,
+            quote_names: QuoteNames::Off,
+            bidi_isolate: BidiIsolate::Off,
+        }
+    }
+
+    fn filter() -> FileFilter {
+        FileFilter {
+            list_dirs_first: false,
+            dotfile_order: DotfileOrder::Mixed,
+            sort_field: SortField::default(),
+            secondary_sort: None,
+            sort_order: SortOrder::Natural,
+            reverse: false,
+            type_order: TypeOrder::Reversible,
+            atime_warning: AtimeWarning::Off,
+            modified_window: ModifiedWindow::default(),
+            only_dirs: false,
+            dot_filter: DotFilter::JustFiles,
+            ignore_patterns: IgnorePatterns::empty(),
+            git_ignore: GitIgnore::Off,
+            ignore_files: IgnoreFiles::Off,
+            max_entries: None,
+        }
+    }
+
+    fn dir_with(path: &Path, names: &[&str]) -> Dir {
+        std::fs::create_dir_all(path).unwrap();
+        for name in names {
+            std::fs::write(path.join(name), b"").unwrap();
+        }
+
+        Dir::read_dir(path.to_path_buf()).unwrap()
+    }
+
+    fn files_in(dir: &Dir) -> Vec<File<'_>> {
+        dir.files(DotFilter::JustFiles, None, false)
+           .map(Result::unwrap)
+           .collect()
+    }
+
+    #[test]
+    fn strict_grid_too_wide() {
+        let path = TempDir::new("grid");
+        let dir = dir_with(&path, &[ "a-rather-long-file-name", "another-long-file-name" ]);
+        let files = files_in(&dir);
+
+        let theme = theme();
+        let file_style = &file_style();
+        let filter = &filter();
+        let opts = &Options { across: false, strict: true, wrap_names: false };
+
+        let mut output = Vec::new();
+        Render { files, theme: &theme, file_style, opts, console_width: 10, filter }.render(&mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output, "a-rather-long-file-name\nanother-long-file-name\n");
+    }
+
+    #[test]
+    fn falls_back_to_list() {
+        let path = TempDir::new("grid-fallback");
+        let dir = dir_with(&path, &[ "one", "two", "three", "a-name-too-long-for-any-grid" ]);
+        let files = files_in(&dir);
+
+        let theme = theme();
+        let file_style = &file_style();
+        let filter = &filter();
+        let opts = &Options { across: false, strict: false, wrap_names: false };
+
+        // The names don’t fit, so the grid gets tried and then abandoned
+        // for a list of the same names.
+        let mut output = Vec::new();
+        Render { files, theme: &theme, file_style, opts, console_width: 20, filter }.render(&mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "a-name-too-long-for-any-grid\none\nthree\ntwo\n");
+    }
+
+    #[test]
+    fn wide_names_aligned() {
+        let path = TempDir::new("grid-wide");
+        let dir = dir_with(&path, &[ "ab", "abcdef", "cd", "ef", "gh", "ij", "kl", "日本語" ]);
+        let files = files_in(&dir);
+
+        let theme = theme();
+        let file_style = &file_style();
+        let filter = &FileFilter { reverse: true, .. filter() };
+        let opts = &Options { across: false, strict: false, wrap_names: false };
+
+        // The CJK name takes up two columns for each character, so the
+        // names under it have to be padded out to six columns, not three.
+        let mut output = Vec::new();
+        Render { files, theme: &theme, file_style, opts, console_width: 12, filter }.render(&mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().take(3).collect::<Vec<_>>(), vec![ "日本語  ab", "kl      ", "ij      " ]);
+    }
+
+    #[test]
+    fn long_names_wrapped() {
+        let path = TempDir::new("grid-wrap");
+        let dir = dir_with(&path, &[ "a-name-too-long-for-any-grid", "short" ]);
+        let files = files_in(&dir);
+
+        let theme = theme();
+        let file_style = &file_style();
+        let filter = &filter();
+        let opts = &Options { across: false, strict: false, wrap_names: true };
+
+        let mut output = Vec::new();
+        Render { files, theme: &theme, file_style, opts, console_width: 10, filter }.render(&mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "a-name-too\n-long-for-\nany-grid\nshort\n");
+    }
+}
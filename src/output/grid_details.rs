@@ -10,7 +10,7 @@ use crate::fs::feature::git::GitCache;
 use crate::fs::feature::xattr::FileAttributes;
 use crate::fs::filter::FileFilter;
 use crate::output::cell::TextCell;
-use crate::output::details::{Options as DetailsOptions, Row as DetailsRow, Render as DetailsRender};
+use crate::output::details::{Options as DetailsOptions, Row as DetailsRow, Render as DetailsRender, HeaderRow};
 use crate::output::file_name::Options as FileStyle;
 use crate::output::grid::Options as GridOptions;
 use crate::output::table::{Table, Row as TableRow, Options as TableOptions};
@@ -106,9 +106,11 @@ impl<'a> Render<'a> {
             file_style:    self.file_style,
             opts:          self.details,
             recurse:       None,
+            budget:        None,
             filter:        self.filter,
             git_ignoring:  self.git_ignoring,
             git:           self.git,
+            unstatted:     Vec::new(),
         }
     }
 
@@ -124,9 +126,11 @@ impl<'a> Render<'a> {
             file_style:    self.file_style,
             opts:          self.details,
             recurse:       None,
+            budget:        None,
             filter:        self.filter,
             git_ignoring:  self.git_ignoring,
             git:           self.git,
+            unstatted:     Vec::new(),
         }
     }
 
@@ -147,11 +151,19 @@ impl<'a> Render<'a> {
 
         let drender = self.details_for_column();
 
-        let (first_table, _) = self.make_table(options, &drender);
+        let (mut first_table, _) = self.make_table(options, &drender);
 
-        let rows = self.files.iter()
-                       .map(|file| first_table.row_for_file(file, file_has_xattrs(file)))
-                       .collect::<Vec<_>>();
+        for file in &self.files {
+            first_table.add_to_totals(file);
+        }
+
+        let mut rows = self.files.iter()
+                           .map(|file| first_table.row_for_file(file, file_has_xattrs(file)))
+                           .collect::<Vec<_>>();
+
+        for row in &mut rows {
+            first_table.add_relative_cells(row);
+        }
 
         let file_names = self.files.iter()
                              .map(|file| self.file_style.for_file(file, self.theme).paint().promote())
@@ -205,7 +217,7 @@ impl<'a> Render<'a> {
         let mut table = Table::new(options, self.git, self.theme);
         let mut rows = Vec::new();
 
-        if self.details.header {
+        if self.details.header == HeaderRow::Show {
             let row = table.header_row();
             table.add_widths(&row);
             rows.push(drender.render_header(row));
@@ -221,7 +233,7 @@ impl<'a> Render<'a> {
         }
 
         let mut num_cells = rows.len();
-        if self.details.header {
+        if self.details.header == HeaderRow::Show {
             num_cells += column_count;
         }
 
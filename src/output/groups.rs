@@ -0,0 +1,130 @@
+//! Splitting a list of files into labelled sections before rendering them.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use crate::fs::File;
+use crate::theme::Theme;
+
+
+/// How to split the files in a listing into sections.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum GroupBy {
+
+    /// List every file together, in one section.
+    Nothing,
+
+    /// List files in one section per extension, with extensionless files
+    /// going first.
+    Extension,
+}
+
+impl Default for GroupBy {
+    fn default() -> Self {
+        Self::Nothing
+    }
+}
+
+
+/// Splits the files into groups by their extension, in order of extension.
+/// Files keep the order they were in within each group.
+pub fn by_extension(files: Vec<File<'_>>) -> Vec<(Option<String>, Vec<File<'_>>)> {
+    let mut groups = BTreeMap::<Option<String>, Vec<File<'_>>>::new();
+
+    for file in files {
+        groups.entry(file.ext.clone()).or_default().push(file);
+    }
+
+    groups.into_iter().collect()
+}
+
+/// The heading to print above a group of files with the given extension,
+/// such as `── rs ──`.
+pub fn heading(ext: Option<&str>, theme: &Theme) -> String {
+    let label = ext.unwrap_or("no extension");
+    theme.ui.punctuation.paint(format!("── {} ──", label)).to_string()
+}
+
+/// Renders the files in one section per extension, with a heading above
+/// each section and a blank line between them. Each section gets rendered
+/// using the given function, which is how this wraps around the other views.
+pub fn render_by_extension<'a, W, R>(files: Vec<File<'a>>, theme: &Theme, w: &mut W, mut render: R) -> io::Result<()>
+where W: Write,
+      R: FnMut(&mut W, Vec<File<'a>>) -> io::Result<()>,
+{
+    for (index, (ext, group)) in by_extension(files).into_iter().enumerate() {
+        if index > 0 {
+            writeln!(w)?;
+        }
+
+        writeln!(w, "{}", heading(ext.as_deref(), theme))?;
+        render(w, group)?;
+    }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fs::{Dir, DotFilter};
+    use crate::fs::filter::{FileFilter, SortField, SortOrder, IgnorePatterns, ModifiedWindow, GitIgnore, IgnoreFiles, TypeOrder, AtimeWarning, DotfileOrder};
+    use crate::output::file_name::{Options as FileStyle, Classify, ShowIcons, SymlinkColours, MarkEmpty, MarkUnreadable, SymlinkPath, QuoteNames, BidiIsolate};
+    use crate::output::lines;
+    use crate::theme::Options as ThemeOptions;
+    use crate::fs::test::TempDir;
+
+    #[test]
+    fn sections_between_groups() {
+        let path = TempDir::new("groups");
+        for name in &[ "main.rs", "README.md", "lib.rs", "Makefile" ] {
+            std::fs::write(path.join(name), b"").unwrap();
+        }
+
+        let dir = Dir::read_dir(path.clone()).unwrap();
+        let files = dir.files(DotFilter::JustFiles, None, false)
+                       .collect::<Result<Vec<_>, _>>()
+                       .unwrap();
+
+        let theme = ThemeOptions::plain().to_theme(false);
+
+        let file_style = &FileStyle {
+            classify: Classify::JustFilenames,
+            show_icons: ShowIcons::Off,
+            symlink_colours: SymlinkColours::Link,
+            mark_empty: MarkEmpty::Off,
+            mark_unreadable: MarkUnreadable::Off,
+            symlink_path: SymlinkPath::Plain,
+            relative_to: None,
+            quote_names: QuoteNames::Off,
+            bidi_isolate: BidiIsolate::Off,
+        };
+
+        let filter = &FileFilter {
+            list_dirs_first: false,
+            dotfile_order: DotfileOrder::Mixed,
+            sort_field: SortField::default(),
+            secondary_sort: None,
+            sort_order: SortOrder::Natural,
+            reverse: false,
+            type_order: TypeOrder::Reversible,
+            atime_warning: AtimeWarning::Off,
+            modified_window: ModifiedWindow::default(),
+            only_dirs: false,
+            dot_filter: DotFilter::JustFiles,
+            ignore_patterns: IgnorePatterns::empty(),
+            git_ignore: GitIgnore::Off,
+            ignore_files: IgnoreFiles::Off,
+            max_entries: None,
+        };
+
+        let mut output = Vec::new();
+        render_by_extension(files, &theme, &mut output, |w, files| {
+            lines::Render { files, theme: &theme, file_style, filter }.render(w)
+        }).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output, "── no extension ──\nMakefile\n\n── md ──\nREADME.md\n\n── rs ──\nlib.rs\nmain.rs\n");
+    }
+}
@@ -19,7 +19,7 @@ pub struct Render<'a> {
 
 impl<'a> Render<'a> {
     pub fn render<W: Write>(mut self, w: &mut W) -> io::Result<()> {
-        self.filter.sort_files(&mut self.files);
+        self.filter.sort_and_limit(&mut self.files);
         for file in &self.files {
             let name_cell = self.render_file(file);
             writeln!(w, "{}", ANSIStrings(&name_cell))?;
@@ -28,6 +28,19 @@ impl<'a> Render<'a> {
         Ok(())
     }
 
+    /// Writes each file’s bare name followed by a NUL byte, without any
+    /// colours, link targets, or escaping, so names with newlines in them
+    /// still come out in one piece.
+    pub fn render_nul_separated<W: Write>(mut self, w: &mut W) -> io::Result<()> {
+        self.filter.sort_and_limit(&mut self.files);
+        for file in &self.files {
+            w.write_all(file.name.as_bytes())?;
+            w.write_all(b"\0")?;
+        }
+
+        Ok(())
+    }
+
     fn render_file<'f>(&self, file: &'f File<'a>) -> TextCellContents {
         self.file_style
             .for_file(file, self.theme)
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 pub use self::cell::{TextCell, TextCellContents, DisplayWidth};
 pub use self::escape::escape;
 
@@ -5,11 +7,16 @@ pub mod details;
 pub mod file_name;
 pub mod grid;
 pub mod grid_details;
+pub mod groups;
 pub mod icons;
 pub mod lines;
+pub mod pager;
 pub mod render;
+pub mod stat;
+pub mod summary;
 pub mod table;
 pub mod time;
+pub mod watch;
 
 mod cell;
 mod escape;
@@ -22,6 +29,56 @@ pub struct View {
     pub mode: Mode,
     pub width: TerminalWidth,
     pub file_style: file_name::Options,
+    pub paging: pager::Paging,
+    pub headings: DirHeadings,
+    pub group_by: groups::GroupBy,
+
+    /// The file to write the output to, if it shouldn’t go to stdout.
+    pub output: Option<PathBuf>,
+
+    /// Whether to list the paths once, keep listing them, or do something
+    /// else entirely.
+    pub run: RunMode,
+}
+
+
+/// The extra lines to print above each directory’s listing. Both of them
+/// include the directory’s path.
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct DirHeadings {
+
+    /// Whether to print how many of each type of file the directory has.
+    pub summary: bool,
+
+    /// Whether to print how much space is left on the directory’s
+    /// filesystem.
+    pub filesystem_info: bool,
+}
+
+impl DirHeadings {
+
+    /// Whether any lines get printed, in which case the path doesn’t need
+    /// printing on its own.
+    pub fn any(self) -> bool {
+        self.summary || self.filesystem_info
+    }
+}
+
+
+/// What exa should do with the paths it’s been given.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum RunMode {
+
+    /// List them once, then exit.
+    Once,
+
+    /// Keep listing them again whenever they change, for as long as the
+    /// output is going to a terminal.
+    Watch,
+
+    /// Print every style in the theme instead of listing any files, for
+    /// working out where a colour is coming from.
+    DebugColours,
 }
 
 
@@ -33,6 +90,15 @@ pub enum Mode {
     Details(details::Options),
     GridDetails(grid_details::Options),
     Lines,
+    Stat,
+
+    /// Just the files’ names, each followed by a NUL byte instead of a
+    /// newline, for shell completion scripts to read.
+    NulSeparated,
+
+    /// No files at all, just one line totalling up how many there are and
+    /// how big they are, for status bars to show.
+    OnelinerSummary,
 }
 
 
@@ -43,19 +109,158 @@ pub enum TerminalWidth {
     /// The user requested this specific number of columns.
     Set(usize),
 
+    /// The user requested this number of columns, but only for when the
+    /// output is going to a terminal.
+    SetForTerminal(usize),
+
     /// Look up the terminal size at runtime.
     Automatic,
 }
 
 impl TerminalWidth {
     pub fn actual_terminal_width(self) -> Option<usize> {
-        // All of stdin, stdout, and stderr could not be connected to a
-        // terminal, but we’re only interested in stdout because it’s
-        // where the output goes.
+        self.width_for(&LiveTerminals)
+    }
 
+    /// The width to use when the output goes to a file, which is never a
+    /// terminal, so only a width the user set regardless of that counts.
+    pub fn file_width(self) -> Option<usize> {
         match self {
             Self::Set(width)  => Some(width),
-            Self::Automatic   => terminal_size::terminal_size().map(|(w, _)| w.0.into()),
+            _                 => None,
+        }
+    }
+
+    fn width_for<T: Terminals>(self, terminals: &T) -> Option<usize> {
+        match self {
+            Self::Set(width)             => Some(width),
+            Self::SetForTerminal(width)  => Some(width).filter(|_| terminals.stdout_is_terminal()),
+            Self::Automatic              => automatic_width(terminals),
         }
     }
 }
+
+/// Works out the terminal width from the terminals that the output streams
+/// are connected to.
+///
+/// All of stdin, stdout, and stderr could not be connected to a terminal,
+/// but we’re only interested in stdout because it’s where the output goes.
+/// Some pseudo-terminals don’t report a size for stdout even though it’s a
+/// terminal, though, so in that case, stderr gets asked instead. Output
+/// that gets piped somewhere never uses stderr’s size, as that would stop
+/// the output from being one file per line.
+fn automatic_width<T: Terminals>(terminals: &T) -> Option<usize> {
+    let positive = |width: Option<usize>| width.filter(|&w| w > 0);
+
+    positive(terminals.stdout_width()).or_else(|| {
+        if terminals.stdout_is_terminal() {
+            positive(terminals.stderr_width())
+        }
+        else {
+            None
+        }
+    })
+}
+
+/// The source of the sizes of the terminals that exa is connected to. This
+/// is a trait so the sizes can be mocked up in tests.
+trait Terminals {
+    fn stdout_is_terminal(&self) -> bool;
+    fn stdout_width(&self) -> Option<usize>;
+    fn stderr_width(&self) -> Option<usize>;
+}
+
+/// The terminals that exa is actually connected to.
+struct LiveTerminals;
+
+impl Terminals for LiveTerminals {
+    #[cfg(unix)]
+    fn stdout_is_terminal(&self) -> bool {
+        unsafe { libc::isatty(libc::STDOUT_FILENO) == 1 }
+    }
+
+    #[cfg(windows)]
+    fn stdout_is_terminal(&self) -> bool {
+        false
+    }
+
+    fn stdout_width(&self) -> Option<usize> {
+        terminal_size::terminal_size().map(|(w, _)| w.0.into())
+    }
+
+    #[cfg(unix)]
+    fn stderr_width(&self) -> Option<usize> {
+        terminal_size::terminal_size_using_fd(libc::STDERR_FILENO).map(|(w, _)| w.0.into())
+    }
+
+    #[cfg(windows)]
+    fn stderr_width(&self) -> Option<usize> {
+        None
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct MockTerminals {
+        stdout_is_terminal: bool,
+        stdout_width: Option<usize>,
+        stderr_width: Option<usize>,
+    }
+
+    impl Terminals for MockTerminals {
+        fn stdout_is_terminal(&self) -> bool           { self.stdout_is_terminal }
+        fn stdout_width(&self)       -> Option<usize>  { self.stdout_width }
+        fn stderr_width(&self)       -> Option<usize>  { self.stderr_width }
+    }
+
+    #[test]
+    fn stdout_width() {
+        let terminals = MockTerminals { stdout_is_terminal: true, stdout_width: Some(80), stderr_width: Some(120) };
+        assert_eq!(automatic_width(&terminals), Some(80));
+    }
+
+    #[test]
+    fn stderr_fallback() {
+        let terminals = MockTerminals { stdout_is_terminal: true, stdout_width: None, stderr_width: Some(120) };
+        assert_eq!(automatic_width(&terminals), Some(120));
+    }
+
+    #[test]
+    fn zero_width_fallback() {
+        let terminals = MockTerminals { stdout_is_terminal: true, stdout_width: Some(0), stderr_width: Some(120) };
+        assert_eq!(automatic_width(&terminals), Some(120));
+    }
+
+    #[test]
+    fn piped_output() {
+        let terminals = MockTerminals { stdout_is_terminal: false, stdout_width: None, stderr_width: Some(120) };
+        assert_eq!(automatic_width(&terminals), None);
+    }
+
+    #[test]
+    fn columns_to_a_terminal() {
+        let terminals = MockTerminals { stdout_is_terminal: true, stdout_width: Some(120), stderr_width: None };
+        assert_eq!(TerminalWidth::SetForTerminal(80).width_for(&terminals), Some(80));
+    }
+
+    #[test]
+    fn columns_when_piped() {
+        let terminals = MockTerminals { stdout_is_terminal: false, stdout_width: None, stderr_width: None };
+        assert_eq!(TerminalWidth::SetForTerminal(80).width_for(&terminals), None);
+    }
+
+    #[test]
+    fn columns_when_piped_with_grid() {
+        let terminals = MockTerminals { stdout_is_terminal: false, stdout_width: None, stderr_width: None };
+        assert_eq!(TerminalWidth::Set(80).width_for(&terminals), Some(80));
+    }
+
+    #[test]
+    fn no_terminals() {
+        let terminals = MockTerminals { stdout_is_terminal: true, stdout_width: None, stderr_width: None };
+        assert_eq!(automatic_width(&terminals), None);
+    }
+}
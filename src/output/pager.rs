@@ -0,0 +1,264 @@
+//! Sending exa’s output through a pager, such as `less`.
+//!
+//! Deciding whether to page in the automatic mode requires knowing how many
+//! lines of output there are going to be, which isn’t known until everything
+//! has been rendered. So when paging is a possibility, the output gets
+//! written to a buffer first, and only sent anywhere once exa has finished.
+
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use log::*;
+
+
+/// When to send the output through a pager.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Paging {
+
+    /// Page the output if it wouldn’t fit on the screen.
+    Automatic,
+
+    /// Page the output whenever it’s going to a terminal.
+    Always,
+
+    /// Never use a pager.
+    Never,
+}
+
+impl Paging {
+
+    /// Whether output that’s `lines` lines long should be sent through a
+    /// pager, given whether stdout is a terminal and the terminal’s height.
+    /// Output that isn’t going to a terminal is never paged, regardless of
+    /// what the user asked for, as there’s nobody there to scroll it.
+    pub fn should_page(self, is_tty: bool, height: Option<usize>, lines: usize) -> bool {
+        if ! is_tty {
+            return false;
+        }
+
+        match self {
+            Self::Never      => false,
+            Self::Always     => true,
+            Self::Automatic  => matches!(height, Some(h) if lines > h),
+        }
+    }
+}
+
+
+/// Look up the height of the terminal, if stdout is connected to one.
+pub fn terminal_height() -> Option<usize> {
+    terminal_size::terminal_size().map(|(_, h)| h.0.into())
+}
+
+
+/// The pager to use when the `PAGER` environment variable isn’t set.
+/// The `-R` flag makes `less` pass colour escape codes through as-is.
+const DEFAULT_PAGER: &str = "less -R";
+
+
+/// The handle that exa writes its output to.
+pub enum Writer {
+
+    /// Output goes straight to stdout, because it’s never going to be paged.
+    Direct(io::Stdout),
+
+    /// Output goes to the file the user asked for instead of stdout.
+    File(io::BufWriter<fs::File>),
+
+    /// Output is kept in memory until exa has finished, at which point it
+    /// gets sent either to a pager or to stdout.
+    Buffered {
+        buffer: Vec<u8>,
+        paging: Paging,
+        height: Option<usize>,
+        pager: Option<OsString>,
+    },
+}
+
+impl Writer {
+
+    /// Create a writer for the given paging preference. Only output that’s
+    /// going to a terminal needs to be buffered; anything else is written
+    /// directly. So is output that would be paged with exa itself, as it’d
+    /// only try to page it again.
+    pub fn new(paging: Paging, is_tty: bool, height: Option<usize>, pager: Option<OsString>) -> Self {
+        if paging == Paging::Never || ! is_tty {
+            Self::Direct(io::stdout())
+        }
+        else if pager.as_deref().map_or(false, runs_exa) {
+            warn!("Pager {:?} is exa, so not paging", pager);
+            Self::Direct(io::stdout())
+        }
+        else {
+            Self::Buffered { buffer: Vec::new(), paging, height, pager }
+        }
+    }
+
+    /// Create a writer that writes to the file at the given path, creating
+    /// it or cutting an existing one down to nothing first.
+    pub fn to_file(path: &Path) -> io::Result<Self> {
+        let file = fs::File::create(path)?;
+        Ok(Self::File(io::BufWriter::new(file)))
+    }
+
+    /// Send any output that has been held back to where it needs to go,
+    /// spawning the pager if there’s enough of it.
+    pub fn finish(self) -> io::Result<()> {
+        match self {
+            Self::Direct(mut stdout) => {
+                stdout.flush()
+            }
+
+            Self::File(mut file) => {
+                file.flush()
+            }
+
+            Self::Buffered { buffer, paging, height, pager } => {
+                #[allow(clippy::naive_bytecount)]
+                let lines = buffer.iter().filter(|&&b| b == b'\n').count();
+
+                if paging.should_page(true, height, lines) {
+                    match spawn_pager(pager, &buffer) {
+                        Ok(()) => return Ok(()),
+                        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return Err(e),
+                        Err(e) => warn!("Failed to run pager: {}", e),
+                    }
+                }
+
+                let mut stdout = io::stdout();
+                stdout.write_all(&buffer)?;
+                stdout.flush()
+            }
+        }
+    }
+}
+
+impl Write for Writer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Direct(stdout)          => stdout.write(buf),
+            Self::File(file)              => file.write(buf),
+            Self::Buffered { buffer, .. } => buffer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Direct(stdout)   => stdout.flush(),
+            Self::File(file)       => file.flush(),
+            Self::Buffered { .. }  => Ok(()),
+        }
+    }
+}
+
+
+/// Whether the given pager command would run exa. Only the program’s name
+/// gets compared, as a pager is usually found through the `PATH` rather
+/// than given as a full path.
+fn runs_exa(pager: &OsStr) -> bool {
+    let command = pager.to_string_lossy();
+    let program = match command.split_whitespace().next().and_then(|p| Path::new(p).file_stem()) {
+        Some(p)  => p,
+        None     => return false,
+    };
+
+    program == "exa" || std::env::current_exe().ok()
+                                               .and_then(|exe| exe.file_stem().map(|stem| stem == program))
+                                               .unwrap_or(false)
+}
+
+
+/// Run the pager command, feeding it the output through its stdin, and wait
+/// for the user to quit it.
+fn spawn_pager(pager: Option<OsString>, output: &[u8]) -> io::Result<()> {
+    let command = pager.and_then(|p| p.into_string().ok())
+                       .filter(|p| ! p.trim().is_empty())
+                       .unwrap_or_else(|| DEFAULT_PAGER.into());
+
+    let mut words = command.split_whitespace();
+    let program = words.next().unwrap_or("less");
+
+    let mut cmd = Command::new(program);
+    cmd.args(words).stdin(Stdio::piped());
+
+    // Users with a plain `PAGER=less` would otherwise see escape codes
+    // instead of colours, so tell less to let them through.
+    if std::env::var_os("LESS").is_none() {
+        cmd.env("LESS", "R");
+    }
+
+    debug!("Spawning pager {:?}", cmd);
+    let mut child = cmd.spawn()?;
+
+    // The pager can quit before reading everything, which breaks the pipe,
+    // but it still has to be waited for before handing back the error, or
+    // it gets left behind while the terminal’s still in its hands.
+    let written = match child.stdin.take() {
+        Some(mut stdin)  => stdin.write_all(output),
+        None             => Ok(()),
+    };
+
+    child.wait()?;
+    written
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn never_page_when_piped() {
+        assert!(! Paging::Automatic.should_page(false, Some(24), 100));
+        assert!(! Paging::Always.should_page(false, Some(24), 100));
+        assert!(! Paging::Always.should_page(false, None, 0));
+    }
+
+    #[test]
+    fn piped_output_is_not_buffered() {
+        let writer = Writer::new(Paging::Always, false, Some(24), None);
+        assert!(matches!(writer, Writer::Direct(_)));
+    }
+
+    #[test]
+    fn never_means_never() {
+        assert!(! Paging::Never.should_page(true, Some(24), 100));
+        assert!(matches!(Writer::new(Paging::Never, true, Some(24), None), Writer::Direct(_)));
+    }
+
+    #[test]
+    fn automatic_only_when_too_tall() {
+        assert!(! Paging::Automatic.should_page(true, Some(24), 24));
+        assert!(Paging::Automatic.should_page(true, Some(24), 25));
+        assert!(! Paging::Automatic.should_page(true, None, 100));
+    }
+
+    #[test]
+    fn not_paged_with_exa() {
+        let exe = std::env::current_exe().unwrap();
+        let name = exe.file_name().unwrap().to_os_string();
+
+        assert!(matches!(Writer::new(Paging::Always, true, Some(24), Some(name)), Writer::Direct(_)));
+        assert!(matches!(Writer::new(Paging::Always, true, Some(24), Some(exe.into_os_string())), Writer::Direct(_)));
+        assert!(matches!(Writer::new(Paging::Always, true, Some(24), Some("exa --oneline".into())), Writer::Direct(_)));
+        assert!(matches!(Writer::new(Paging::Always, true, Some(24), Some("less".into())), Writer::Buffered { .. }));
+    }
+
+    #[test]
+    fn always_when_interactive() {
+        assert!(Paging::Always.should_page(true, Some(24), 1));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn pager_that_quits_early() {
+        // More than fits in a pipe, so the write is still going when the
+        // pager exits without reading any of it.
+        let output = vec![ b'x'; 1024 * 1024 ];
+        let error = spawn_pager(Some("true".into()), &output).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+    }
+}
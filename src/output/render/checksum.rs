@@ -0,0 +1,57 @@
+use ansi_term::Style;
+
+use crate::fs::fields as f;
+use crate::output::cell::TextCell;
+
+
+impl f::Checksum {
+    pub fn render<C: Colours>(&self, colours: &C) -> TextCell {
+        match self {
+            Self::Some(hash)  => TextCell::paint(colours.checksum(), hash.clone()),
+            Self::None        => TextCell::blank(colours.no_checksum()),
+        }
+    }
+}
+
+
+pub trait Colours {
+    fn checksum(&self) -> Style;
+    fn no_checksum(&self) -> Style;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use ansi_term::Style;
+    use ansi_term::Colour::*;
+
+    use super::Colours;
+    use crate::output::cell::TextCell;
+    use crate::fs::fields as f;
+
+
+    struct TestColours;
+
+    impl Colours for TestColours {
+        fn checksum(&self)    -> Style { Cyan.normal() }
+        fn no_checksum(&self) -> Style { Black.italic() }
+    }
+
+
+    #[test]
+    fn no_checksum() {
+        let checksum = f::Checksum::None;
+        let expected = TextCell::blank(Black.italic());
+
+        assert_eq!(expected, checksum.render(&TestColours));
+    }
+
+
+    #[test]
+    fn some_checksum() {
+        let checksum = f::Checksum::Some("cbf43926".into());
+        let expected = TextCell::paint_str(Cyan.normal(), "cbf43926");
+
+        assert_eq!(expected, checksum.render(&TestColours));
+    }
+}
@@ -0,0 +1,72 @@
+use ansi_term::Style;
+
+use crate::fs::fields as f;
+use crate::output::cell::TextCell;
+
+
+impl f::Encoding {
+    pub fn render<C: Colours>(self, colours: &C) -> TextCell {
+        let label = match self {
+            Self::Ascii    => "ascii",
+            Self::Utf8     => "utf-8",
+            Self::Utf16Le  => "utf-16le",
+            Self::Utf16Be  => "utf-16be",
+            Self::Binary   => return TextCell::paint_str(colours.binary(), "binary"),
+            Self::None     => return TextCell::blank(colours.no_encoding()),
+        };
+
+        TextCell::paint_str(colours.encoding(), label)
+    }
+}
+
+
+pub trait Colours {
+    fn encoding(&self) -> Style;
+    fn binary(&self) -> Style;
+    fn no_encoding(&self) -> Style;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use ansi_term::Style;
+    use ansi_term::Colour::*;
+
+    use super::Colours;
+    use crate::output::cell::TextCell;
+    use crate::fs::fields as f;
+
+
+    struct TestColours;
+
+    impl Colours for TestColours {
+        fn encoding(&self)    -> Style { Cyan.normal() }
+        fn binary(&self)      -> Style { Red.normal() }
+        fn no_encoding(&self) -> Style { Black.italic() }
+    }
+
+
+    #[test]
+    fn utf16le() {
+        let expected = TextCell::paint_str(Cyan.normal(), "utf-16le");
+        assert_eq!(expected, f::Encoding::Utf16Le.render(&TestColours));
+    }
+
+    #[test]
+    fn ascii() {
+        let expected = TextCell::paint_str(Cyan.normal(), "ascii");
+        assert_eq!(expected, f::Encoding::Ascii.render(&TestColours));
+    }
+
+    #[test]
+    fn binary() {
+        let expected = TextCell::paint_str(Red.normal(), "binary");
+        assert_eq!(expected, f::Encoding::Binary.render(&TestColours));
+    }
+
+    #[test]
+    fn none() {
+        let expected = TextCell::blank(Black.italic());
+        assert_eq!(expected, f::Encoding::None.render(&TestColours));
+    }
+}
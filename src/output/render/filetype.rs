@@ -1,6 +1,7 @@
 use ansi_term::{ANSIString, Style};
 
 use crate::fs::fields as f;
+use crate::output::cell::TextCell;
 
 
 impl f::Type {
@@ -16,6 +17,35 @@ impl f::Type {
             Self::Special      => colours.special().paint("?"),
         }
     }
+
+    /// The style that this type gets painted in, wherever it’s shown.
+    pub fn style<C: Colours>(self, colours: &C) -> Style {
+        match self {
+            Self::File         => colours.normal(),
+            Self::Directory    => colours.directory(),
+            Self::Pipe         => colours.pipe(),
+            Self::Link         => colours.symlink(),
+            Self::BlockDevice  => colours.block_device(),
+            Self::CharDevice   => colours.char_device(),
+            Self::Socket       => colours.socket(),
+            Self::Special      => colours.special(),
+        }
+    }
+
+    /// Renders the whole name of the type, for the type column, rather
+    /// than the single character in the permissions.
+    pub fn render_name<C: Colours>(self, colours: &C) -> TextCell {
+        match self {
+            Self::File         => TextCell::paint_str(colours.normal(), "file"),
+            Self::Directory    => TextCell::paint_str(colours.directory(), "dir"),
+            Self::Pipe         => TextCell::paint_str(colours.pipe(), "pipe"),
+            Self::Link         => TextCell::paint_str(colours.symlink(), "link"),
+            Self::BlockDevice  => TextCell::paint_str(colours.block_device(), "block"),
+            Self::CharDevice   => TextCell::paint_str(colours.char_device(), "char"),
+            Self::Socket       => TextCell::paint_str(colours.socket(), "socket"),
+            Self::Special      => TextCell::paint_str(colours.special(), "other"),
+        }
+    }
 }
 
 
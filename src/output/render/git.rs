@@ -106,4 +106,23 @@ pub mod test {
 
         assert_eq!(expected, stati.render(&TestColours))
     }
+
+
+    #[test]
+    fn git_conflicted() {
+        let stati = f::Git {
+            staged:   f::GitStatus::Conflicted,
+            unstaged: f::GitStatus::Conflicted,
+        };
+
+        let expected = TextCell {
+            width: DisplayWidth::from(2),
+            contents: vec![
+                Fixed(97).paint("U"),
+                Fixed(97).paint("U"),
+            ].into(),
+        };
+
+        assert_eq!(expected, stati.render(&TestColours))
+    }
 }
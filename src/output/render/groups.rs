@@ -5,6 +5,8 @@ use crate::fs::fields as f;
 use crate::output::cell::TextCell;
 use crate::output::table::UserFormat;
 
+use super::users::truncate_name;
+
 
 impl f::Group {
     pub fn render<C: Colours, U: Users+Groups>(self, colours: &C, users: &U, format: UserFormat) -> TextCell {
@@ -27,12 +29,18 @@ impl f::Group {
             }
         }
 
-        let group_name = match format {
-            UserFormat::Name => group.name().to_string_lossy().into(),
-            UserFormat::Numeric => group.gid().to_string(),
-        };
+        TextCell::paint(style, self.name(users, format))
+    }
 
-        TextCell::paint(style, group_name)
+    /// The text to display for this group: its name, or its ID if it doesn’t
+    /// have one or numbers were asked for.
+    pub fn name<U: Groups>(self, users: &U, format: UserFormat) -> String {
+        match (format, users.get_group_by_gid(self.0)) {
+            (_, None)                        => self.0.to_string(),
+            (UserFormat::Numeric, _)         => self.0.to_string(),
+            (UserFormat::Name, Some(group))  => group.name().to_string_lossy().into(),
+            (UserFormat::Truncated(width), Some(group))  => truncate_name(group.name().to_string_lossy().into(), width),
+        }
     }
 }
 
@@ -11,6 +11,13 @@ impl f::Inode {
 }
 
 
+impl f::DeviceInode {
+    pub fn render(self, style: Style) -> TextCell {
+        TextCell::paint(style, format!("{}:{}", self.device, self.inode))
+    }
+}
+
+
 #[cfg(test)]
 pub mod test {
     use crate::output::cell::TextCell;
@@ -25,4 +32,18 @@ pub mod test {
         let expected = TextCell::paint_str(Cyan.underline(), "1414213");
         assert_eq!(expected, io.render(Cyan.underline()));
     }
+
+    #[test]
+    fn device_and_inode() {
+        let di = f::DeviceInode { device: 2049, inode: 1_414_213 };
+        let expected = TextCell::paint_str(Cyan.underline(), "2049:1414213");
+        assert_eq!(expected, di.render(Cyan.underline()));
+    }
+
+    #[test]
+    fn same_inode_on_different_devices() {
+        let one   = f::DeviceInode { device: 2049, inode: 12 };
+        let other = f::DeviceInode { device: 64769, inode: 12 };
+        assert_ne!(one.render(Cyan.normal()), other.render(Cyan.normal()));
+    }
 }
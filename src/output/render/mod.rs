@@ -1,6 +1,12 @@
 mod blocks;
 pub use self::blocks::Colours as BlocksColours;
 
+mod checksum;
+pub use self::checksum::Colours as ChecksumColours;
+
+mod encoding;
+pub use self::encoding::Colours as EncodingColours;
+
 mod filetype;
 pub use self::filetype::Colours as FiletypeColours;
 
@@ -18,14 +24,23 @@ mod inode;
 mod links;
 pub use self::links::Colours as LinksColours;
 
+#[cfg(unix)]
+mod owner;
+#[cfg(unix)]
+pub use self::owner::Colours as OwnerColours;
+
 mod permissions;
 pub use self::permissions::Colours as PermissionsColours;
 
+mod security;
+pub use self::security::Colours as SecurityColours;
+
 mod size;
 pub use self::size::Colours as SizeColours;
 
 mod times;
 pub use self::times::Render as TimeRender;
+pub use self::times::{Colours as DateAgeColours, Recency};
 // times does too
 
 #[cfg(unix)]
@@ -33,5 +48,16 @@ mod users;
 #[cfg(unix)]
 pub use self::users::Colours as UserColours;
 
+mod version;
+pub use self::version::Colours as VersionColours;
+
+#[cfg(unix)]
+mod volume;
+#[cfg(unix)]
+pub use self::volume::Colours as VolumeColours;
+
+mod mount_info;
+pub use self::mount_info::Colours as MountInfoColours;
+
 mod octal;
 // octal uses just one colour
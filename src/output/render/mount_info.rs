@@ -0,0 +1,88 @@
+use ansi_term::Style;
+
+use crate::fs::fields as f;
+use crate::output::cell::{TextCell, DisplayWidth};
+
+
+impl f::MountInfo {
+
+    /// Renders a mount point as two letters: a ‘`b`’ if it’s a bind mount,
+    /// then its propagation, which is one of ‘`p`’ for private, ‘`s`’ for
+    /// shared, ‘`m`’ for a slave with a master, or ‘`u`’ for unbindable.
+    /// Anything that isn’t a mount point gets a dash.
+    pub fn render<C: Colours>(self, colours: &C) -> TextCell {
+        let (bind, propagation) = match self {
+            Self::Mount { bind, propagation }  => (bind, propagation),
+            Self::NotMount                     => return TextCell::blank(colours.no_mount()),
+        };
+
+        let bind = if bind { colours.mount().paint("b") }
+                      else { colours.no_mount().paint("-") };
+
+        let propagation = match propagation {
+            f::Propagation::Private     => "p",
+            f::Propagation::Shared      => "s",
+            f::Propagation::Slave       => "m",
+            f::Propagation::Unbindable  => "u",
+        };
+
+        TextCell {
+            width:    DisplayWidth::from(2),
+            contents: vec![ bind, colours.mount().paint(propagation) ].into(),
+        }
+    }
+}
+
+
+pub trait Colours {
+    fn mount(&self) -> Style;
+    fn no_mount(&self) -> Style;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use ansi_term::Style;
+    use ansi_term::Colour::*;
+
+    use super::Colours;
+    use crate::output::cell::{TextCell, DisplayWidth};
+    use crate::fs::fields as f;
+
+
+    struct TestColours;
+
+    impl Colours for TestColours {
+        fn mount(&self)    -> Style { Cyan.normal() }
+        fn no_mount(&self) -> Style { Black.italic() }
+    }
+
+
+    #[test]
+    fn bind_mount() {
+        let expected = TextCell {
+            width:    DisplayWidth::from(2),
+            contents: vec![ Cyan.paint("b"), Cyan.paint("s") ].into(),
+        };
+
+        let info = f::MountInfo::Mount { bind: true, propagation: f::Propagation::Shared };
+        assert_eq!(expected, info.render(&TestColours));
+    }
+
+    #[test]
+    fn private_mount() {
+        let expected = TextCell {
+            width:    DisplayWidth::from(2),
+            contents: vec![ Black.italic().paint("-"), Cyan.paint("p") ].into(),
+        };
+
+        let info = f::MountInfo::Mount { bind: false, propagation: f::Propagation::Private };
+        assert_eq!(expected, info.render(&TestColours));
+    }
+
+    #[test]
+    fn not_mount() {
+        let expected = TextCell::blank(Black.italic());
+        assert_eq!(expected, f::MountInfo::NotMount.render(&TestColours));
+    }
+}
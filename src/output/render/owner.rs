@@ -0,0 +1,97 @@
+use ansi_term::Style;
+use users::{Users, Groups};
+
+use crate::fs::fields as f;
+use crate::output::cell::TextCell;
+use crate::output::table::UserFormat;
+
+use super::groups::Colours as GroupColours;
+use super::users::Colours as UserColours;
+
+
+impl f::Owner {
+    pub fn render<C: Colours, U: Users+Groups>(self, colours: &C, users: &U, format: UserFormat) -> TextCell {
+        let mut cell = self.user.render(colours, users, format);
+
+        // Personal files usually have a group named after their user, and
+        // there’s no point saying it twice.
+        if self.user.name(users, format) != self.group.name(users, format) {
+            cell.push(colours.separator().paint(":"), 1);
+            cell.append(self.group.render(colours, users, format));
+        }
+
+        cell
+    }
+}
+
+
+pub trait Colours: UserColours + GroupColours {
+    fn separator(&self) -> Style;
+}
+
+
+#[cfg(test)]
+#[allow(unused_results)]
+pub mod test {
+    use super::Colours;
+    use crate::fs::fields as f;
+    use crate::output::cell::TextCell;
+    use crate::output::render::{UserColours, GroupColours};
+    use crate::output::table::UserFormat;
+
+    use users::{User, Group};
+    use users::mock::MockUsers;
+    use ansi_term::Colour::*;
+    use ansi_term::Style;
+
+
+    struct TestColours;
+
+    impl UserColours for TestColours {
+        fn you(&self)          -> Style { Red.bold() }
+        fn someone_else(&self) -> Style { Blue.underline() }
+    }
+
+    impl GroupColours for TestColours {
+        fn yours(&self)     -> Style { Fixed(80).normal() }
+        fn not_yours(&self) -> Style { Fixed(81).normal() }
+    }
+
+    impl Colours for TestColours {
+        fn separator(&self) -> Style { Fixed(244).normal() }
+    }
+
+
+    #[test]
+    fn same_name() {
+        let mut users = MockUsers::with_current_uid(1000);
+        users.add_user(User::new(1000, "enoch", 1000));
+        users.add_group(Group::new(1000, "enoch"));
+
+        let owner = f::Owner { user: f::User(1000), group: f::Group(1000) };
+        let expected = TextCell::paint_str(Red.bold(), "enoch");
+        assert_eq!(expected, owner.render(&TestColours, &users, UserFormat::Name));
+    }
+
+    #[test]
+    fn different_names() {
+        let mut users = MockUsers::with_current_uid(1000);
+        users.add_user(User::new(1000, "enoch", 1000));
+        users.add_group(Group::new(100, "folk"));
+
+        let owner = f::Owner { user: f::User(1000), group: f::Group(100) };
+        let mut expected = TextCell::paint_str(Red.bold(), "enoch");
+        expected.push(Fixed(244).normal().paint(":"), 1);
+        expected.append(TextCell::paint_str(Fixed(81).normal(), "folk"));
+        assert_eq!(expected, owner.render(&TestColours, &users, UserFormat::Name));
+    }
+
+    #[test]
+    fn same_number() {
+        let users = MockUsers::with_current_uid(0);
+
+        let owner = f::Owner { user: f::User(1000), group: f::Group(1000) };
+        let expected = TextCell::paint_str(Blue.underline(), "1000");
+        assert_eq!(expected, owner.render(&TestColours, &users, UserFormat::Numeric));
+    }
+}
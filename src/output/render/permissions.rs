@@ -88,6 +88,24 @@ impl f::Permissions {
     }
 }
 
+impl f::EffectivePermissions {
+    pub fn render<C: Colours>(self, colours: &C) -> TextCell {
+        let bit = |bit, chr: &'static str, style: Style| {
+            if bit { style.paint(chr) }
+              else { colours.dash().paint("-") }
+        };
+
+        TextCell {
+            width:    DisplayWidth::from(3),
+            contents: vec![
+                bit(self.read,    "r", colours.user_read()),
+                bit(self.write,   "w", colours.user_write()),
+                bit(self.execute, "x", colours.user_execute_file()),
+            ].into(),
+        }
+    }
+}
+
 impl f::Attributes {
     pub fn render<C: Colours+FiletypeColours>(&self, colours: &C) -> Vec<ANSIString<'static>> {
         let bit = |bit, chr: &'static str, style: Style| {
@@ -240,4 +258,16 @@ pub mod test {
 
         assert_eq!(expected, bits.render(&TestColours, true).into())
     }
+
+
+    #[test]
+    fn effective_read_write() {
+        let access = f::EffectivePermissions { read: true, write: true, execute: false };
+
+        let expected = TextCellContents::from(vec![
+            Fixed(101).paint("r"),  Fixed(102).paint("w"),  Fixed(11).paint("-"),
+        ]);
+
+        assert_eq!(expected, access.render(&TestColours).contents)
+    }
 }
@@ -0,0 +1,97 @@
+use ansi_term::Style;
+
+use crate::fs::fields as f;
+use crate::output::cell::TextCell;
+
+
+impl f::SecuritySummary {
+
+    /// Renders the summary as an ‘x’ followed by the number of extended
+    /// attributes, then a ‘+’ if the file has an ACL, so two attributes and
+    /// an ACL comes out as ‘x2+’.
+    pub fn render<C: Colours>(self, colours: &C) -> TextCell {
+        if self.xattrs == 0 && ! self.acl {
+            return TextCell::blank(colours.no_security());
+        }
+
+        let mut cell = TextCell::default();
+
+        if self.xattrs > 0 {
+            let count = format!("x{}", self.xattrs);
+            let width = count.len();
+            cell.push(colours.attributes().paint(count), width);
+        }
+
+        if self.acl {
+            cell.push(colours.acl().paint("+"), 1);
+        }
+
+        cell
+    }
+}
+
+
+pub trait Colours {
+    fn attributes(&self) -> Style;
+    fn acl(&self) -> Style;
+    fn no_security(&self) -> Style;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use std::io;
+
+    use ansi_term::Style;
+    use ansi_term::Colour::*;
+
+    use super::Colours;
+    use crate::fs::feature::xattr::{self, Attribute, FileAttributes};
+    use crate::fs::fields as f;
+    use crate::output::cell::TextCell;
+
+
+    struct TestColours;
+
+    impl Colours for TestColours {
+        fn attributes(&self)  -> Style { Cyan.normal() }
+        fn acl(&self)         -> Style { Yellow.bold() }
+        fn no_security(&self) -> Style { Black.italic() }
+    }
+
+    struct FakeFile(&'static [&'static str]);
+
+    impl FileAttributes for FakeFile {
+        fn attributes(&self) -> io::Result<Vec<Attribute>> {
+            Ok(self.0.iter().map(|&name| Attribute { name: name.to_string(), size: 0 }).collect())
+        }
+
+        fn symlink_attributes(&self) -> io::Result<Vec<Attribute>> {
+            self.attributes()
+        }
+    }
+
+
+    #[test]
+    fn two_xattrs_and_an_acl() {
+        let file = FakeFile(&[ "user.one", "system.posix_acl_access", "user.two" ]);
+        let summary = xattr::security_summary(&file);
+        assert_eq!(summary, f::SecuritySummary { xattrs: 2, acl: true });
+
+        let mut expected = TextCell::paint_str(Cyan.normal(), "x2");
+        expected.push(Yellow.bold().paint("+"), 1);
+        assert_eq!(expected, summary.render(&TestColours));
+    }
+
+    #[test]
+    fn just_an_acl() {
+        let summary = f::SecuritySummary { xattrs: 0, acl: true };
+        assert_eq!(TextCell::paint_str(Yellow.bold(), "+"), summary.render(&TestColours));
+    }
+
+    #[test]
+    fn nothing() {
+        let summary = xattr::security_summary(&FakeFile(&[]));
+        assert_eq!(TextCell::blank(Black.italic()), summary.render(&TestColours));
+    }
+}
@@ -20,6 +20,33 @@ impl f::Size {
         let result = match size_format {
             SizeFormat::DecimalBytes  => NumberPrefix::decimal(size as f64),
             SizeFormat::BinaryBytes   => NumberPrefix::binary(size as f64),
+            SizeFormat::BothBytes     => {
+
+                // Show the binary size as usual, then the decimal one after
+                // it, unless there’s no prefix to tell them apart.
+                let mut cell = Self::Some(size).render(colours, SizeFormat::BinaryBytes, numerics);
+
+                if let NumberPrefix::Prefixed(prefix, n) = NumberPrefix::decimal(size as f64) {
+                    let number = if n < 10_f64 {
+                        numerics.format_float(n, 2)
+                    } else if n < 100_f64 {
+                        numerics.format_float(n, 1)
+                    } else {
+                        numerics.format_int(n.round() as isize)
+                    };
+
+                    // symbol is guaranteed to be ASCII here too.
+                    let unit = format!(" {}B)", prefix.symbol());
+                    let number_width = *DisplayWidth::from(&*number);
+                    let unit_width = unit.len();
+
+                    cell.push(colours.unit(Some(prefix)).paint(" ("), 2);
+                    cell.push(colours.size(Some(prefix)).paint(number), number_width);
+                    cell.push(colours.unit(Some(prefix)).paint(unit), unit_width);
+                }
+
+                return cell;
+            }
             SizeFormat::JustBytes     => {
 
                 // Use the binary prefix to select a style.
@@ -56,6 +83,16 @@ impl f::Size {
             ].into(),
         }
     }
+
+    /// Renders the size as a plain number of bytes if it’s smaller than the
+    /// given threshold, and in the given format if it’s not, or if there’s
+    /// no threshold at all.
+    pub fn render_above<C: Colours>(self, colours: &C, size_format: SizeFormat, threshold: Option<u64>, numerics: &NumericLocale) -> TextCell {
+        match (self, threshold) {
+            (Self::Some(size), Some(threshold)) if size < threshold  => self.render(colours, SizeFormat::JustBytes, numerics),
+            _                                                        => self.render(colours, size_format, numerics),
+        }
+    }
 }
 
 
@@ -165,6 +202,63 @@ pub mod test {
     }
 
 
+    #[test]
+    fn file_both() {
+        let directory = f::Size::Some(1_048_576);
+        let expected = TextCell {
+            width: DisplayWidth::from(15),
+            contents: vec![
+                Fixed(66).paint("1.0"),
+                Fixed(77).bold().paint("Mi"),
+                Fixed(77).bold().paint(" ("),
+                Fixed(66).paint("1.05"),
+                Fixed(77).bold().paint(" MB)"),
+            ].into(),
+        };
+
+        assert_eq!(expected, directory.render(&TestColours, SizeFormat::BothBytes, &NumericLocale::english()))
+    }
+
+
+    #[test]
+    fn small_file_both() {
+        let directory = f::Size::Some(512);
+        let expected = TextCell {
+            width: DisplayWidth::from(3),
+            contents: vec![
+                Fixed(66).paint("512"),
+            ].into(),
+        };
+
+        assert_eq!(expected, directory.render(&TestColours, SizeFormat::BothBytes, &NumericLocale::english()))
+    }
+
+
+    #[test]
+    fn human_above_threshold() {
+        let small = f::Size::Some(10);
+        let expected = TextCell {
+            width: DisplayWidth::from(2),
+            contents: vec![
+                Fixed(66).paint("10"),
+            ].into(),
+        };
+
+        assert_eq!(expected, small.render_above(&TestColours, SizeFormat::DecimalBytes, Some(1000), &NumericLocale::english()));
+
+        let large = f::Size::Some(10_000_000);
+        let expected = TextCell {
+            width: DisplayWidth::from(3),
+            contents: vec![
+                Fixed(66).paint("10"),
+                Fixed(77).bold().paint("M"),
+            ].into(),
+        };
+
+        assert_eq!(expected, large.render_above(&TestColours, SizeFormat::DecimalBytes, Some(1000), &NumericLocale::english()));
+    }
+
+
     #[test]
     fn device_ids() {
         let directory = f::Size::DeviceIDs(f::DeviceIDs { major: 10, minor: 80 });
@@ -1,24 +1,24 @@
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 use datetime::TimeZone;
 use ansi_term::Style;
 
 use crate::output::cell::TextCell;
-use crate::output::time::TimeFormat;
+use crate::output::time::{TimeFormat, SubSeconds};
 
 
 pub trait Render {
-    fn render(self, style: Style, tz: &Option<TimeZone>, format: TimeFormat) -> TextCell;
+    fn render(self, style: Style, tz: &Option<TimeZone>, format: TimeFormat, sub: SubSeconds) -> TextCell;
 }
 
 impl Render for Option<SystemTime> {
-    fn render(self, style: Style, tz: &Option<TimeZone>, format: TimeFormat) -> TextCell {
+    fn render(self, style: Style, tz: &Option<TimeZone>, format: TimeFormat, sub: SubSeconds) -> TextCell {
         let datestamp = if let Some(time) = self {
             if let Some(ref tz) = tz {
-                format.format_zoned(time, tz)
+                format.format_zoned(time, tz, sub)
             }
             else {
-                format.format_local(time)
+                format.format_local(time, sub)
             }
         }
         else {
@@ -28,3 +28,84 @@ impl Render for Option<SystemTime> {
         TextCell::paint(style, datestamp)
     }
 }
+
+
+/// How long ago a timestamp was, for colouring dates by their age with
+/// `--colour-age`. Times in the future count as recent.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Recency {
+
+    /// Within the last day.
+    Today,
+
+    /// Within the last week, but not the last day.
+    ThisWeek,
+
+    /// Any longer ago than that.
+    Older,
+}
+
+impl Recency {
+    const DAY:  Duration = Duration::from_secs(24 * 60 * 60);
+    const WEEK: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+    /// Which bucket the given time falls into, counting back from `now`.
+    pub fn of(time: SystemTime, now: SystemTime) -> Self {
+        let age = now.duration_since(time).unwrap_or_default();
+
+        if      age < Self::DAY   { Self::Today }
+        else if age < Self::WEEK  { Self::ThisWeek }
+        else                      { Self::Older }
+    }
+
+    /// The style that dates in this bucket get painted in.
+    pub fn style<C: Colours>(self, colours: &C) -> Style {
+        match self {
+            Self::Today     => colours.today(),
+            Self::ThisWeek  => colours.this_week(),
+            Self::Older     => colours.older(),
+        }
+    }
+}
+
+
+pub trait Colours {
+    fn today(&self) -> Style;
+    fn this_week(&self) -> Style;
+    fn older(&self) -> Style;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use ansi_term::Colour::*;
+
+
+    struct TestColours;
+
+    impl Colours for TestColours {
+        fn today(&self)     -> Style { Cyan.bold() }
+        fn this_week(&self) -> Style { Blue.normal() }
+        fn older(&self)     -> Style { Blue.dimmed() }
+    }
+
+
+    #[test]
+    fn three_ages() {
+        let now = SystemTime::now();
+        let hour = Duration::from_secs(60 * 60);
+
+        let styles = [ now - hour, now - 72 * hour, now - 30 * 24 * hour ].iter()
+                         .map(|time| Recency::of(*time, now).style(&TestColours))
+                         .collect::<Vec<_>>();
+
+        assert_eq!(styles, vec![ Cyan.bold(), Blue.normal(), Blue.dimmed() ]);
+    }
+
+    #[test]
+    fn future_is_today() {
+        let now = SystemTime::now();
+        assert_eq!(Recency::of(now + Duration::from_secs(60), now), Recency::Today);
+    }
+}
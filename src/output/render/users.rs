@@ -1,4 +1,5 @@
 use ansi_term::Style;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 use users::Users;
 
 use crate::fs::fields as f;
@@ -8,16 +9,45 @@ use crate::output::table::UserFormat;
 
 impl f::User {
     pub fn render<C: Colours, U: Users>(self, colours: &C, users: &U, format: UserFormat) -> TextCell {
-        let user_name = match (format, users.get_user_by_uid(self.0)) {
+        let style = if users.get_current_uid() == self.0 { colours.you() }
+                                                    else { colours.someone_else() };
+        TextCell::paint(style, self.name(users, format))
+    }
+
+    /// The text to display for this user: their name, or their ID if they
+    /// don’t have one or numbers were asked for.
+    pub fn name<U: Users>(self, users: &U, format: UserFormat) -> String {
+        match (format, users.get_user_by_uid(self.0)) {
             (_, None)                      => self.0.to_string(),
             (UserFormat::Numeric, _)       => self.0.to_string(),
             (UserFormat::Name, Some(user)) => user.name().to_string_lossy().into(),
-        };
+            (UserFormat::Truncated(width), Some(user)) => truncate_name(user.name().to_string_lossy().into(), width),
+        }
+    }
+}
 
-        let style = if users.get_current_uid() == self.0 { colours.you() }
-                                                    else { colours.someone_else() };
-        TextCell::paint(style, user_name)
+
+/// Cuts a user or group name down to the given width, if it’s any wider,
+/// ending it with an ellipsis to show that something’s missing.
+pub fn truncate_name(name: String, width: usize) -> String {
+    if UnicodeWidthStr::width(name.as_str()) <= width {
+        return name;
+    }
+
+    let mut truncated = String::new();
+    let mut truncated_width = 0;
+    for c in name.chars() {
+        let char_width = UnicodeWidthChar::width(c).unwrap_or(0);
+        if truncated_width + char_width + 1 > width {
+            break;
+        }
+
+        truncated_width += char_width;
+        truncated.push(c);
     }
+
+    truncated.push('…');
+    truncated
 }
 
 
@@ -82,6 +112,22 @@ pub mod test {
         assert_eq!(expected, user.render(&TestColours, &users, UserFormat::Name));
     }
 
+    #[test]
+    fn truncated() {
+        let mut users = MockUsers::with_current_uid(1000);
+        users.add_user(User::new(1000, "enoch.of.the.directory", 100));
+        users.add_user(User::new(1001, "enoch", 100));
+
+        let expected = TextCell::paint_str(Red.bold(), "enoch.o…");
+        assert_eq!(expected, f::User(1000).render(&TestColours, &users, UserFormat::Truncated(8)));
+
+        let expected = TextCell::paint_str(Blue.underline(), "enoch");
+        assert_eq!(expected, f::User(1001).render(&TestColours, &users, UserFormat::Truncated(8)));
+
+        let expected = TextCell::paint_str(Red.bold(), "1000");
+        assert_eq!(expected, f::User(1000).render(&TestColours, &users, UserFormat::Numeric));
+    }
+
     #[test]
     fn different_unnamed() {
         let user = f::User(1000);
@@ -0,0 +1,57 @@
+use ansi_term::Style;
+
+use crate::fs::fields as f;
+use crate::output::cell::TextCell;
+
+
+impl f::Version {
+    pub fn render<C: Colours>(self, colours: &C) -> TextCell {
+        match self {
+            Self::Some(version)  => TextCell::paint(colours.version(), version.to_string()),
+            Self::None           => TextCell::blank(colours.no_version()),
+        }
+    }
+}
+
+
+pub trait Colours {
+    fn version(&self) -> Style;
+    fn no_version(&self) -> Style;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use ansi_term::Style;
+    use ansi_term::Colour::*;
+
+    use super::Colours;
+    use crate::output::cell::TextCell;
+    use crate::fs::fields as f;
+
+
+    struct TestColours;
+
+    impl Colours for TestColours {
+        fn version(&self)    -> Style { Purple.bold() }
+        fn no_version(&self) -> Style { Black.italic() }
+    }
+
+
+    #[test]
+    fn versionless() {
+        let version = f::Version::None;
+        let expected = TextCell::blank(Black.italic());
+
+        assert_eq!(expected, version.render(&TestColours));
+    }
+
+
+    #[test]
+    fn versioned() {
+        let version = f::Version::Some(2_814_263_742);
+        let expected = TextCell::paint_str(Purple.bold(), "2814263742");
+
+        assert_eq!(expected, version.render(&TestColours));
+    }
+}
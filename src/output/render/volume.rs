@@ -0,0 +1,52 @@
+use ansi_term::Style;
+
+use crate::fs::fields as f;
+use crate::output::cell::TextCell;
+
+
+impl f::Volume<'_> {
+    pub fn render<C: Colours>(self, colours: &C) -> TextCell {
+        match self.0 {
+            Some(label)  => TextCell::paint(colours.volume(), label.to_owned()),
+            None         => TextCell::blank(colours.no_volume()),
+        }
+    }
+}
+
+
+pub trait Colours {
+    fn volume(&self) -> Style;
+    fn no_volume(&self) -> Style;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use ansi_term::Style;
+    use ansi_term::Colour::*;
+
+    use super::Colours;
+    use crate::output::cell::TextCell;
+    use crate::fs::fields as f;
+
+
+    struct TestColours;
+
+    impl Colours for TestColours {
+        fn volume(&self)    -> Style { Cyan.normal() }
+        fn no_volume(&self) -> Style { Black.italic() }
+    }
+
+
+    #[test]
+    fn label() {
+        let expected = TextCell::paint_str(Cyan.normal(), "usb");
+        assert_eq!(expected, f::Volume(Some("usb")).render(&TestColours));
+    }
+
+    #[test]
+    fn none() {
+        let expected = TextCell::blank(Black.italic());
+        assert_eq!(expected, f::Volume(None).render(&TestColours));
+    }
+}
@@ -0,0 +1,139 @@
+//! The **stat** view, which lists everything exa knows about one file, one
+//! field per line, rather like `stat(1)`.
+
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use ansi_term::{ANSIString, Style};
+
+use crate::fs::{File, fields as f};
+use crate::output::time::{TimeFormat, SubSeconds};
+use crate::theme::Theme;
+
+
+pub struct Render<'a> {
+    pub files: Vec<File<'a>>,
+    pub theme: &'a Theme,
+}
+
+impl<'a> Render<'a> {
+    pub fn render<W: Write>(self, w: &mut W) -> io::Result<()> {
+        for file in &self.files {
+            self.render_file(w, file)?;
+        }
+
+        Ok(())
+    }
+
+    fn render_file<W: Write>(&self, w: &mut W, file: &File<'_>) -> io::Result<()> {
+        let ui = &self.theme.ui;
+
+        writeln!(w, "{}: {}", label("File"), full_path(file).display())?;
+        writeln!(w, "{}: {} bytes", label("Size"), ui.size.number_byte.paint(file.metadata.len().to_string()))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+
+            let permissions = f::PermissionsPlus { file_type: file.type_char(), permissions: file.permissions(), xattrs: false };
+            let octal = f::OctalPermissions { permissions: file.permissions() };
+            writeln!(w, "{}: {} ({})", label("Access"), permissions.render(self.theme).strings(), octal.render(ui.octal).strings())?;
+
+            writeln!(w, "{}: {}", label("Blocks"), ui.blocks.paint(file.metadata.blocks().to_string()))?;
+            writeln!(w, "{}: {}", label("Inode"), ui.inode.paint(file.metadata.ino().to_string()))?;
+            writeln!(w, "{}: {}", label("Links"), ui.links.normal.paint(file.metadata.nlink().to_string()))?;
+
+            let user_id = file.metadata.uid();
+            let user = users::get_user_by_uid(user_id).map(|u| u.name().to_string_lossy().into_owned());
+            writeln!(w, "{}: {}", label("Owner"), owner(ui.users.user_you, user, user_id))?;
+
+            let group_id = file.metadata.gid();
+            let group = users::get_group_by_gid(group_id).map(|g| g.name().to_string_lossy().into_owned());
+            writeln!(w, "{}: {}", label("Group"), owner(ui.users.group_yours, group, group_id))?;
+        }
+
+        #[cfg(windows)]
+        {
+            let permissions = f::PermissionsPlus { file_type: file.type_char(), attributes: file.attributes(), xattrs: false };
+            writeln!(w, "{}: {}", label("Access"), permissions.render(self.theme).strings())?;
+        }
+
+        writeln!(w, "{}: {}", label("Modified"), timestamp(ui.date, file.modified_time()))?;
+        writeln!(w, "{}: {}", label("Changed"), timestamp(ui.date, file.changed_time()))?;
+        writeln!(w, "{}: {}", label("Accessed"), timestamp(ui.date, file.accessed_time()))?;
+
+        Ok(())
+    }
+}
+
+
+/// Right-aligns a field’s label, so all the values line up.
+fn label(name: &str) -> String {
+    format!("{:>8}", name)
+}
+
+/// The path to the file, made absolute if it was given relative to the
+/// current directory. Symlinks in the path are left as they are.
+fn full_path(file: &File<'_>) -> PathBuf {
+    if file.path.is_absolute() {
+        file.path.clone()
+    }
+    else {
+        std::env::current_dir()
+            .map(|cwd| cwd.join(&file.path))
+            .unwrap_or_else(|_| file.path.clone())
+    }
+}
+
+/// A user or group’s name, if it has one, followed by its ID.
+#[cfg(unix)]
+fn owner(style: Style, name: Option<String>, id: u32) -> ANSIString<'static> {
+    match name {
+        Some(name)  => style.paint(format!("{} ({})", name, id)),
+        None        => style.paint(id.to_string()),
+    }
+}
+
+fn timestamp(style: Style, time: Option<SystemTime>) -> ANSIString<'static> {
+    match time {
+        Some(time)  => style.paint(TimeFormat::FullISO.format_local(time, SubSeconds::Hidden)),
+        None        => style.paint("-"),
+    }
+}
+
+
+#[cfg(test)]
+#[cfg(unix)]
+mod test {
+    use super::*;
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+    use crate::theme::Options as ThemeOptions;
+    use crate::fs::test::TempDir;
+
+    #[test]
+    fn key_fields() {
+        let dir = TempDir::new("stat");
+        let path = dir.join("file");
+        std::fs::write(&path, b"contents").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640)).unwrap();
+
+        let file = File::from_args(path.clone(), None, None).unwrap();
+        let inode = file.metadata.ino();
+
+        let theme = ThemeOptions::plain().to_theme(false);
+
+        let mut output = Vec::new();
+        Render { files: vec![ file ], theme: &theme }.render(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains(&format!("    File: {}\n", path.display())));
+        assert!(output.contains("    Size: 8 bytes\n"));
+        assert!(output.contains("  Access: .rw-r----- (0640)\n"));
+        assert!(output.contains(&format!("   Inode: {}\n", inode)));
+        assert!(output.contains("   Links: 1\n"));
+        assert!(output.contains("Modified: "));
+        assert!(output.contains(" Changed: "));
+        assert!(output.contains("Accessed: "));
+    }
+}
@@ -0,0 +1,198 @@
+//! The summary line that can be printed above a directory’s listing.
+
+use std::path::Path;
+
+use ansi_term::{ANSIString, Style};
+use locale::Numeric as NumericLocale;
+
+use crate::fs::File;
+use crate::fs::feature::filesystem::Space;
+use crate::fs::fields as f;
+use crate::output::escape;
+use crate::theme::Theme;
+
+
+/// The totals for a list of files: how many there are, and how many bytes
+/// they take up between them.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub struct Summary {
+    pub count: usize,
+    pub bytes: u64,
+}
+
+impl FromIterator<f::Size> for Summary {
+    fn from_iter<I: IntoIterator<Item = f::Size>>(iter: I) -> Self {
+        let mut summary = Self::default();
+
+        for size in iter {
+            summary.count += 1;
+
+            // Directories and devices don’t have a size to count, but they
+            // still count as entries.
+            if let f::Size::Some(bytes) = size {
+                summary.bytes += bytes;
+            }
+        }
+
+        summary
+    }
+}
+
+impl Summary {
+
+    /// Totals up the given files, using the same sizes that would be
+    /// displayed in the size column.
+    pub fn of_files(files: &[File<'_>]) -> Self {
+        files.iter().map(File::size).collect()
+    }
+
+    /// Renders the summary as a line of text for the directory at the given
+    /// path, such as `src: 12 entries, 3456 bytes`.
+    pub fn render(&self, path: &Path, theme: &Theme) -> Vec<ANSIString<'static>> {
+        let mut bits = Vec::new();
+        escape(path.display().to_string(), &mut bits, theme.ui.header, theme.ui.control_char);
+        bits.push(theme.ui.punctuation.paint(":"));
+
+        let entries = if self.count == 1 { "entry" } else { "entries" };
+        bits.push(Style::default().paint(format!(" {} {}", self.count, entries)));
+        bits.push(theme.ui.punctuation.paint(","));
+        bits.push(Style::default().paint(format!(" {} bytes", self.bytes)));
+        bits
+    }
+}
+
+
+/// The totals for `--oneliner-summary`, which counts directories apart from
+/// everything else.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub struct Totals {
+    pub files: usize,
+    pub dirs: usize,
+    pub bytes: u64,
+}
+
+impl Totals {
+
+    /// Totals up the given files. Only the files that aren’t directories
+    /// have their sizes counted, the same as in the size column.
+    pub fn of_files(files: &[File<'_>]) -> Self {
+        let mut totals = Self::default();
+
+        for file in files {
+            if file.is_directory() {
+                totals.dirs += 1;
+            }
+            else {
+                totals.files += 1;
+            }
+
+            if let f::Size::Some(bytes) = file.size() {
+                totals.bytes += bytes;
+            }
+        }
+
+        totals
+    }
+
+    /// Adds another set of totals to this one.
+    pub fn add(&mut self, other: Self) {
+        self.files += other.files;
+        self.dirs += other.dirs;
+        self.bytes += other.bytes;
+    }
+
+    /// Renders the totals as one line of text, such as
+    /// `12 files, 3 dirs, 4.2M`.
+    pub fn render(&self, theme: &Theme, numerics: &NumericLocale) -> String {
+        use crate::output::table::SizeFormat;
+
+        let files = if self.files == 1 { "file" } else { "files" };
+        let dirs = if self.dirs == 1 { "dir" } else { "dirs" };
+        let size = f::Size::Some(self.bytes).render(theme, SizeFormat::DecimalBytes, numerics);
+
+        format!("{} {}, {} {}, {}", self.files, files, self.dirs, dirs, size.contents.strings())
+    }
+}
+
+
+/// Renders the sizes of the filesystem that the directory at the given path
+/// is on as a line of text, such as `src: 500G total, 120G used, 380G free`.
+pub fn render_space(space: Space, path: &Path, theme: &Theme, numerics: &NumericLocale) -> Vec<ANSIString<'static>> {
+    use crate::output::table::SizeFormat;
+
+    let mut bits = Vec::new();
+    escape(path.display().to_string(), &mut bits, theme.ui.header, theme.ui.control_char);
+    bits.push(theme.ui.punctuation.paint(":"));
+
+    let amounts = [ (space.total, "total"), (space.used, "used"), (space.free, "free") ];
+    for (index, &(bytes, label)) in amounts.iter().enumerate() {
+        if index > 0 {
+            bits.push(theme.ui.punctuation.paint(","));
+        }
+
+        bits.push(Style::default().paint(" "));
+        let size = f::Size::Some(bytes).render(theme, SizeFormat::DecimalBytes, numerics);
+        bits.extend(size.contents.iter().cloned());
+        bits.push(Style::default().paint(format!(" {}", label)));
+    }
+
+    bits
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fs::{Dir, DotFilter};
+    use crate::fs::feature::filesystem::SpaceSource;
+    use crate::theme::Options;
+    use ansi_term::ANSIStrings;
+    use crate::fs::test::TempDir;
+
+    #[test]
+    fn counts_sizes() {
+        let sizes = vec![ f::Size::Some(10), f::Size::None, f::Size::Some(5) ];
+        let summary = sizes.into_iter().collect::<Summary>();
+        assert_eq!(summary, Summary { count: 3, bytes: 15 });
+    }
+
+    #[test]
+    fn summarises_a_directory() {
+        let path = TempDir::new("summary");
+        std::fs::create_dir_all(path.join("subdir")).unwrap();
+        std::fs::write(path.join("one"), b"12345").unwrap();
+        std::fs::write(path.join("two"), b"1234567890").unwrap();
+
+        let dir = Dir::read_dir(path.clone()).unwrap();
+        let files = dir.files(DotFilter::JustFiles, None, false)
+                       .collect::<Result<Vec<_>, _>>()
+                       .unwrap();
+
+        let summary = Summary::of_files(&files);
+        assert_eq!(summary, Summary { count: 3, bytes: 15 });
+
+        let theme = Options::plain().to_theme(false);
+
+        let line = ANSIStrings(&summary.render(&path, &theme)).to_string();
+        assert_eq!(line, format!("{}: 3 entries, 15 bytes", path.display()));
+    }
+
+    struct MockSpace(Space);
+
+    impl SpaceSource for MockSpace {
+        fn space(&self, _path: &Path) -> std::io::Result<Space> {
+            Ok(self.0)
+        }
+    }
+
+    #[test]
+    fn filesystem_space() {
+        let source = MockSpace(Space { total: 1_000_000_000, used: 600_000_000, free: 350_000_000 });
+        let space = source.space(Path::new("/mnt")).unwrap();
+
+        let theme = Options::plain().to_theme(false);
+
+        let line = ANSIStrings(&render_space(space, Path::new("/mnt"), &theme, &NumericLocale::english())).to_string();
+        assert_eq!(line, "/mnt: 1.0G total, 600M used, 350M free");
+    }
+}
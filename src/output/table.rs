@@ -1,9 +1,12 @@
 use std::cmp::max;
+use std::collections::HashMap;
 use std::env;
 use std::ops::Deref;
 #[cfg(unix)]
 use std::sync::{Mutex, MutexGuard};
+use std::time::SystemTime;
 
+use ansi_term::Style;
 use datetime::TimeZone;
 use zoneinfo_compiled::{CompiledData, Result as TZResult};
 
@@ -12,11 +15,15 @@ use log::*;
 #[cfg(unix)]
 use users::UsersCache;
 
-use crate::fs::{File, fields as f};
+use crate::fs::{File, checksum, fields as f};
 use crate::fs::feature::git::GitCache;
+#[cfg(unix)]
+use crate::fs::feature::volume::Volumes;
+use crate::fs::feature::mount_info::Mounts;
+use crate::fs::feature::xattr;
 use crate::output::cell::TextCell;
-use crate::output::render::TimeRender;
-use crate::output::time::TimeFormat;
+use crate::output::render::{self, TimeRender};
+use crate::output::time::{TimeFormat, SubSeconds, format_age};
 use crate::theme::Theme;
 
 
@@ -24,9 +31,57 @@ use crate::theme::Theme;
 #[derive(PartialEq, Eq, Debug)]
 pub struct Options {
     pub size_format: SizeFormat,
+
+    /// The size below which files’ sizes get shown as plain numbers of
+    /// bytes, whatever the size format is, if there is one.
+    pub human_above: Option<u64>,
+
     pub time_format: TimeFormat,
+    pub sub_seconds: SubSeconds,
     pub user_format: UserFormat,
     pub columns: Columns,
+    pub column_widths: ColumnWidths,
+
+    /// The characters to draw borders between the columns with, if any.
+    pub borders: Option<Borders>,
+
+    /// Whether to mark the sizes of files that take up much less space on
+    /// disk than their length.
+    pub mark_sparse: bool,
+
+    /// Whether to colour dates by how long ago they were, instead of all
+    /// in the same colour.
+    pub colour_age: bool,
+
+    /// What to colour files’ sizes by.
+    pub size_colour: SizeColour,
+
+    /// Whether a right-aligned first column gets padded on the left, as
+    /// usual, rather than left-aligned so rows never start with spaces.
+    pub lead_pad: bool,
+}
+
+#[cfg(test)]
+impl Options {
+
+    /// Options that show just the given columns, with every other setting
+    /// at its simplest, so a test only has to change what it’s testing.
+    pub fn plain(columns: Columns) -> Self {
+        Self {
+            size_format: SizeFormat::JustBytes,
+            human_above: None,
+            time_format: TimeFormat::LongISO,
+            sub_seconds: SubSeconds::Hidden,
+            user_format: UserFormat::Numeric,
+            columns,
+            column_widths: ColumnWidths::default(),
+            borders: None,
+            mark_sparse: false,
+            colour_age: false,
+            size_colour: SizeColour::Magnitude,
+            lead_pad: true,
+        }
+    }
 }
 
 /// Extra columns to display in the table.
@@ -39,27 +94,93 @@ pub struct Columns {
 
     // The rest are just on/off
     pub inode: bool,
+    pub device_inode: bool,
+    pub file_version: bool,
     pub links: bool,
     pub blocks: bool,
     pub group: bool,
     pub git: bool,
     pub octal: bool,
 
+    // Shows the user and group in one column, rather than two:
+    pub merge_owner: bool,
+
+    // Shows how long ago the first timestamp column was:
+    pub age: bool,
+
+    // Off unless an algorithm is picked, as it reads every file:
+    pub checksum: Option<checksum::Algorithm>,
+
+    // Also off by default, as it reads the start of every file:
+    pub encoding: bool,
+    pub security_summary: bool,
+
     // Defaults to true:
     pub permissions: bool,
     pub filesize: bool,
     pub user: bool,
+
+    // Shows each file’s share of the total size, after the size column:
+    pub size_percent: bool,
+
+    // Shows each file’s size compared to the largest as a little bar:
+    pub size_bar: bool,
+
+    // Shows what the current user can do with each file, after its
+    // permissions:
+    pub effective_permissions: bool,
+
+    // Shows how many of the listed symlinks point at each file:
+    pub reverse_links: bool,
+
+    // Shows the name of the mount each file is on:
+    pub volume: bool,
+
+    // Shows each file’s type as a word, after its permissions:
+    pub file_type: bool,
+
+    // Shows whether each directory is a bind mount, and its propagation:
+    pub mount_info: bool,
 }
 
 impl Columns {
+
+    /// No columns at all, not even a timestamp, for tests to turn on just
+    /// the ones they need.
+    #[cfg(test)]
+    pub fn none() -> Self {
+        Self {
+            time_types: TimeTypes { modified: false, changed: false, accessed: false, created: false },
+            inode: false, device_inode: false, file_version: false, links: false,
+            blocks: false, group: false, git: false, octal: false, merge_owner: false,
+            age: false, checksum: None, encoding: false, security_summary: false,
+            permissions: false, filesize: false, user: false, size_percent: false,
+            size_bar: false, effective_permissions: false, reverse_links: false,
+            volume: false, file_type: false, mount_info: false,
+        }
+    }
+
+    /// Lists the columns to display, in order. Each column has a fixed
+    /// place, whichever others are turned on around it: the inode and
+    /// version first, then permissions, links, sizes, blocks, owners,
+    /// timestamps, anything read from the file’s contents, the volume and
+    /// mount info, and the Git status last. Turning a column off just closes up the gap.
     pub fn collect(&self, actually_enable_git: bool) -> Vec<Column> {
         let mut columns = Vec::with_capacity(4);
 
-        if self.inode {
+        if self.device_inode {
+            #[cfg(unix)]
+            columns.push(Column::DeviceInode);
+        }
+        else if self.inode {
             #[cfg(unix)]
             columns.push(Column::Inode);
         }
 
+        if self.file_version {
+            columns.push(Column::FileVersion);
+        }
+
         if self.octal {
             #[cfg(unix)]
             columns.push(Column::Octal);
@@ -69,26 +190,52 @@ impl Columns {
             columns.push(Column::Permissions);
         }
 
+        if self.file_type {
+            columns.push(Column::FileType);
+        }
+
+        if self.effective_permissions {
+            #[cfg(unix)]
+            columns.push(Column::EffectivePermissions);
+        }
+
         if self.links {
             #[cfg(unix)]
             columns.push(Column::HardLinks);
         }
 
+        if self.reverse_links {
+            #[cfg(unix)]
+            columns.push(Column::ReverseLinks);
+        }
+
         if self.filesize {
             columns.push(Column::FileSize);
         }
 
+        if self.size_percent {
+            columns.push(Column::SizePercent);
+        }
+
+        if self.size_bar {
+            columns.push(Column::SizeBar);
+        }
+
         if self.blocks {
             #[cfg(unix)]
             columns.push(Column::Blocks);
         }
 
-        if self.user {
+        if self.user && self.merge_owner {
+            #[cfg(unix)]
+            columns.push(Column::Owner);
+        }
+        else if self.user {
             #[cfg(unix)]
             columns.push(Column::User);
         }
 
-        if self.group {
+        if self.group && ! (self.user && self.merge_owner) {
             #[cfg(unix)]
             columns.push(Column::Group);
         }
@@ -101,14 +248,49 @@ impl Columns {
             columns.push(Column::Timestamp(TimeType::Changed));
         }
 
+        // Without birth times, a ‘created’ column would only be able to show
+        // the changed time under the wrong heading, so show it as what it is.
         if self.time_types.created {
-            columns.push(Column::Timestamp(TimeType::Created));
+            if *CREATED_TIMES_SUPPORTED {
+                columns.push(Column::Timestamp(TimeType::Created));
+            }
+            else if ! self.time_types.changed {
+                columns.push(Column::Timestamp(TimeType::Changed));
+            }
         }
 
         if self.time_types.accessed {
             columns.push(Column::Timestamp(TimeType::Accessed));
         }
 
+        if self.age {
+            let time_type = columns.iter()
+                                   .find_map(|c| if let Column::Timestamp(t) = c { Some(*t) } else { None })
+                                   .unwrap_or(TimeType::Modified);
+            columns.push(Column::Age(time_type));
+        }
+
+        if let Some(algorithm) = self.checksum {
+            columns.push(Column::Checksum(algorithm));
+        }
+
+        if self.encoding {
+            columns.push(Column::Encoding);
+        }
+
+        if self.security_summary {
+            columns.push(Column::SecuritySummary);
+        }
+
+        if self.volume {
+            #[cfg(unix)]
+            columns.push(Column::Volume);
+        }
+
+        if self.mount_info {
+            columns.push(Column::MountInfo);
+        }
+
         if self.git && actually_enable_git {
             columns.push(Column::GitStatus);
         }
@@ -122,8 +304,12 @@ impl Columns {
 #[derive(Debug, Copy, Clone)]
 pub enum Column {
     Permissions,
+    FileType,
     FileSize,
+    SizePercent,
+    SizeBar,
     Timestamp(TimeType),
+    Age(TimeType),
     #[cfg(unix)]
     Blocks,
     #[cfg(unix)]
@@ -131,12 +317,27 @@ pub enum Column {
     #[cfg(unix)]
     Group,
     #[cfg(unix)]
+    Owner,
+    #[cfg(unix)]
     HardLinks,
     #[cfg(unix)]
+    ReverseLinks,
+    #[cfg(unix)]
     Inode,
+    #[cfg(unix)]
+    DeviceInode,
+    FileVersion,
+    Checksum(checksum::Algorithm),
+    Encoding,
+    SecuritySummary,
+    #[cfg(unix)]
+    Volume,
+    MountInfo,
     GitStatus,
     #[cfg(unix)]
     Octal,
+    #[cfg(unix)]
+    EffectivePermissions,
 }
 
 /// Each column can pick its own **Alignment**. Usually, numbers are
@@ -154,9 +355,14 @@ impl Column {
     pub fn alignment(self) -> Alignment {
         match self {
             Self::FileSize   |
+            Self::SizePercent |
             Self::HardLinks  |
+            Self::ReverseLinks |
             Self::Inode      |
+            Self::DeviceInode |
             Self::Blocks     |
+            Self::Age(_)     |
+            Self::FileVersion |
             Self::GitStatus  => Alignment::Right,
             _                => Alignment::Left,
         }
@@ -166,6 +372,9 @@ impl Column {
     pub fn alignment(&self) -> Alignment {
         match self {
             Self::FileSize   |
+            Self::SizePercent |
+            Self::Age(_)     |
+            Self::FileVersion |
             Self::GitStatus  => Alignment::Right,
             _                => Alignment::Left,
         }
@@ -179,8 +388,12 @@ impl Column {
             Self::Permissions   => "Permissions",
             #[cfg(windows)]
             Self::Permissions   => "Mode",
+            Self::FileType      => "Type",
             Self::FileSize      => "Size",
+            Self::SizePercent   => "Size%",
+            Self::SizeBar       => "Bar",
             Self::Timestamp(t)  => t.header(),
+            Self::Age(_)        => "Age",
             #[cfg(unix)]
             Self::Blocks        => "Blocks",
             #[cfg(unix)]
@@ -188,12 +401,121 @@ impl Column {
             #[cfg(unix)]
             Self::Group         => "Group",
             #[cfg(unix)]
+            Self::Owner         => "Owner",
+            #[cfg(unix)]
             Self::HardLinks     => "Links",
             #[cfg(unix)]
+            Self::ReverseLinks  => "Symlinks",
+            #[cfg(unix)]
             Self::Inode         => "inode",
+            #[cfg(unix)]
+            Self::DeviceInode   => "Device:inode",
+            Self::FileVersion   => "Version",
+            Self::Checksum(checksum::Algorithm::Crc32)  => "CRC32",
+            Self::Checksum(checksum::Algorithm::Md5)    => "MD5",
+            Self::Encoding      => "Encoding",
+            Self::SecuritySummary => "Security",
+            #[cfg(unix)]
+            Self::Volume        => "Volume",
+            Self::MountInfo     => "Mount",
             Self::GitStatus     => "Git",
             #[cfg(unix)]
             Self::Octal         => "Octal",
+            #[cfg(unix)]
+            Self::EffectivePermissions => "Access",
+        }
+    }
+}
+
+
+/// Paints every part of a cell in the same style, for the summary row, and
+/// for sizes coloured by their file’s type.
+struct OneStyle(Style);
+
+impl render::SizeColours for OneStyle {
+    fn size(&self, _prefix: Option<number_prefix::Prefix>) -> Style { self.0 }
+    fn unit(&self, _prefix: Option<number_prefix::Prefix>) -> Style { self.0 }
+    fn no_size(&self)  -> Style { self.0 }
+    fn major(&self)    -> Style { self.0 }
+    fn comma(&self)    -> Style { self.0 }
+    fn minor(&self)    -> Style { self.0 }
+}
+
+impl render::LinksColours for OneStyle {
+    fn normal(&self)           -> Style { self.0 }
+    fn multi_link_file(&self)  -> Style { self.0 }
+}
+
+
+/// The limits on how wide each column is allowed to be.
+///
+/// Columns normally grow to fit their widest cell, but some can be pinned to
+/// a configured width. Every column’s limits get decided here, so the rest of
+/// the table code never needs to know which ones have been configured.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub struct ColumnWidths {
+
+    /// The width to fix the Git status column at, if any.
+    pub git: Option<usize>,
+}
+
+impl ColumnWidths {
+
+    /// The narrowest this column is allowed to be: it gets padded out to
+    /// this width even if all its cells are narrower.
+    pub fn minimum(self, column: Column) -> usize {
+        self.maximum(column).unwrap_or(0)
+    }
+
+    /// The widest this column is allowed to be, if there’s a limit. Cells that
+    /// are any wider get truncated.
+    pub fn maximum(self, column: Column) -> Option<usize> {
+        match column {
+            Column::GitStatus  => self.git,
+            _                  => None,
+        }
+    }
+
+    /// Truncates the given cell, if it’s too wide for its column.
+    pub fn limit(self, column: Column, cell: &mut TextCell) {
+        if let Some(max_width) = self.maximum(column) {
+            cell.truncate(max_width);
+        }
+    }
+}
+
+
+/// The characters used to draw borders between columns, and the line under
+/// the header.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Borders {
+
+    /// Box-drawing characters, for terminals that can show them.
+    Unicode,
+
+    /// Plain ASCII characters, for terminals that can’t.
+    Ascii,
+}
+
+impl Borders {
+    fn vertical(self) -> &'static str {
+        match self {
+            Self::Unicode  => "│",
+            Self::Ascii    => "|",
+        }
+    }
+
+    fn horizontal(self) -> char {
+        match self {
+            Self::Unicode  => '─',
+            Self::Ascii    => '-',
+        }
+    }
+
+    fn crossing(self) -> char {
+        match self {
+            Self::Unicode  => '┼',
+            Self::Ascii    => '+',
         }
     }
 }
@@ -214,6 +536,22 @@ pub enum SizeFormat {
 
     /// Do no formatting and just display the size as a number of bytes.
     JustBytes,
+
+    /// Format the file size using binary prefixes, followed by the same
+    /// size using decimal prefixes in parentheses.
+    BothBytes,
+}
+
+/// What to pick the colours of the size column by.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum SizeColour {
+
+    /// Colour each size by how big it is, which is the default.
+    Magnitude,
+
+    /// Colour each size the same as its file’s type, so directories’ sizes
+    /// look like directories, symlinks’ like symlinks, and so on.
+    ByType,
 }
 
 /// Formatting options for user and group.
@@ -223,6 +561,8 @@ pub enum UserFormat {
     Numeric,
     /// Show the name
     Name,
+    /// Show the name, cut down to at most this many characters
+    Truncated(usize),
 }
 
 impl Default for SizeFormat {
@@ -386,6 +726,22 @@ fn determine_time_zone() -> TZResult<TimeZone> {
 
 lazy_static! {
     static ref ENVIRONMENT: Environment = Environment::load_all();
+
+    /// Whether this platform can say when files were created. This gets
+    /// checked once, against the root directory, rather than for every file.
+    static ref CREATED_TIMES_SUPPORTED: bool = std::fs::metadata("/").and_then(|m| m.created()).is_ok();
+
+    /// The mount points on the system, for the mount-info column, which
+    /// also only get read the first time they’re needed.
+    static ref MOUNTS: Mounts = Mounts::load();
+}
+
+#[cfg(unix)]
+lazy_static! {
+
+    /// The mounts on the system, for the volume column, which only get
+    /// read the first time they’re needed.
+    static ref VOLUMES: Volumes = Volumes::load();
 }
 
 
@@ -395,22 +751,85 @@ pub struct Table<'a> {
     env: &'a Environment,
     widths: TableWidths,
     time_format: TimeFormat,
+    sub_seconds: SubSeconds,
+    now: SystemTime,
     size_format: SizeFormat,
+    human_above: Option<u64>,
     user_format: UserFormat,
+    column_widths: ColumnWidths,
+    borders: Option<Borders>,
+    mark_sparse: bool,
+    colour_age: bool,
+    size_colour: SizeColour,
+    lead_pad: bool,
     git: Option<&'a GitCache>,
+    git_tally: Option<f::GitTally>,
+    totals: Totals,
+
+    /// How many of the symlinks added so far point at each file, by its
+    /// device and inode, if the symlinks column is being shown.
+    link_targets: Option<HashMap<(u64, u64), u64>>,
+
+    /// The names of the mounts on the system, if the volume column is
+    /// being shown.
+    #[cfg(unix)]
+    volumes: Option<&'a Volumes>,
+
+    /// The mount points on the system, if the mount-info column is being
+    /// shown.
+    mounts: Option<&'a Mounts>,
+}
+
+/// Running sums of the columns that make sense to add up, for the summary
+/// row at the bottom of the table.
+#[derive(Copy, Clone, Default)]
+struct Totals {
+    size: u64,
+
+    /// The size of the largest file, which gets the fullest size bar.
+    largest: u64,
+    #[cfg(unix)]
+    links: u64,
 }
 
 #[derive(Clone)]
 pub struct Row {
     cells: Vec<TextCell>,
+
+    /// The size of the file this row is for, if it has one, so its share of
+    /// the total can be filled in once every file’s size is known.
+    size: Option<u64>,
+
+    /// The device and inode of the file this row is for, so the number of
+    /// symlinks pointing at it can be filled in once they’ve all been seen.
+    id: Option<(u64, u64)>,
 }
 
 impl<'a, 'f> Table<'a> {
     pub fn new(options: &'a Options, git: Option<&'a GitCache>, theme: &'a Theme) -> Table<'a> {
         let columns = options.columns.collect(git.is_some());
-        let widths = TableWidths::zero(columns.len());
+        let widths = TableWidths::minimums(&columns, options.column_widths);
         let env = &*ENVIRONMENT;
 
+        // Only keep a tally when the Git column is actually being shown.
+        let git_tally = if columns.iter().any(|c| matches!(c, Column::GitStatus)) { Some(f::GitTally::default()) }
+                                                                               else { None };
+
+        // The same goes for counting where symlinks point.
+        #[cfg(unix)]
+        let link_targets = if columns.iter().any(|c| matches!(c, Column::ReverseLinks)) { Some(HashMap::new()) }
+                                                                                    else { None };
+        #[cfg(windows)]
+        let link_targets = None;
+
+        // The list of mounts only gets read if it’s going to be used.
+        #[cfg(unix)]
+        let volumes = if columns.iter().any(|c| matches!(c, Column::Volume)) { Some(&*VOLUMES) }
+                                                                        else { None };
+
+        let mounts = if columns.iter().any(|c| matches!(c, Column::MountInfo)) { Some(&*MOUNTS) }
+                                                                          else { None };
+
         Table {
             theme,
             widths,
@@ -418,35 +837,191 @@ impl<'a, 'f> Table<'a> {
             git,
             env,
             time_format: options.time_format,
+            sub_seconds: options.sub_seconds,
+            now: SystemTime::now(),
             size_format: options.size_format,
+            human_above: options.human_above,
             user_format: options.user_format,
+            column_widths: options.column_widths,
+            borders: options.borders,
+            mark_sparse: options.mark_sparse,
+            colour_age: options.colour_age,
+            size_colour: options.size_colour,
+            lead_pad: options.lead_pad,
+            git_tally,
+            totals: Totals::default(),
+            link_targets,
+            #[cfg(unix)]
+            volumes,
+            mounts,
+        }
+    }
+
+    /// The width of a rendered row, up to where the file name starts.
+    pub fn total_width(&self) -> usize {
+        match self.borders {
+            Some(_)  => self.widths.total() + self.widths.len() * 2,
+            None     => self.widths.total(),
         }
     }
 
-    pub fn widths(&self) -> &TableWidths {
-        &self.widths
+    /// The line to draw under the header when there are borders, crossing
+    /// each one, and carrying on under file names of the given width.
+    pub fn header_rule(&self, name_width: usize) -> Option<TextCell> {
+        let borders = self.borders?;
+        let mut line = String::new();
+
+        for width in self.widths.iter() {
+            line.extend(std::iter::repeat(borders.horizontal()).take(width + 1));
+            line.push(borders.crossing());
+            line.push(borders.horizontal());
+        }
+
+        line.extend(std::iter::repeat(borders.horizontal()).take(name_width));
+        Some(TextCell::paint(self.theme.ui.punctuation, line))
     }
 
     pub fn header_row(&self) -> Row {
         let cells = self.columns.iter()
-                        .map(|c| TextCell::paint_str(self.theme.ui.header, c.header()))
+                        .map(|c| self.limited(*c, TextCell::paint_str(self.theme.ui.header, c.header())))
                         .collect();
 
-        Row { cells }
+        Row { cells, size: None, id: None }
     }
 
     pub fn row_for_file(&self, file: &File<'_>, xattrs: bool) -> Row {
         let cells = self.columns.iter()
-                        .map(|c| self.display(file, *c, xattrs))
+                        .map(|c| self.limited(*c, self.display(file, *c, xattrs)))
+                        .collect();
+
+        let size = match file.size() {
+            f::Size::Some(size)  => Some(size),
+            _                    => None,
+        };
+
+        Row { cells, size, id: file_id(file) }
+    }
+
+    /// A row for a file that couldn’t be statted, so there’s nothing to
+    /// show in any of its columns.
+    pub fn row_for_unstatted(&self) -> Row {
+        let cells = self.columns.iter()
+                        .map(|_| TextCell::blank(self.theme.ui.punctuation))
                         .collect();
 
-        Row { cells }
+        Row { cells, size: None, id: None }
+    }
+
+    fn limited(&self, column: Column, mut cell: TextCell) -> TextCell {
+        self.column_widths.limit(column, &mut cell);
+        cell
     }
 
     pub fn add_widths(&mut self, row: &Row) {
         self.widths.add_widths(row)
     }
 
+    /// Counts the given file’s Git status towards the tally, if the Git
+    /// column is being shown. A file whose status never got looked up is
+    /// counted as skipped instead.
+    pub fn add_to_git_tally(&mut self, file: &File<'_>) {
+        if let Some(mut tally) = self.git_tally {
+            let status = self.git_status(file);
+
+            if self.git.map_or(false, |g| g.is_over_limit(&file.path)) {
+                tally.skipped += 1;
+            }
+            else {
+                tally.add(status);
+            }
+
+            self.git_tally = Some(tally);
+        }
+    }
+
+    /// The tally of every file’s Git status, if the Git column is being
+    /// shown.
+    pub fn git_tally(&self) -> Option<f::GitTally> {
+        self.git_tally
+    }
+
+    /// Adds the given file’s size and link count to the running totals.
+    /// Directories have no size, so they don’t count towards it. If the
+    /// file is a symlink, the file it ends up at gets counted as a target.
+    pub fn add_to_totals(&mut self, file: &File<'_>) {
+        if let f::Size::Some(size) = file.size() {
+            self.totals.size += size;
+            self.totals.largest = self.totals.largest.max(size);
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+
+            self.totals.links += file.links().count;
+
+            if let Some(ref mut targets) = self.link_targets {
+                if file.is_link() {
+                    if let Ok(metadata) = std::fs::metadata(&file.path) {
+                        *targets.entry((metadata.dev(), metadata.ino())).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fills in the cells of a row that was made before every file had
+    /// been added, using every file added so far: the size percentage and
+    /// size bar cells, which need the total and largest sizes, and the
+    /// symlinks cell, which needs every symlink. Rows with no size, such as
+    /// the header or a directory’s, get no size cells.
+    pub fn add_relative_cells(&self, row: &mut Row) {
+        for (column, cell) in self.columns.iter().zip(row.cells.iter_mut()) {
+            match (column, row.size) {
+                (Column::SizePercent, Some(size)) => {
+                    let percent = TextCell::paint(self.theme.ui.size.number_byte, format!("{}%", percentage(size, self.totals.size)));
+                    *cell = self.limited(*column, percent);
+                }
+                (Column::SizeBar, Some(size)) => {
+                    let bar = TextCell::paint_str(self.theme.ui.size.number_byte, size_bar(size, self.totals.largest));
+                    *cell = self.limited(*column, bar);
+                }
+                #[cfg(unix)]
+                (Column::ReverseLinks, _) => {
+                    let count = row.id.and_then(|id| self.link_targets.as_ref()?.get(&id).copied()).unwrap_or(0);
+                    if count > 0 {
+                        *cell = self.limited(*column, TextCell::paint(self.theme.ui.links.normal, self.env.numeric.format_int(count)));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// A row with the totals of every file added so far in the size and
+    /// links columns, all in the header style, and every other cell blank.
+    pub fn summary_row(&self) -> Row {
+        let footer = OneStyle(self.theme.ui.header);
+
+        let cells = self.columns.iter()
+                        .map(|c| {
+                            let cell = match *c {
+                                Column::FileSize => {
+                                    f::Size::Some(self.totals.size).render_above(&footer, self.size_format, self.human_above, &self.env.numeric)
+                                }
+                                #[cfg(unix)]
+                                Column::HardLinks => {
+                                    f::Links { count: self.totals.links, multiple: false }.render(&footer, &self.env.numeric)
+                                }
+                                _ => TextCell::default(),
+                            };
+                            self.limited(*c, cell)
+                        })
+                        .collect();
+
+        Row { cells, size: None, id: None }
+    }
+
     fn permissions_plus(&self, file: &File<'_>, xattrs: bool) -> f::PermissionsPlus {
         f::PermissionsPlus {
             file_type: file.type_char(),
@@ -470,8 +1045,33 @@ impl<'a, 'f> Table<'a> {
             Column::Permissions => {
                 self.permissions_plus(file, xattrs).render(self.theme)
             }
+            Column::FileType => {
+                file.type_char().render_name(self.theme)
+            }
             Column::FileSize => {
-                file.size().render(self.theme, self.size_format, &self.env.numeric)
+                #[allow(unused_mut)]
+                let mut cell = match self.size_colour {
+                    SizeColour::Magnitude  => file.size().render_above(self.theme, self.size_format, self.human_above, &self.env.numeric),
+                    SizeColour::ByType     => file.size().render_above(&OneStyle(file.type_char().style(self.theme)), self.size_format, self.human_above, &self.env.numeric),
+                };
+
+                #[cfg(unix)]
+                if self.mark_sparse && file.is_file() {
+                    use std::os::unix::fs::MetadataExt;
+                    add_sparse_marker(&mut cell, self.theme.ui.punctuation, file.metadata.blocks(), file.metadata.len());
+                }
+
+                cell
+            }
+            Column::SizePercent | Column::SizeBar => {
+                // Filled in by `add_relative_cells` once the totals are known.
+                TextCell::blank(self.theme.ui.punctuation)
+            }
+            #[cfg(unix)]
+            Column::ReverseLinks => {
+                // Also filled in by `add_relative_cells`, once every
+                // symlink has been seen.
+                TextCell::blank(self.theme.ui.punctuation)
             }
             #[cfg(unix)]
             Column::HardLinks => {
@@ -482,6 +1082,34 @@ impl<'a, 'f> Table<'a> {
                 file.inode().render(self.theme.ui.inode)
             }
             #[cfg(unix)]
+            Column::DeviceInode => {
+                file.device_inode().render(self.theme.ui.inode)
+            }
+            Column::FileVersion => {
+                file.version().render(self.theme)
+            }
+            Column::Checksum(algorithm) => {
+                file.checksum(algorithm).render(self.theme)
+            }
+            Column::Encoding => {
+                file.encoding().render(self.theme)
+            }
+            Column::SecuritySummary => {
+                xattr::security_summary(file.path.as_path()).render(self.theme)
+            }
+            #[cfg(unix)]
+            Column::Volume => {
+                use std::os::unix::fs::MetadataExt;
+
+                match self.volumes {
+                    Some(volumes)  => volumes.volume(file.metadata.dev()).render(self.theme),
+                    None           => f::Volume(None).render(self.theme),
+                }
+            }
+            Column::MountInfo => {
+                self.mount_info(file).render(self.theme)
+            }
+            #[cfg(unix)]
             Column::Blocks => {
                 file.blocks().render(self.theme)
             }
@@ -493,6 +1121,11 @@ impl<'a, 'f> Table<'a> {
             Column::Group => {
                 file.group().render(self.theme, &*self.env.lock_users(), self.user_format)
             }
+            #[cfg(unix)]
+            Column::Owner => {
+                let owner = f::Owner { user: file.user(), group: file.group() };
+                owner.render(self.theme, &*self.env.lock_users(), self.user_format)
+            }
             Column::GitStatus => {
                 self.git_status(file).render(self.theme)
             }
@@ -500,22 +1133,52 @@ impl<'a, 'f> Table<'a> {
             Column::Octal => {
                 self.octal_permissions(file).render(self.theme.ui.octal)
             }
+            #[cfg(unix)]
+            Column::EffectivePermissions => {
+                file.effective_permissions().render(self.theme)
+            }
 
             Column::Timestamp(TimeType::Modified)  => {
-                file.modified_time().render(self.theme.ui.date, &self.env.tz, self.time_format)
+                let time = file.modified_time();
+                time.render(self.date_style(time), &self.env.tz, self.time_format, self.sub_seconds)
             }
             Column::Timestamp(TimeType::Changed)   => {
-                file.changed_time().render(self.theme.ui.date, &self.env.tz, self.time_format)
+                let time = file.changed_time();
+                time.render(self.date_style(time), &self.env.tz, self.time_format, self.sub_seconds)
             }
             Column::Timestamp(TimeType::Created)   => {
-                file.created_time().render(self.theme.ui.date, &self.env.tz, self.time_format)
+                let time = file.created_time();
+                time.render(self.date_style(time), &self.env.tz, self.time_format, self.sub_seconds)
             }
             Column::Timestamp(TimeType::Accessed)  => {
-                file.accessed_time().render(self.theme.ui.date, &self.env.tz, self.time_format)
+                let time = file.accessed_time();
+                time.render(self.date_style(time), &self.env.tz, self.time_format, self.sub_seconds)
+            }
+            Column::Age(time_type) => {
+                let time = match time_type {
+                    TimeType::Modified  => file.modified_time(),
+                    TimeType::Changed   => file.changed_time(),
+                    TimeType::Created   => file.created_time(),
+                    TimeType::Accessed  => file.accessed_time(),
+                };
+
+                match time {
+                    Some(time)  => TextCell::paint(self.theme.ui.date, format_age(time, self.now)),
+                    None        => TextCell::blank(self.theme.ui.punctuation),
+                }
             }
         }
     }
 
+    /// The style to paint a date in, which depends on how long ago it was
+    /// if dates are being coloured by their age.
+    fn date_style(&self, time: Option<SystemTime>) -> Style {
+        match time {
+            Some(time) if self.colour_age  => render::Recency::of(time, self.now).style(self.theme),
+            _                              => self.theme.ui.date,
+        }
+    }
+
     fn git_status(&self, file: &File<'_>) -> f::Git {
         debug!("Getting Git status for file {:?}", file.path);
 
@@ -524,6 +1187,21 @@ impl<'a, 'f> Table<'a> {
             .unwrap_or_default()
     }
 
+    /// Looks up what sort of mount a directory is. Mount points are listed
+    /// by their absolute paths, so the directory’s path has to be resolved
+    /// first; anything that isn’t a directory never counts.
+    fn mount_info(&self, file: &File<'_>) -> f::MountInfo {
+        let mounts = match self.mounts {
+            Some(m) if file.is_directory()  => m,
+            _                               => return f::MountInfo::NotMount,
+        };
+
+        match std::fs::canonicalize(&file.path) {
+            Ok(path)  => mounts.mount_info(&path),
+            Err(_)    => f::MountInfo::NotMount,
+        }
+    }
+
     pub fn render(&self, row: Row) -> TextCell {
         let mut cell = TextCell::default();
 
@@ -534,7 +1212,12 @@ impl<'a, 'f> Table<'a> {
         for (n, (this_cell, width)) in iter {
             let padding = width - *this_cell.width;
 
-            match self.columns[n].alignment() {
+            // Without leading padding, the first column gets its padding
+            // after it instead, which keeps the later columns lined up.
+            let alignment = if n == 0 && ! self.lead_pad { Alignment::Left }
+                                                      else { self.columns[n].alignment() };
+
+            match alignment {
                 Alignment::Left => {
                     cell.append(this_cell);
                     cell.add_spaces(padding);
@@ -545,6 +1228,11 @@ impl<'a, 'f> Table<'a> {
                 }
             }
 
+            if let Some(borders) = self.borders {
+                cell.add_spaces(1);
+                cell.push(self.theme.ui.punctuation.paint(borders.vertical()), 1);
+            }
+
             cell.add_spaces(1);
         }
 
@@ -552,6 +1240,62 @@ impl<'a, 'f> Table<'a> {
     }
 }
 
+/// The least number of bytes a file has to be missing from disk before it
+/// counts as sparse. Some filesystems keep small files inside their inodes,
+/// allocating no blocks at all, and those shouldn’t get marked.
+#[cfg(unix)]
+const SPARSE_THRESHOLD: u64 = 64 * 1024;
+
+/// The device and inode of a file, which symlinks to it resolve to. These
+/// are always there on Unix, but Windows has nothing to return.
+#[cfg(unix)]
+#[allow(clippy::unnecessary_wraps)]
+fn file_id(file: &File<'_>) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((file.metadata.dev(), file.metadata.ino()))
+}
+
+#[cfg(windows)]
+fn file_id(_file: &File<'_>) -> Option<(u64, u64)> {
+    None
+}
+
+/// Adds a ‘`~`’ after a file’s size if its allocated blocks, which are 512
+/// bytes each whatever the filesystem’s block size, cover less than half its
+/// length and fall short of it by at least the threshold.
+#[cfg(unix)]
+fn add_sparse_marker(cell: &mut TextCell, style: Style, blocks: u64, len: u64) {
+    let allocated = blocks.saturating_mul(512);
+    if allocated < len / 2 && len - allocated >= SPARSE_THRESHOLD {
+        cell.push(style.paint("~"), 1);
+    }
+}
+
+/// The given size as a percentage of the total, rounded to the nearest
+/// whole number. Nothing is any share of nothing, so an empty total gives 0.
+fn percentage(size: u64, total: u64) -> u64 {
+    if total == 0 {
+        return 0;
+    }
+
+    ((u128::from(size) * 100 + u128::from(total) / 2) / u128::from(total)) as u64
+}
+
+
+/// The blocks a size bar is drawn with, from emptiest to fullest.
+const SIZE_BARS: [&str; 8] = [ "▁", "▂", "▃", "▄", "▅", "▆", "▇", "█" ];
+
+/// A one-character bar for the given size, scaled so the largest size gets
+/// a full block. Empty files get no bar at all, rather than the smallest.
+fn size_bar(size: u64, largest: u64) -> &'static str {
+    if size == 0 || largest == 0 {
+        return " ";
+    }
+
+    let eighths = (u128::from(size) * 8 + u128::from(largest) - 1) / u128::from(largest);
+    SIZE_BARS[(eighths.clamp(1, 8) - 1) as usize]
+}
+
 
 pub struct TableWidths(Vec<usize>);
 
@@ -564,8 +1308,9 @@ impl Deref for TableWidths {
 }
 
 impl TableWidths {
-    pub fn zero(count: usize) -> Self {
-        Self(vec![0; count])
+    /// Starts each column off at the narrowest it’s allowed to be.
+    pub fn minimums(columns: &[Column], limits: ColumnWidths) -> Self {
+        Self(columns.iter().map(|c| limits.minimum(*c)).collect())
     }
 
     pub fn add_widths(&mut self, row: &Row) {
@@ -578,3 +1323,501 @@ impl TableWidths {
         self.0.len() + self.0.iter().sum::<usize>()
     }
 }
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ansi_term::Style;
+    use crate::fs::test::TempDir;
+    use crate::theme::Options as ThemeOptions;
+
+    fn plain_theme() -> Theme {
+        ThemeOptions::plain().to_theme(false)
+    }
+
+    #[test]
+    fn git_column_unlimited_by_default() {
+        let limits = ColumnWidths::default();
+        let mut cell = TextCell::paint_str(Style::default(), "NM");
+        limits.limit(Column::GitStatus, &mut cell);
+
+        assert_eq!(*cell.width, 2);
+        assert_eq!(limits.minimum(Column::GitStatus), 0);
+    }
+
+    #[test]
+    fn git_column_padded_to_width() {
+        let limits = ColumnWidths { git: Some(4) };
+        let columns = [ Column::FileSize, Column::GitStatus ];
+
+        let mut widths = TableWidths::minimums(&columns, limits);
+        widths.add_widths(&Row { cells: vec![ TextCell::paint_str(Style::default(), "12"),
+                                              TextCell::paint_str(Style::default(), "NM") ], size: None, id: None });
+
+        assert_eq!(&*widths, &[ 2, 4 ]);
+    }
+
+    #[test]
+    fn git_column_truncated_to_width() {
+        let limits = ColumnWidths { git: Some(1) };
+        let mut cell = TextCell::paint_str(Style::default(), "NM");
+        limits.limit(Column::GitStatus, &mut cell);
+
+        assert_eq!(*cell.width, 1);
+        assert_eq!(cell.strings().to_string(), "N");
+    }
+
+    #[test]
+    fn other_columns_not_truncated() {
+        let limits = ColumnWidths { git: Some(1) };
+        let mut cell = TextCell::paint_str(Style::default(), "1.2k");
+        limits.limit(Column::FileSize, &mut cell);
+
+        assert_eq!(*cell.width, 4);
+    }
+
+    #[cfg(unix)]
+    fn rendered_row(permissions: bool, borders: Option<Borders>) -> String {
+        let dir = TempDir::new(&format!("table-{}-{:?}", permissions, borders));
+        let path = dir.join("file");
+        std::fs::write(&path, b"contents").unwrap();
+        let file = File::from_args(path, None, None).unwrap();
+
+        let theme = plain_theme();
+
+        let options = Options { borders, .. Options::plain(Columns { time_types: TimeTypes::default(), permissions, filesize: true, .. Columns::none() }) };
+
+        let mut table = Table::new(&options, None, &theme);
+        let row = table.row_for_file(&file, false);
+        table.add_widths(&row);
+        table.render(row).strings().to_string()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn permissions_column() {
+        assert!(rendered_row(true, None).contains("rw"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn no_permissions_column() {
+        let rendered = rendered_row(false, None);
+        assert!(! rendered.contains("rw"));
+        assert!(rendered.starts_with('8'));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn borders_between_columns() {
+        assert!(rendered_row(true, Some(Borders::Unicode)).contains(" │ 8 │ "));
+        assert!(rendered_row(true, Some(Borders::Ascii)).contains(" | 8 | "));
+        assert!(! rendered_row(true, None).contains('│'));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn directory_size_is_blank() {
+        let path = TempDir::new("dir-size");
+        std::fs::create_dir_all(path.join("dir")).unwrap();
+        std::fs::write(path.join("file"), b"contents").unwrap();
+
+        let theme = plain_theme();
+
+        let options = Options::plain(Columns { filesize: true, .. Columns::none() });
+
+        // A directory’s length is a filesystem detail rather than anything
+        // meaningful, so its size cell is a dash, whatever the length is.
+        let table = Table::new(&options, None, &theme);
+        let sizes = [ "dir", "file" ].iter().map(|name| {
+            let file = File::from_args(path.join(name), None, None).unwrap();
+            table.row_for_file(&file, false).cells[0].strings().to_string()
+        }).collect::<Vec<_>>();
+
+        assert_eq!(sizes, vec![ "-", "8" ]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn summary_row_totals() {
+        let path = TempDir::new("summary-row");
+        std::fs::write(path.join("one"), b"contents").unwrap();
+        std::fs::write(path.join("two"), b"more contents").unwrap();
+
+        let theme = plain_theme();
+
+        let options = Options::plain(Columns { links: true, permissions: true, filesize: true, .. Columns::none() });
+
+        let mut table = Table::new(&options, None, &theme);
+        for name in &[ "one", "two" ] {
+            let file = File::from_args(path.join(name), None, None).unwrap();
+            table.add_to_totals(&file);
+        }
+
+        let summary = table.summary_row();
+        let cells = summary.cells.iter().map(|c| c.strings().to_string()).collect::<Vec<_>>();
+        assert_eq!(cells, vec![ "", "2", "21" ]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn size_bars() {
+        let path = TempDir::new("size-bar");
+        std::fs::write(path.join("small"), [ 0; 1 ]).unwrap();
+        std::fs::write(path.join("large"), [ 0; 8 ]).unwrap();
+
+        let theme = plain_theme();
+
+        let options = Options::plain(Columns { size_bar: true, .. Columns::none() });
+
+        let mut table = Table::new(&options, None, &theme);
+        let files = [ "small", "large" ].iter()
+                        .map(|name| File::from_args(path.join(name), None, None).unwrap())
+                        .collect::<Vec<_>>();
+
+        for file in &files {
+            table.add_to_totals(file);
+        }
+
+        let bars = files.iter().map(|file| {
+            let mut row = table.row_for_file(file, false);
+            table.add_relative_cells(&mut row);
+            row.cells[0].strings().to_string()
+        }).collect::<Vec<_>>();
+
+        assert_eq!(bars, vec![ "▁", "█" ]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn reverse_links() {
+        use std::os::unix::fs::symlink;
+
+        let path = TempDir::new("reverse-links");
+        std::fs::write(path.join("target"), b"contents").unwrap();
+        std::fs::write(path.join("other"), b"contents").unwrap();
+        symlink("target", path.join("one")).unwrap();
+        symlink("target", path.join("two")).unwrap();
+
+        let theme = plain_theme();
+
+        let options = Options::plain(Columns { reverse_links: true, .. Columns::none() });
+
+        let mut table = Table::new(&options, None, &theme);
+        let files = [ "one", "other", "target", "two" ].iter()
+                        .map(|name| File::from_args(path.join(name), None, None).unwrap())
+                        .collect::<Vec<_>>();
+
+        for file in &files {
+            table.add_to_totals(file);
+        }
+
+        let counts = files.iter().map(|file| {
+            let mut row = table.row_for_file(file, false);
+            table.add_relative_cells(&mut row);
+            row.cells[0].strings().to_string()
+        }).collect::<Vec<_>>();
+
+        assert_eq!(counts, vec![ "-", "-", "2", "-" ]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn volume_column() {
+        use std::os::unix::fs::MetadataExt;
+
+        let dir = TempDir::new("volume");
+        let path = dir.join("file");
+        std::fs::write(&path, b"contents").unwrap();
+        let file = File::from_args(path, None, None).unwrap();
+
+        let theme = plain_theme();
+
+        let options = Options::plain(Columns { volume: true, .. Columns::none() });
+
+        // Rather than the real list of mounts, use one that puts the file
+        // on a mount of its own.
+        let volumes = Volumes::from_mounts(vec![ (file.metadata.dev(), std::path::Path::new("/mnt/usb")) ]);
+        let no_volumes = Volumes::default();
+        let mut table = Table::new(&options, None, &theme);
+        table.volumes = Some(&volumes);
+        assert_eq!(table.row_for_file(&file, false).cells[0].strings().to_string(), "usb");
+
+        table.volumes = Some(&no_volumes);
+        assert_eq!(table.row_for_file(&file, false).cells[0].strings().to_string(), "-");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn file_type_column() {
+        let path = TempDir::new("type-column");
+        std::fs::create_dir_all(path.join("dir")).unwrap();
+        std::os::unix::fs::symlink("dir", path.join("link")).unwrap();
+
+        let theme = plain_theme();
+
+        let options = Options::plain(Columns { file_type: true, .. Columns::none() });
+
+        let table = Table::new(&options, None, &theme);
+        let type_of = |name| {
+            let file = File::from_args(path.join(name), None, None).unwrap();
+            table.row_for_file(&file, false).cells[0].strings().to_string()
+        };
+
+        assert_eq!(type_of("dir"), "dir");
+        assert_eq!(type_of("link"), "link");
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn mount_info_column() {
+        let path = TempDir::new("mount-info");
+        std::fs::create_dir_all(path.join("bound")).unwrap();
+        std::fs::create_dir_all(path.join("plain")).unwrap();
+
+        let theme = plain_theme();
+
+        let options = Options::plain(Columns { mount_info: true, .. Columns::none() });
+
+        // Rather than the real list of mounts, use one that has the first
+        // directory bind-mounted from somewhere else.
+        let bound = std::fs::canonicalize(path.join("bound")).unwrap();
+        let mountinfo = format!("22 1 8:1 / / rw shared:1 - ext4 /dev/sda1 rw\n46 22 8:1 /srv/data {} rw - ext4 /dev/sda1 rw\n", bound.display());
+        let mounts = Mounts::from_mountinfo(&mountinfo);
+
+        let mut table = Table::new(&options, None, &theme);
+        table.mounts = Some(&mounts);
+        let mount_of = |name| {
+            let file = File::from_args(path.join(name), None, None).unwrap();
+            table.row_for_file(&file, false).cells[0].strings().to_string()
+        };
+
+        assert_eq!(mount_of("bound"), "bp");
+        assert_eq!(mount_of("plain"), "-");
+    }
+
+    #[test]
+    fn size_bar_scaling() {
+        assert_eq!(size_bar(0, 8), " ");
+        assert_eq!(size_bar(4, 8), "▄");
+        assert_eq!(size_bar(5, 8), "▅");
+        assert_eq!(size_bar(1, 1_000_000), "▁");
+        assert_eq!(size_bar(8, 8), "█");
+    }
+
+    #[test]
+    fn created_column_header() {
+        let columns = Columns { time_types: TimeTypes { modified: false, changed: false, accessed: false, created: true }, .. Columns::none() };
+
+        let headers = columns.collect(false).into_iter().map(Column::header).collect::<Vec<_>>();
+        if *CREATED_TIMES_SUPPORTED {
+            assert_eq!(headers, vec![ "Date Created" ]);
+        }
+        else {
+            assert_eq!(headers, vec![ "Date Changed" ]);
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn optional_column_order() {
+        let columns = Columns { inode: true, security_summary: true, links: true, git: true, permissions: true, filesize: true, .. Columns::none() };
+
+        let headers = |columns: &Columns, git| columns.collect(git).into_iter().map(Column::header).collect::<Vec<_>>();
+        assert_eq!(headers(&columns, true),  vec![ "inode", "Permissions", "Links", "Size", "Security", "Git" ]);
+        assert_eq!(headers(&columns, false), vec![ "inode", "Permissions", "Links", "Size", "Security" ]);
+
+        // Taking columns out of the middle leaves the rest where they were.
+        let fewer = Columns { permissions: false, links: false, .. columns };
+        assert_eq!(headers(&fewer, true), vec![ "inode", "Size", "Security", "Git" ]);
+
+        // The header and the rows are built from the same list, so each
+        // cell lines up with its heading.
+        let dir = TempDir::new("column-order");
+        let path = dir.join("file");
+        std::fs::write(&path, b"contents").unwrap();
+        let file = File::from_args(path, None, None).unwrap();
+
+        let theme = plain_theme();
+
+        let options = Options::plain(fewer);
+
+        let table = Table::new(&options, None, &theme);
+        let header = table.header_row();
+        let row = table.row_for_file(&file, false);
+        assert_eq!(header.cells.len(), 3);
+        assert_eq!(row.cells.len(), 3);
+        assert_eq!(header.cells[1].strings().to_string(), "Size");
+        assert_eq!(row.cells[1].strings().to_string(), "8");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn merged_owner_column() {
+        let columns = Columns { group: true, merge_owner: true, user: true, .. Columns::none() };
+
+        let headers = columns.collect(false).into_iter().map(Column::header).collect::<Vec<_>>();
+        assert_eq!(headers, vec![ "Owner" ]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn age_column() {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let dir = TempDir::new("age");
+        let path = dir.join("file");
+        std::fs::write(&path, b"contents").unwrap();
+
+        // Three days and an hour ago, to the second.
+        let mtime = SystemTime::now() - Duration::from_secs(60 * 60 * (24 * 3 + 1));
+        let seconds = mtime.duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let mtime = UNIX_EPOCH + Duration::from_secs(seconds);
+        let c_path = CString::new(path.as_os_str().as_bytes()).unwrap();
+        let times = [ libc::timeval { tv_sec: seconds as libc::time_t, tv_usec: 0 }; 2 ];
+        assert_eq!(unsafe { libc::utimes(c_path.as_ptr(), times.as_ptr()) }, 0);
+
+        let file = File::from_args(path, None, None).unwrap();
+
+        let theme = plain_theme();
+
+        let options = Options::plain(Columns { time_types: TimeTypes::default(), age: true, .. Columns::none() });
+
+        let mut table = Table::new(&options, None, &theme);
+        let row = table.row_for_file(&file, false);
+        table.add_widths(&row);
+        let rendered = table.render(row).strings().to_string();
+
+        let date = Some(mtime).render(Style::default(), &ENVIRONMENT.tz, TimeFormat::LongISO, SubSeconds::Hidden);
+        assert!(rendered.contains(&*date.strings().to_string()));
+        assert!(rendered.contains("3d"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn colour_age_dates() {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+        use std::time::{Duration, UNIX_EPOCH};
+        use crate::theme::{Options as ThemeOptions, UseColours};
+
+        let path = TempDir::new("colour-age");
+
+        // An hour, three days, and thirty days ago.
+        let hour = 60 * 60;
+        let files = [ ("recent", hour), ("this-week", 72 * hour), ("old", 30 * 24 * hour) ].iter().map(|(name, age)| {
+            std::fs::write(path.join(name), b"").unwrap();
+
+            let seconds = (SystemTime::now() - Duration::from_secs(*age)).duration_since(UNIX_EPOCH).unwrap().as_secs();
+            let c_path = CString::new(path.join(name).as_os_str().as_bytes()).unwrap();
+            let times = [ libc::timeval { tv_sec: seconds as libc::time_t, tv_usec: 0 }; 2 ];
+            assert_eq!(unsafe { libc::utimes(c_path.as_ptr(), times.as_ptr()) }, 0);
+
+            File::from_args(path.join(name), None, None).unwrap()
+        }).collect::<Vec<_>>();
+
+        let theme = ThemeOptions { use_colours: UseColours::Always, .. ThemeOptions::plain() }.to_theme(false);
+
+        let options = Options { colour_age: true, .. Options::plain(Columns { time_types: TimeTypes::default(), .. Columns::none() }) };
+
+        let table = Table::new(&options, None, &theme);
+        let styles = files.iter()
+                          .map(|file| *table.row_for_file(file, false).cells[0].contents[0].style_ref())
+                          .collect::<Vec<_>>();
+
+        let ages = &theme.ui.date_ages;
+        assert_eq!(styles, vec![ ages.today, ages.this_week, ages.older ]);
+        assert_ne!(styles[0], styles[1]);
+        assert_ne!(styles[1], styles[2]);
+        assert_ne!(styles[0], styles[2]);
+    }
+
+    #[test]
+    fn size_coloured_by_type() {
+        use crate::theme::{Options as ThemeOptions, UseColours};
+
+        let path = TempDir::new("size-colour");
+        let dir = File::from_args(path.clone(), None, None).unwrap();
+
+        let theme = ThemeOptions { use_colours: UseColours::Always, .. ThemeOptions::plain() }.to_theme(false);
+
+        let mut options = Options { size_colour: SizeColour::ByType, .. Options::plain(Columns { filesize: true, .. Columns::none() }) };
+
+        let table = Table::new(&options, None, &theme);
+        assert_eq!(table.row_for_file(&dir, false).cells[0], TextCell::blank(theme.ui.filekinds.directory));
+
+        options.size_colour = SizeColour::Magnitude;
+        let table = Table::new(&options, None, &theme);
+        assert_eq!(table.row_for_file(&dir, false).cells[0], TextCell::blank(theme.ui.punctuation));
+    }
+
+    #[test]
+    fn first_column_without_lead_pad() {
+        let path = TempDir::new("lead-pad");
+        std::fs::write(path.join("small"), b"1").unwrap();
+        std::fs::write(path.join("large"), vec![ 0; 12345 ]).unwrap();
+
+        let files = [ "small", "large" ].iter()
+                        .map(|name| File::from_args(path.join(name), None, None).unwrap())
+                        .collect::<Vec<_>>();
+
+        let theme = plain_theme();
+
+        let mut options = Options { lead_pad: false, .. Options::plain(Columns { encoding: true, filesize: true, .. Columns::none() }) };
+
+        let render_all = |options: &Options| {
+            let mut table = Table::new(options, None, &theme);
+            let rows = files.iter().map(|file| table.row_for_file(file, false)).collect::<Vec<_>>();
+            for row in &rows {
+                table.add_widths(row);
+            }
+
+            rows.into_iter().map(|row| table.render(row).strings().to_string()).collect::<Vec<_>>()
+        };
+
+        // The sizes are right-aligned, and the encodings after them should
+        // still line up with each other.
+        let lines = render_all(&options);
+        assert!(lines.iter().all(|line| ! line.starts_with(' ')));
+        assert!(lines[0].contains("ascii"));
+        assert_eq!(lines[0].find("ascii"), lines[1].find("binary"));
+
+        options.lead_pad = true;
+        let padded = render_all(&options);
+        assert!(padded[0].starts_with(' '));
+        assert_eq!(padded[0].find("ascii"), padded[1].find("binary"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn sparse_file_marked() {
+        // A gigabyte-long file with only eight blocks written to.
+        let mut cell = TextCell::paint_str(Style::default(), "1.1G");
+        add_sparse_marker(&mut cell, Style::default(), 8, 1_073_741_824);
+
+        assert_eq!(cell.contents.strings().to_string(), "1.1G~");
+        assert_eq!(*cell.width, 5);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn full_file_unmarked() {
+        let mut cell = TextCell::paint_str(Style::default(), "1.0M");
+        add_sparse_marker(&mut cell, Style::default(), 2048, 1_048_576);
+
+        assert_eq!(cell.contents.strings().to_string(), "1.0M");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn inline_file_unmarked() {
+        // Small files can be kept in the inode, with no blocks at all.
+        let mut cell = TextCell::paint_str(Style::default(), "100");
+        add_sparse_marker(&mut cell, Style::default(), 0, 100);
+
+        assert_eq!(cell.contents.strings().to_string(), "100");
+    }
+}
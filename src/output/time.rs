@@ -24,14 +24,14 @@ use unicode_width::UnicodeWidthStr;
 /// prints month names as numbers.
 ///
 /// Currently exa does not support *custom* styles, where the user enters a
-/// format string in an environment variable or something. Just these four.
+/// format string in an environment variable or something. Just these five.
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
 pub enum TimeFormat {
 
     /// The **default format** uses the user’s locale to print month names,
-    /// and specifies the timestamp down to the minute for recent times, and
-    /// day for older times.
-    DefaultFormat,
+    /// unless it’s been given some to use instead, and specifies the
+    /// timestamp down to the minute for recent times, and day for older times.
+    DefaultFormat(MonthNames),
 
     /// Use the **ISO format**, which specifies the timestamp down to the
     /// minute for recent times, and day for older times. It uses a number
@@ -42,30 +42,99 @@ pub enum TimeFormat {
     /// minute using only numbers, without needing the locale or year.
     LongISO,
 
+    /// Use the **ISO format with seconds**, which always specifies the
+    /// timestamp down to the second using only numbers, however old it is.
+    ISOSeconds,
+
     /// Use the **full ISO format**, which specifies the timestamp down to the
     /// millisecond and includes its offset down to the minute. This too uses
     /// only numbers so doesn’t require any special consideration.
     FullISO,
 }
 
+/// Where the default format gets its month names from.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum MonthNames {
+
+    /// Use the names from the user’s locale, as set by `LC_TIME`.
+    Locale,
+
+    /// Use these abbreviations, from January to December.
+    Fixed(&'static [&'static str; 12]),
+}
+
+impl MonthNames {
+
+    /// The month abbreviations for the given locale, such as `fr_FR.UTF-8`,
+    /// going by its language. Languages without a set of names here, as
+    /// well as the `C` and `POSIX` locales, get the English ones.
+    pub fn for_locale(locale: &str) -> Self {
+        let language = locale.split(&[ '_', '.', '@', '-' ][..]).next().unwrap_or_default();
+
+        let names = match language {
+            "de"  => &GERMAN_MONTHS,
+            "es"  => &SPANISH_MONTHS,
+            "fr"  => &FRENCH_MONTHS,
+            "it"  => &ITALIAN_MONTHS,
+            "nl"  => &DUTCH_MONTHS,
+            "pt"  => &PORTUGUESE_MONTHS,
+            "sv"  => &SWEDISH_MONTHS,
+            _     => &ENGLISH_MONTHS,
+        };
+
+        Self::Fixed(names)
+    }
+}
+
+static ENGLISH_MONTHS:    [&str; 12] = [ "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec" ];
+static GERMAN_MONTHS:     [&str; 12] = [ "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez" ];
+static SPANISH_MONTHS:    [&str; 12] = [ "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic" ];
+static FRENCH_MONTHS:     [&str; 12] = [ "janv.", "févr.", "mars", "avril", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.", "déc." ];
+static ITALIAN_MONTHS:    [&str; 12] = [ "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic" ];
+static DUTCH_MONTHS:      [&str; 12] = [ "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec" ];
+static PORTUGUESE_MONTHS: [&str; 12] = [ "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez" ];
+static SWEDISH_MONTHS:    [&str; 12] = [ "jan", "feb", "mar", "apr", "maj", "jun", "jul", "aug", "sep", "okt", "nov", "dec" ];
+
+
+/// Whether to display the fractional part of each timestamp’s second, which
+/// most formats leave out even when the filesystem records it.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum SubSeconds {
+
+    /// Display timestamps only as precisely as their format does.
+    Hidden,
+
+    /// Add the seconds and nanoseconds to formats that display the time of
+    /// day. Formats that only display the date are left alone.
+    Nanoseconds,
+}
+
+impl Default for SubSeconds {
+    fn default() -> Self {
+        Self::Hidden
+    }
+}
+
 // There are two different formatting functions because local and zoned
 // timestamps are separate types.
 
 impl TimeFormat {
-    pub fn format_local(self, time: SystemTime) -> String {
+    pub fn format_local(self, time: SystemTime, sub: SubSeconds) -> String {
         match self {
-            Self::DefaultFormat  => default_local(time),
-            Self::ISOFormat      => iso_local(time),
-            Self::LongISO        => long_local(time),
+            Self::DefaultFormat(months)  => default_local(time, sub, months),
+            Self::ISOFormat      => iso_local(time, sub),
+            Self::LongISO        => long_local(time, sub),
+            Self::ISOSeconds     => seconds_local(time, sub),
             Self::FullISO        => full_local(time),
         }
     }
 
-    pub fn format_zoned(self, time: SystemTime, zone: &TimeZone) -> String {
+    pub fn format_zoned(self, time: SystemTime, zone: &TimeZone, sub: SubSeconds) -> String {
         match self {
-            Self::DefaultFormat  => default_zoned(time, zone),
-            Self::ISOFormat      => iso_zoned(time, zone),
-            Self::LongISO        => long_zoned(time, zone),
+            Self::DefaultFormat(months)  => default_zoned(time, zone, sub, months),
+            Self::ISOFormat      => iso_zoned(time, zone, sub),
+            Self::LongISO        => long_zoned(time, zone, sub),
+            Self::ISOSeconds     => seconds_zoned(time, zone, sub),
             Self::FullISO        => full_zoned(time, zone),
         }
     }
@@ -73,17 +142,49 @@ impl TimeFormat {
 
 
 #[allow(trivial_numeric_casts)]
-fn default_local(time: SystemTime) -> String {
+fn default_local(time: SystemTime, sub: SubSeconds, months: MonthNames) -> String {
     let date = LocalDateTime::at(systemtime_epoch(time));
-    let date_format = get_dateformat(&date);
-    date_format.format(&date, &*LOCALE)
+    let mut datestamp = default_datestamp(&date, months);
+
+    if is_recent(&date) {
+        datestamp.push_str(&after_minutes(&date, time, sub));
+    }
+
+    datestamp
 }
 
 #[allow(trivial_numeric_casts)]
-fn default_zoned(time: SystemTime, zone: &TimeZone) -> String {
+fn default_zoned(time: SystemTime, zone: &TimeZone, sub: SubSeconds, months: MonthNames) -> String {
     let date = zone.to_zoned(LocalDateTime::at(systemtime_epoch(time)));
-    let date_format = get_dateformat(&date);
-    date_format.format(&date, &*LOCALE)
+    let mut datestamp = default_datestamp(&date, months);
+
+    if is_recent(&date) {
+        datestamp.push_str(&after_minutes(&date, time, sub));
+    }
+
+    datestamp
+}
+
+/// The default format’s timestamp, up to the minute for recent times and
+/// the year otherwise. Month names that don’t come from the locale get
+/// padded to the width of the widest, the same as the locale’s ones do.
+#[allow(trivial_numeric_casts)]
+fn default_datestamp(date: &LocalDateTime, months: MonthNames) -> String {
+    let names = match months {
+        MonthNames::Locale        => return get_dateformat(date).format(date, &*LOCALE),
+        MonthNames::Fixed(names)  => names,
+    };
+
+    let name = names[date.month() as usize - 1];
+    let widest = names.iter().map(|n| UnicodeWidthStr::width(*n)).max().unwrap_or(0);
+    let padding = " ".repeat(widest - UnicodeWidthStr::width(name));
+
+    if is_recent(date) {
+        format!("{:>2} {}{} {:02}:{:02}", date.day(), name, padding, date.hour(), date.minute())
+    }
+    else {
+        format!("{:>2} {}{} {:>5}", date.day(), name, padding, date.year())
+    }
 }
 
 fn get_dateformat(date: &LocalDateTime) -> &'static DateFormat<'static> {
@@ -98,19 +199,35 @@ fn get_dateformat(date: &LocalDateTime) -> &'static DateFormat<'static> {
 }
 
 #[allow(trivial_numeric_casts)]
-fn long_local(time: SystemTime) -> String {
+fn long_local(time: SystemTime, sub: SubSeconds) -> String {
     let date = LocalDateTime::at(systemtime_epoch(time));
-    format!("{:04}-{:02}-{:02} {:02}:{:02}",
+    format!("{:04}-{:02}-{:02} {:02}:{:02}{}",
             date.year(), date.month() as usize, date.day(),
-            date.hour(), date.minute())
+            date.hour(), date.minute(), after_minutes(&date, time, sub))
 }
 
 #[allow(trivial_numeric_casts)]
-fn long_zoned(time: SystemTime, zone: &TimeZone) -> String {
+fn long_zoned(time: SystemTime, zone: &TimeZone, sub: SubSeconds) -> String {
     let date = zone.to_zoned(LocalDateTime::at(systemtime_epoch(time)));
-    format!("{:04}-{:02}-{:02} {:02}:{:02}",
+    format!("{:04}-{:02}-{:02} {:02}:{:02}{}",
+            date.year(), date.month() as usize, date.day(),
+            date.hour(), date.minute(), after_minutes(&date, time, sub))
+}
+
+#[allow(trivial_numeric_casts)]
+fn seconds_local(time: SystemTime, sub: SubSeconds) -> String {
+    let date = LocalDateTime::at(systemtime_epoch(time));
+    format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}{}",
             date.year(), date.month() as usize, date.day(),
-            date.hour(), date.minute())
+            date.hour(), date.minute(), date.second(), after_seconds(time, sub))
+}
+
+#[allow(trivial_numeric_casts)]
+fn seconds_zoned(time: SystemTime, zone: &TimeZone, sub: SubSeconds) -> String {
+    let date = zone.to_zoned(LocalDateTime::at(systemtime_epoch(time)));
+    format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}{}",
+            date.year(), date.month() as usize, date.day(),
+            date.hour(), date.minute(), date.second(), after_seconds(time, sub))
 }
 
 #[allow(trivial_numeric_casts)]
@@ -135,13 +252,13 @@ fn full_zoned(time: SystemTime, zone: &TimeZone) -> String {
 }
 
 #[allow(trivial_numeric_casts)]
-fn iso_local(time: SystemTime) -> String {
+fn iso_local(time: SystemTime, sub: SubSeconds) -> String {
     let date = LocalDateTime::at(systemtime_epoch(time));
 
     if is_recent(&date) {
-        format!("{:02}-{:02} {:02}:{:02}",
+        format!("{:02}-{:02} {:02}:{:02}{}",
                 date.month() as usize, date.day(),
-                date.hour(), date.minute())
+                date.hour(), date.minute(), after_minutes(&date, time, sub))
     }
     else {
         format!("{:04}-{:02}-{:02}",
@@ -150,13 +267,13 @@ fn iso_local(time: SystemTime) -> String {
 }
 
 #[allow(trivial_numeric_casts)]
-fn iso_zoned(time: SystemTime, zone: &TimeZone) -> String {
+fn iso_zoned(time: SystemTime, zone: &TimeZone, sub: SubSeconds) -> String {
     let date = zone.to_zoned(LocalDateTime::at(systemtime_epoch(time)));
 
     if is_recent(&date) {
-        format!("{:02}-{:02} {:02}:{:02}",
+        format!("{:02}-{:02} {:02}:{:02}{}",
                 date.month() as usize, date.day(),
-                date.hour(), date.minute())
+                date.hour(), date.minute(), after_minutes(&date, time, sub))
     }
     else {
         format!("{:04}-{:02}-{:02}",
@@ -165,6 +282,25 @@ fn iso_zoned(time: SystemTime, zone: &TimeZone) -> String {
 }
 
 
+/// The extra precision to add to a timestamp that ends with its minutes:
+/// nothing, or its seconds and nanoseconds.
+fn after_minutes<D: TimePiece>(date: &D, time: SystemTime, sub: SubSeconds) -> String {
+    match sub {
+        SubSeconds::Hidden       => String::new(),
+        SubSeconds::Nanoseconds  => format!(":{:02}{}", date.second(), after_seconds(time, sub)),
+    }
+}
+
+/// The extra precision to add to a timestamp that ends with its seconds:
+/// nothing, or its nanoseconds.
+fn after_seconds(time: SystemTime, sub: SubSeconds) -> String {
+    match sub {
+        SubSeconds::Hidden       => String::new(),
+        SubSeconds::Nanoseconds  => format!(".{:09}", systemtime_nanos(time)),
+    }
+}
+
+
 fn systemtime_epoch(time: SystemTime) -> i64 {
     time.duration_since(UNIX_EPOCH)
         .map(|t| t.as_secs() as i64)
@@ -241,3 +377,100 @@ lazy_static! {
         "{2>:D} {:M} {5>:Y}"
     ).unwrap();
 }
+
+
+/// Formats how long before `now` the given time was, in the largest unit
+/// that fits, such as `3d` for three days or `2w` for two weeks. Times in
+/// the future count as no time ago.
+pub fn format_age(time: SystemTime, now: SystemTime) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR:   u64 = 60 * MINUTE;
+    const DAY:    u64 = 24 * HOUR;
+    const WEEK:   u64 = 7 * DAY;
+    const YEAR:   u64 = 365 * DAY;
+
+    let seconds = now.duration_since(time).map_or(0, |d| d.as_secs());
+
+    if      seconds < MINUTE  { format!("{}s", seconds) }
+    else if seconds < HOUR    { format!("{}m", seconds / MINUTE) }
+    else if seconds < DAY     { format!("{}h", seconds / HOUR) }
+    else if seconds < WEEK    { format!("{}d", seconds / DAY) }
+    else if seconds < YEAR    { format!("{}w", seconds / WEEK) }
+    else                      { format!("{}y", seconds / YEAR) }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    fn timestamp() -> SystemTime {
+        UNIX_EPOCH + Duration::new(1_000_000_000, 123_456_789)
+    }
+
+    #[test]
+    fn ages() {
+        let now = timestamp();
+        let ago = |seconds| format_age(now - Duration::from_secs(seconds), now);
+
+        assert_eq!(ago(0), "0s");
+        assert_eq!(ago(59), "59s");
+        assert_eq!(ago(60 * 5), "5m");
+        assert_eq!(ago(60 * 60 * 23), "23h");
+        assert_eq!(ago(60 * 60 * 24 * 3 + 100), "3d");
+        assert_eq!(ago(60 * 60 * 24 * 15), "2w");
+        assert_eq!(ago(60 * 60 * 24 * 800), "2y");
+    }
+
+    #[test]
+    fn future_age() {
+        assert_eq!(format_age(timestamp() + Duration::from_secs(100), timestamp()), "0s");
+    }
+
+    #[test]
+    fn fixed_month_names() {
+        let january = UNIX_EPOCH + Duration::from_secs(979_562_040);  // 2001-01-15 12:34
+
+        let english = TimeFormat::DefaultFormat(MonthNames::for_locale("C"));
+        let spanish = TimeFormat::DefaultFormat(MonthNames::for_locale("es_ES.UTF-8"));
+        let french = TimeFormat::DefaultFormat(MonthNames::for_locale("fr"));
+
+        assert_eq!(english.format_local(january, SubSeconds::Hidden), "15 Jan  2001");
+        assert_eq!(spanish.format_local(january, SubSeconds::Hidden), "15 ene  2001");
+        assert_eq!(french.format_local(january, SubSeconds::Hidden),  "15 janv.  2001");
+    }
+
+    #[test]
+    fn unknown_locale_months() {
+        assert_eq!(MonthNames::for_locale("xx_XX"), MonthNames::Fixed(&ENGLISH_MONTHS));
+        assert_eq!(MonthNames::for_locale(""), MonthNames::Fixed(&ENGLISH_MONTHS));
+    }
+
+    #[test]
+    fn iso_seconds() {
+        assert_eq!(TimeFormat::ISOSeconds.format_local(timestamp(), SubSeconds::Hidden), "2001-09-09 01:46:40");
+    }
+
+    #[test]
+    fn full_iso_nanoseconds() {
+        assert_eq!(TimeFormat::FullISO.format_local(timestamp(), SubSeconds::Hidden), "2001-09-09 01:46:40.123456789");
+    }
+
+    #[test]
+    fn long_iso_nanoseconds() {
+        assert_eq!(TimeFormat::LongISO.format_local(timestamp(), SubSeconds::Hidden),      "2001-09-09 01:46");
+        assert_eq!(TimeFormat::LongISO.format_local(timestamp(), SubSeconds::Nanoseconds), "2001-09-09 01:46:40.123456789");
+    }
+
+    #[test]
+    fn iso_seconds_nanoseconds() {
+        assert_eq!(TimeFormat::ISOSeconds.format_local(timestamp(), SubSeconds::Nanoseconds), "2001-09-09 01:46:40.123456789");
+    }
+
+    #[test]
+    fn full_iso_unchanged() {
+        assert_eq!(TimeFormat::FullISO.format_local(timestamp(), SubSeconds::Nanoseconds),
+                   TimeFormat::FullISO.format_local(timestamp(), SubSeconds::Hidden));
+    }
+}
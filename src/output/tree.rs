@@ -148,6 +148,13 @@ impl TreeParams {
     pub fn is_at_root(&self) -> bool {
         self.depth.0 == 0
     }
+
+    /// The width of the tree parts, and the space after them, that get
+    /// drawn before an entry with these parameters.
+    pub fn width(&self) -> usize {
+        if self.is_at_root() { 0 }
+                        else { self.depth.0 * 4 + 1 }
+    }
 }
 
 impl TreeDepth {
@@ -0,0 +1,110 @@
+//! Listing the same paths over and over again, for `--watch`.
+//!
+//! There’s no portable way to be told when a directory changes, so exa just
+//! looks at the directories every so often and compares what it finds with
+//! what it found last time. This loops until exa gets killed, which is
+//! usually by the user pressing Ctrl-C.
+
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+
+/// Clears the terminal and moves the cursor to its top-left corner, so each
+/// listing replaces the one before it.
+pub const CLEAR_SCREEN: &str = "\x1B[2J\x1B[H";
+
+/// How long to wait between looking at the directories.
+const INTERVAL: Duration = Duration::from_millis(500);
+
+
+/// Something that decides when it’s time to list everything again.
+pub trait Trigger {
+
+    /// Waits until something has changed, returning whether the paths
+    /// should be listed again, or `false` if watching should stop.
+    fn wait(&mut self) -> bool;
+}
+
+
+/// A trigger that polls the given paths until they change.
+pub struct Poll {
+    paths: Vec<PathBuf>,
+    last: Vec<Stamp>,
+}
+
+/// What a path looked like when it was last looked at: its name, when it was
+/// last modified, and how big it was.
+type Stamp = (PathBuf, Option<SystemTime>, u64);
+
+impl Poll {
+
+    /// Starts watching the given paths, remembering what they look like now.
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let last = stamps(&paths);
+        Self { paths, last }
+    }
+}
+
+impl Trigger for Poll {
+    fn wait(&mut self) -> bool {
+        loop {
+            thread::sleep(INTERVAL);
+
+            let stamps = stamps(&self.paths);
+            if stamps != self.last {
+                self.last = stamps;
+                return true;
+            }
+        }
+    }
+}
+
+
+/// Stamps each path and, for directories, each of their entries. Files
+/// deeper down aren’t looked at, so a change there doesn’t count until it
+/// touches something at the top.
+fn stamps(paths: &[PathBuf]) -> Vec<Stamp> {
+    let mut stamps = Vec::new();
+
+    for path in paths {
+        stamps.push(stamp(path.clone()));
+
+        if let Ok(entries) = fs::read_dir(path) {
+            let mut entries = entries.filter_map(Result::ok)
+                                     .map(|e| stamp(e.path()))
+                                     .collect::<Vec<_>>();
+
+            entries.sort();
+            stamps.extend(entries);
+        }
+    }
+
+    stamps
+}
+
+fn stamp(path: PathBuf) -> Stamp {
+    let metadata = fs::symlink_metadata(&path).ok();
+    let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+    let size = metadata.map_or(0, |m| m.len());
+    (path, modified, size)
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fs::test::TempDir;
+
+    #[test]
+    fn new_file_changes_stamps() {
+        let path = TempDir::new("watch-stamps");
+
+        let before = stamps(&[ path.clone() ]);
+        assert_eq!(before, stamps(&[ path.clone() ]));
+
+        std::fs::write(path.join("new"), b"").unwrap();
+        assert_ne!(before, stamps(&[ path.clone() ]));
+    }
+}
@@ -66,6 +66,12 @@ impl UiStyles {
                 conflicted:  Red.normal(),
             },
 
+            date_ages: DateAges {
+                today:      Cyan.bold(),
+                this_week:  Blue.normal(),
+                older:      Blue.dimmed(),
+            },
+
             punctuation:  Fixed(244).normal(),
             date:         Blue.normal(),
             inode:        Purple.normal(),
@@ -77,7 +83,41 @@ impl UiStyles {
             control_char:         Red.normal(),
             broken_symlink:       Red.normal(),
             broken_path_overlay:  Style::default().underline(),
+            unreadable_overlay:   Style::default().dimmed().strikethrough(),
+        }
+    }
+
+    /// The default theme, adjusted for terminals with light backgrounds.
+    /// Yellow text is all but invisible on white, so it gets swapped for
+    /// darker shades, as do the paler colours in the size gradient.
+    pub fn light_theme(scale: ColourScale) -> Self {
+        let mut ui = Self::default_theme(scale);
+
+        ui.filekinds.pipe          = Fixed(136).normal();
+        ui.filekinds.block_device  = Fixed(136).bold();
+        ui.filekinds.char_device   = Fixed(136).bold();
+        ui.filekinds.special       = Fixed(136).normal();
+
+        ui.perms.user_read         = Fixed(136).bold();
+        ui.perms.group_read        = Fixed(136).normal();
+        ui.perms.other_read        = Fixed(136).normal();
+
+        ui.users.user_you          = Fixed(136).bold();
+        ui.users.group_yours       = Fixed(136).bold();
+
+        ui.links.multi_link_file   = Red.on(Fixed(229));
+        ui.git.renamed             = Fixed(136).normal();
+        ui.punctuation             = Fixed(242).normal();
+
+        if scale == ColourScale::Gradient {
+            ui.size.number_byte = Fixed(28).normal();
+            ui.size.number_kilo = Fixed(64).normal();
+            ui.size.number_mega = Fixed(136).normal();
+            ui.size.number_giga = Fixed(130).normal();
+            ui.size.number_huge = Fixed(124).normal();
         }
+
+        ui
     }
 }
 
@@ -21,7 +21,15 @@ pub struct Options {
 
     pub colour_scale: ColourScale,
 
+    pub colour_by: ColourBy,
+
+    pub colour_scheme: ColourScheme,
+
     pub definitions: Definitions,
+
+    /// Whether a pattern for a file’s whole compound extension, such as
+    /// `*.tar.gz`, should win over any other pattern matching its name.
+    pub compound_extensions: bool,
 }
 
 /// Under what circumstances we should display coloured, rather than plain,
@@ -50,6 +58,32 @@ pub enum ColourScale {
     Gradient,
 }
 
+/// Which set of default colours to use, depending on whether they’re going
+/// to be shown on a dark or a light background.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum ColourScheme {
+
+    /// Colours that stand out against a dark background, which is what
+    /// most terminals have.
+    Dark,
+
+    /// Colours that stay readable against a light background, with the
+    /// yellows in particular swapped for darker shades.
+    Light,
+}
+
+/// What to look at when picking a colour for a regular file.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum ColourBy {
+
+    /// Use the file’s name and extension, which is cheap.
+    Extension,
+
+    /// Read the start of the file and use what it contains, falling back
+    /// to its name if it can’t be read.
+    Content,
+}
+
 #[derive(PartialEq, Eq, Debug, Default)]
 pub struct Definitions {
     pub ls: Option<String>,
@@ -64,8 +98,23 @@ pub struct Theme {
 
 impl Options {
 
+    /// Options with colours turned off and everything else at its default,
+    /// for tests that only care about the text being output.
+    #[cfg(test)]
+    pub fn plain() -> Self {
+        Self {
+            use_colours: UseColours::Never,
+            colour_scale: ColourScale::Fixed,
+            colour_by: ColourBy::Extension,
+            colour_scheme: ColourScheme::Dark,
+            definitions: Definitions::default(),
+            compound_extensions: false,
+        }
+    }
+
     #[allow(trivial_casts)]   // the `as Box<_>` stuff below warns about this for some reason
     pub fn to_theme(&self, isatty: bool) -> Theme {
+        use crate::info::content::ContentColours;
         use crate::info::filetype::FileExtensions;
 
         if self.use_colours == UseColours::Never || (self.use_colours == UseColours::Automatic && ! isatty) {
@@ -75,8 +124,12 @@ impl Options {
         }
 
         // Parse the environment variables into colours and extension mappings
-        let mut ui = UiStyles::default_theme(self.colour_scale);
-        let (exts, use_default_filetypes) = self.definitions.parse_color_vars(&mut ui);
+        let mut ui = match self.colour_scheme {
+            ColourScheme::Dark   => UiStyles::default_theme(self.colour_scale),
+            ColourScheme::Light  => UiStyles::light_theme(self.colour_scale),
+        };
+        let (mut exts, use_default_filetypes) = self.definitions.parse_color_vars(&mut ui);
+        exts.compound = self.compound_extensions;
 
         // Use between 0 and 2 file name highlighters
         let exts = match (exts.is_non_empty(), use_default_filetypes) {
@@ -86,6 +139,12 @@ impl Options {
             ( true,  true)  => Box::new((exts, FileExtensions))  as Box<_>,
         };
 
+        // Sniffing file contents takes priority over everything by name
+        let exts = match self.colour_by {
+            ColourBy::Extension  => exts,
+            ColourBy::Content    => Box::new((ContentColours, exts)) as Box<_>,
+        };
+
         Theme { ui, exts }
     }
 }
@@ -171,19 +230,35 @@ where A: FileColours,
     }
 }
 
+// A boxed colouriser can itself go in a pair, so extra ones can be put in
+// front of whichever set was picked above.
+impl<T> FileColours for Box<T>
+where T: FileColours + ?Sized,
+{
+    fn colour_file(&self, file: &File<'_>) -> Option<Style> {
+        (**self).colour_file(file)
+    }
+}
+
 
 #[derive(PartialEq, Debug, Default)]
 struct ExtensionMappings {
     mappings: Vec<(glob::Pattern, Style)>,
+    compound: bool,
 }
 
 // Loop through backwards so that colours specified later in the list override
-// colours specified earlier, like we do with options and strict mode
+// colours specified earlier, like we do with options and strict mode.
+// With `--compound-extensions`, a pattern for the file’s whole compound
+// extension gets looked for first, so `*.tar.gz` beats a later `*.gz`.
 
 impl FileColours for ExtensionMappings {
     fn colour_file(&self, file: &File<'_>) -> Option<Style> {
-        self.mappings.iter().rev()
-            .find(|t| t.0.matches(&file.name))
+        let compound_ext = file.compound_ext.as_deref().filter(|_| self.compound);
+
+        compound_ext
+            .and_then(|ext| self.mappings.iter().rev().find(|t| Self::is_for_extension(&t.0, ext)))
+            .or_else(|| self.mappings.iter().rev().find(|t| t.0.matches(&file.name)))
             .map (|t| t.1)
     }
 }
@@ -196,6 +271,14 @@ impl ExtensionMappings {
     fn add(&mut self, pattern: glob::Pattern, style: Style) {
         self.mappings.push((pattern, style))
     }
+
+    /// Whether the pattern is exactly ‘`*.`’ followed by the given
+    /// extension, ignoring case, as extensions are lowercased.
+    fn is_for_extension(pattern: &glob::Pattern, ext: &str) -> bool {
+        pattern.as_str()
+               .strip_prefix("*.")
+               .map_or(false, |e| e.eq_ignore_ascii_case(ext))
+    }
 }
 
 
@@ -206,6 +289,45 @@ impl render::BlocksColours for Theme {
     fn no_blocks(&self)    -> Style { self.ui.punctuation }
 }
 
+impl render::VersionColours for Theme {
+    fn version(&self)     -> Style { self.ui.inode }
+    fn no_version(&self)  -> Style { self.ui.punctuation }
+}
+
+impl render::SecurityColours for Theme {
+    fn attributes(&self)   -> Style { self.ui.perms.attribute }
+    fn acl(&self)          -> Style { self.ui.perms.special_other }
+    fn no_security(&self)  -> Style { self.ui.punctuation }
+}
+
+impl render::ChecksumColours for Theme {
+    fn checksum(&self)     -> Style { self.ui.inode }
+    fn no_checksum(&self)  -> Style { self.ui.punctuation }
+}
+
+impl render::DateAgeColours for Theme {
+    fn today(&self)      -> Style { self.ui.date_ages.today }
+    fn this_week(&self)  -> Style { self.ui.date_ages.this_week }
+    fn older(&self)      -> Style { self.ui.date_ages.older }
+}
+
+impl render::EncodingColours for Theme {
+    fn encoding(&self)     -> Style { self.ui.inode }
+    fn binary(&self)       -> Style { self.ui.filekinds.executable }
+    fn no_encoding(&self)  -> Style { self.ui.punctuation }
+}
+
+#[cfg(unix)]
+impl render::VolumeColours for Theme {
+    fn volume(&self)     -> Style { self.ui.inode }
+    fn no_volume(&self)  -> Style { self.ui.punctuation }
+}
+
+impl render::MountInfoColours for Theme {
+    fn mount(&self)     -> Style { self.ui.inode }
+    fn no_mount(&self)  -> Style { self.ui.punctuation }
+}
+
 impl render::FiletypeColours for Theme {
     fn normal(&self)       -> Style { self.ui.filekinds.normal }
     fn directory(&self)    -> Style { self.ui.filekinds.directory }
@@ -240,6 +362,11 @@ impl render::LinksColours for Theme {
     fn multi_link_file(&self)  -> Style { self.ui.links.multi_link_file }
 }
 
+#[cfg(unix)]
+impl render::OwnerColours for Theme {
+    fn separator(&self)  -> Style { self.ui.punctuation }
+}
+
 impl render::PermissionsColours for Theme {
     fn dash(&self)               -> Style { self.ui.punctuation }
     fn user_read(&self)          -> Style { self.ui.perms.user_read }
@@ -301,6 +428,8 @@ impl FileNameColours for Theme {
     fn broken_control_char(&self) -> Style { apply_overlay(self.ui.control_char,   self.ui.broken_path_overlay) }
     fn control_char(&self)        -> Style { self.ui.control_char }
     fn symlink_path(&self)        -> Style { self.ui.symlink_path }
+    fn empty_marker(&self)        -> Style { self.ui.punctuation }
+    fn unreadable_filename(&self, style: Style) -> Style { apply_overlay(style, self.ui.unreadable_overlay) }
     fn executable_file(&self)     -> Style { self.ui.filekinds.executable }
 
     fn colour_file(&self, file: &File<'_>) -> Style {
@@ -376,7 +505,7 @@ mod customs_test {
                 };
 
                 let (result, _reset) = definitions.parse_color_vars(&mut UiStyles::default());
-                assert_eq!(ExtensionMappings { mappings }, result);
+                assert_eq!(ExtensionMappings { mappings, compound: false }, result);
             }
         };
         ($name:ident:  ls $ls:expr, exa $exa:expr  =>  colours $expected:ident -> $process_expected:expr, exts $mappings:expr) => {
@@ -397,7 +526,7 @@ mod customs_test {
 
                 let mut meh = UiStyles::colourful(false);
                 let (result, _reset) = definitions.parse_color_vars(&vars, &mut meh);
-                assert_eq!(ExtensionMappings { mappings }, result);
+                assert_eq!(ExtensionMappings { mappings, compound: false }, result);
                 assert_eq!($expected, meh);
             }
         };
@@ -498,6 +627,7 @@ mod customs_test {
     test!(exa_lp:  ls "", exa "lp=38;5;133"  =>  colours c -> { c.symlink_path              = Fixed(133).normal(); });
     test!(exa_cc:  ls "", exa "cc=38;5;134"  =>  colours c -> { c.control_char              = Fixed(134).normal(); });
     test!(exa_bo:  ls "", exa "bO=4"         =>  colours c -> { c.broken_path_overlay       = Style::default().underline(); });
+    test!(exa_uo:  ls "", exa "uO=9"         =>  colours c -> { c.unreadable_overlay        = Style::default().strikethrough(); });
 
     // All the while, LS_COLORS treats them as filenames:
     test!(ls_uu:   ls "uu=38;5;117", exa ""  =>  exts [ ("uu", Fixed(117).normal()) ]);
@@ -528,3 +658,36 @@ mod customs_test {
     test!(ls_overwrite:  ls "pi=31:pi=32:pi=33", exa ""  =>  colours c -> { c.filekinds.pipe = Yellow.normal(); });
     test!(exa_overwrite: ls "", exa "da=36:da=35:da=34"  =>  colours c -> { c.date = Blue.normal(); });
 }
+
+
+#[cfg(test)]
+mod compound_test {
+    use super::*;
+    use ansi_term::Colour::*;
+    use crate::fs::test::TempDir;
+
+    fn colour_of(name: &str, compound_extensions: bool) -> Option<Style> {
+        let path = TempDir::new(&format!("compound-colour-{}", name));
+        std::fs::write(path.join(name), b"").unwrap();
+        let file = File::from_args(path.join(name), None, None).unwrap();
+
+        let definitions = Definitions { ls: None, exa: Some("*.tar.gz=31:*.gz=32".into()) };
+        let theme = Options { use_colours: UseColours::Always, definitions, compound_extensions, .. Options::plain() }.to_theme(false);
+        theme.exts.colour_file(&file)
+    }
+
+    #[test]
+    fn last_pattern_wins() {
+        assert_eq!(colour_of("backup.tar.gz", false), Some(Green.normal()));
+    }
+
+    #[test]
+    fn compound_pattern_wins() {
+        assert_eq!(colour_of("backup.TAR.gz", true), Some(Red.normal()));
+    }
+
+    #[test]
+    fn falls_back_to_other_patterns() {
+        assert_eq!(colour_of("backup.gz", true), Some(Green.normal()));
+    }
+}
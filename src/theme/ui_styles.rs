@@ -1,3 +1,5 @@
+use std::io::{self, Write};
+
 use ansi_term::Style;
 
 use crate::theme::lsc::Pair;
@@ -13,6 +15,7 @@ pub struct UiStyles {
     pub users:      Users,
     pub links:      Links,
     pub git:        Git,
+    pub date_ages:  DateAges,
 
     pub punctuation:  Style,
     pub date:         Style,
@@ -25,6 +28,7 @@ pub struct UiStyles {
     pub control_char:         Style,
     pub broken_symlink:       Style,
     pub broken_path_overlay:  Style,
+    pub unreadable_overlay:   Style,
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
@@ -104,10 +108,109 @@ pub struct Git {
     pub conflicted: Style,
 }
 
+/// The colours dates get painted in by how long ago they were, with
+/// `--colour-age`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DateAges {
+    pub today:      Style,
+    pub this_week:  Style,
+    pub older:      Style,
+}
+
 impl UiStyles {
     pub fn plain() -> Self {
         Self::default()
     }
+
+    /// Every style in this set of colours, each with a name saying what it
+    /// gets used for, in the order `--debug-colours` lists them.
+    pub fn named_styles(&self) -> Vec<(&'static str, Style)> {
+        vec![
+            ("normal",                self.filekinds.normal),
+            ("directory",             self.filekinds.directory),
+            ("symlink",               self.filekinds.symlink),
+            ("pipe",                  self.filekinds.pipe),
+            ("block_device",          self.filekinds.block_device),
+            ("char_device",           self.filekinds.char_device),
+            ("socket",                self.filekinds.socket),
+            ("special",               self.filekinds.special),
+            ("executable",            self.filekinds.executable),
+
+            ("user_read",             self.perms.user_read),
+            ("user_write",            self.perms.user_write),
+            ("user_execute_file",     self.perms.user_execute_file),
+            ("user_execute_other",    self.perms.user_execute_other),
+            ("group_read",            self.perms.group_read),
+            ("group_write",           self.perms.group_write),
+            ("group_execute",         self.perms.group_execute),
+            ("other_read",            self.perms.other_read),
+            ("other_write",           self.perms.other_write),
+            ("other_execute",         self.perms.other_execute),
+            ("special_user_file",     self.perms.special_user_file),
+            ("special_other",         self.perms.special_other),
+            ("attribute",             self.perms.attribute),
+
+            ("size_major",            self.size.major),
+            ("size_minor",            self.size.minor),
+            ("number_byte",           self.size.number_byte),
+            ("number_kilo",           self.size.number_kilo),
+            ("number_mega",           self.size.number_mega),
+            ("number_giga",           self.size.number_giga),
+            ("number_huge",           self.size.number_huge),
+            ("unit_byte",             self.size.unit_byte),
+            ("unit_kilo",             self.size.unit_kilo),
+            ("unit_mega",             self.size.unit_mega),
+            ("unit_giga",             self.size.unit_giga),
+            ("unit_huge",             self.size.unit_huge),
+
+            ("user_you",              self.users.user_you),
+            ("user_someone_else",     self.users.user_someone_else),
+            ("group_yours",           self.users.group_yours),
+            ("group_not_yours",       self.users.group_not_yours),
+
+            ("links",                 self.links.normal),
+            ("multi_link_file",       self.links.multi_link_file),
+
+            ("git_new",               self.git.new),
+            ("git_modified",          self.git.modified),
+            ("git_deleted",           self.git.deleted),
+            ("git_renamed",           self.git.renamed),
+            ("git_typechange",        self.git.typechange),
+            ("git_ignored",           self.git.ignored),
+            ("git_conflicted",        self.git.conflicted),
+
+            ("date_today",            self.date_ages.today),
+            ("date_this_week",        self.date_ages.this_week),
+            ("date_older",            self.date_ages.older),
+
+            ("punctuation",           self.punctuation),
+            ("date",                  self.date),
+            ("inode",                 self.inode),
+            ("blocks",                self.blocks),
+            ("header",                self.header),
+            ("octal",                 self.octal),
+            ("symlink_path",          self.symlink_path),
+            ("control_char",          self.control_char),
+            ("broken_symlink",        self.broken_symlink),
+            ("broken_path_overlay",   self.broken_path_overlay),
+            ("unreadable_overlay",    self.unreadable_overlay),
+        ]
+    }
+
+    /// Writes one line for each style, with its name, a sample of text
+    /// painted in it, and its escape codes, for `--debug-colours`. Styles
+    /// that don’t change anything have their codes shown as `none`.
+    pub fn render_dump<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        for (name, style) in self.named_styles() {
+            let prefix = style.prefix().to_string();
+            let codes = prefix.trim_start_matches("\x1B[").trim_end_matches('m');
+            let codes = if codes.is_empty() { "none" } else { codes };
+
+            writeln!(w, "{:<20} {} {}", name, style.paint("sample"), codes)?;
+        }
+
+        Ok(())
+    }
 }
 
 
@@ -192,6 +295,7 @@ impl UiStyles {
             "lp" => self.symlink_path             = pair.to_style(),
             "cc" => self.control_char             = pair.to_style(),
             "bO" => self.broken_path_overlay      = pair.to_style(),
+            "uO" => self.unreadable_overlay       = pair.to_style(),
 
              _   => return false,
         }
@@ -215,3 +319,28 @@ impl UiStyles {
         self.size.unit_huge = style;
     }
 }
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::theme::ColourScale;
+
+    #[test]
+    fn dump_includes_directories() {
+        let mut output = Vec::new();
+        UiStyles::default_theme(ColourScale::Fixed).render_dump(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.lines().any(|line| line == "directory            \x1B[1;34msample\x1B[0m 1;34"));
+    }
+
+    #[test]
+    fn dump_of_plain_styles() {
+        let mut output = Vec::new();
+        UiStyles::plain().render_dump(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.lines().any(|line| line == "directory            sample none"));
+    }
+}